@@ -36,6 +36,134 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/api/v1/admin/command": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["list_admin_commands"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/admin/command/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_admin_command"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/admin/item-demand/import": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["queue_item_demand_import"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/admin/manifest/{res_version}/import": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["queue_asset_mapping_import"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/admin/version/check": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["queue_version_check"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/admin/version/{id}/download": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["queue_version_download"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/admin/version/{id}/reset": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get?: never;
+        put?: never;
+        post: operations["queue_version_reset"];
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/asset/history": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_asset_history"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/api/v1/bundle": {
         parameters: {
             query?: never;
@@ -52,6 +180,38 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/api/v1/bundle/history": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_bundle_history"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/bundle/search": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["search_bundles"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/api/v1/bundle/{id}": {
         parameters: {
             query?: never;
@@ -68,6 +228,55 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/api/v1/bundle/{id}/content": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_bundle_content"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/channel": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Lists the tracked channels, the primary one first. */
+        get: operations["list_channels"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/diff": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["diff_versions"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/api/v1/docker/launch": {
         parameters: {
             query?: never;
@@ -84,6 +293,22 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/api/v1/file/{hash}/content": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_file_content"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/api/v1/files": {
         parameters: {
             query?: never;
@@ -132,6 +357,22 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/api/v1/manifest/diff": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["diff_manifests"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/api/v1/manifest/{version_id}/children": {
         parameters: {
             query?: never;
@@ -148,6 +389,22 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/api/v1/manifest/{version_id}/dependencies": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_manifest_dependencies"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/api/v1/manifest/{version_id}/detail": {
         parameters: {
             query?: never;
@@ -180,6 +437,38 @@ export interface paths {
         patch?: never;
         trace?: never;
     };
+    "/api/v1/stats": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_stats"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/storage/replication": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_replication_status"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
     "/api/v1/version": {
         parameters: {
             query?: never;
@@ -187,121 +476,1594 @@ export interface paths {
             path?: never;
             cookie?: never;
         };
-        get: operations["list_version"];
-        put?: never;
-        post?: never;
-        delete?: never;
-        options?: never;
-        head?: never;
-        patch?: never;
-        trace?: never;
+        get: operations["list_version"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/version/{id}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_version"];
+        put?: never;
+        post?: never;
+        delete: operations["delete_version"];
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/version/{id}/failures": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_version_failures"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/version/{id}/files": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_files_by_version"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/version/{id}/packs": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_version_packs"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/version/{id}/packs/{pid}": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_version_pack"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/version/{id}/progress": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_version_progress"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/version/{id}/progress/stream": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        /** Emits a `progress` event whenever the stored progress changes. The stream
+         *     closes once no sync run is active, the download status is settled, the
+         *     progress has not moved for ten minutes or the database fails. */
+        get: operations["stream_version_progress"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/version/{id}/stats": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_version_stats"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/worker/leader": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_worker_leader"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+    "/api/v1/worker/status": {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        get: operations["get_worker_status"];
+        put?: never;
+        post?: never;
+        delete?: never;
+        options?: never;
+        head?: never;
+        patch?: never;
+        trace?: never;
+    };
+}
+export type webhooks = Record<string, never>;
+export interface components {
+    schemas: {
+        /** @description A worker operation queued through the admin API. */
+        AdminCommand: {
+            /** Format: date-time */
+            createdAt: string;
+            error?: string | null;
+            /** Format: date-time */
+            finishedAt?: string | null;
+            /** Format: int64 */
+            id: number;
+            /** @description One of `check_version`, `download_version`, `import_asset_mapping`,
+             *     `import_item_demand` or `reset_version`. */
+            kind: string;
+            resVersion?: string | null;
+            /** Format: date-time */
+            startedAt?: string | null;
+            /** @description One of `pending`, `running`, `succeeded` or `failed`. */
+            status: string;
+            /** Format: int32 */
+            versionId?: number | null;
+        };
+        AssetMappingDetails: {
+            assetName: string;
+            assetPath?: string | null;
+            bundleHash?: string | null;
+            bundlePath: string;
+            /** Format: int64 */
+            bundleSize?: number | null;
+            shortName?: string | null;
+        };
+        BundleClosure: {
+            assetName: string;
+            bundlePath: string;
+            bundles: components["schemas"]["ClosureBundle"][];
+            /**
+             * Format: int64
+             * @description Sum of the sizes of all downloaded bundles in the closure.
+             */
+            totalSize: number;
+        };
+        BundleDetails: {
+            /**
+             * Format: int64
+             * @description Upstream metadata from the version's hot update list.
+             */
+            abSize?: number | null;
+            /** Format: int32 */
+            cid?: number | null;
+            fileHash: string;
+            /** Format: int32 */
+            fileId: number;
+            /** Format: int64 */
+            fileSize: number;
+            /** Format: int32 */
+            id: number;
+            md5?: string | null;
+            path: string;
+            pid?: string | null;
+            /** Format: int64 */
+            totalSize?: number | null;
+            type?: string | null;
+            upstreamHash?: string | null;
+            versionChannel: string;
+            versionClient: string;
+            /** Format: int32 */
+            versionId: number;
+            versionIsReady: boolean;
+            versionRes: string;
+        };
+        BundleDiffEntry: {
+            /** Format: int32 */
+            newFileId?: number | null;
+            /** Format: int64 */
+            newSize?: number | null;
+            /** Format: int32 */
+            oldFileId?: number | null;
+            /** Format: int64 */
+            oldSize?: number | null;
+            path: string;
+            /** @description Pack of the bundle in the newer version, or in the older one if removed. */
+            pid?: string | null;
+            /** Format: int64 */
+            sizeDelta: number;
+        };
+        BundleFailure: {
+            /** Format: int32 */
+            attempts: number;
+            /** Format: date-time */
+            firstFailedAt: string;
+            lastError: string;
+            /** Format: date-time */
+            lastFailedAt: string;
+            path: string;
+        };
+        BundleSearchHit: {
+            fileHash: string;
+            /** Format: int32 */
+            fileId: number;
+            /** Format: int64 */
+            fileSize: number;
+            /** Format: int32 */
+            id: number;
+            path: string;
+            /**
+             * Format: float
+             * @description Trigram similarity to the query, higher is better.
+             */
+            score: number;
+            versionChannel: string;
+            versionClient: string;
+            /**
+             * Format: int32
+             * @description Latest searched version containing the bundle.
+             */
+            versionId: number;
+            versionRes: string;
+        };
+        BundleSize: {
+            fileHash: string;
+            path: string;
+            /** Format: int64 */
+            size: number;
+        };
+        /** @enum {string} */
+        BundleSort: "id" | "path" | "fileSize" | "abSize" | "totalSize" | "md5" | "hash" | "pid" | "type" | "cid";
+        /** @description A tracked AK channel, without its upstream URLs. */
+        Channel: {
+            name: string;
+            platform: string;
+            server: string;
+        };
+        /** @description A bundle required to load an asset, either its own bundle or one of the
+         *     bundles it transitively depends on. */
+        ClosureBundle: {
+            /** @description Hash of the stored file, missing until the bundle is downloaded. */
+            hash?: string | null;
+            path: string;
+            /** Format: int64 */
+            size?: number | null;
+        };
+        DeletedVersion: {
+            /** Format: int64 */
+            assetMappings: number;
+            /** Format: int64 */
+            bundles: number;
+            channel: string;
+            resVersion: string;
+            /** Format: int32 */
+            versionId: number;
+        };
+        DockerLaunchRequest: {
+            client_version: string;
+            exclude?: string | null;
+            include?: string | null;
+            prev_client_version: string;
+            prev_res_version: string;
+            res_version: string;
+        };
+        DockerLaunchResponse: {
+            container_name: string;
+            status: string;
+        };
+        Health: {
+            ok: boolean;
+        };
+        HealthReport: {
+            database: boolean;
+            /** @description Whether the database is reachable and no worker component is stale or
+             *     stopped. */
+            ok: boolean;
+            workers: components["schemas"]["WorkerStatus"][];
+        };
+        /** @enum {string} */
+        HistoryChange: "added" | "moved" | "modified" | "removed";
+        HistoryEntry: {
+            /** @description Bundle holding the asset from this version on, absent once removed. */
+            bundlePath?: string | null;
+            change: components["schemas"]["HistoryChange"];
+            channel: string;
+            clientVersion: string;
+            fileHash?: string | null;
+            resVersion: string;
+            /** Format: int32 */
+            versionId: number;
+        };
+        /** @description A `Page` of rows, or every row as a plain array with `legacy`. */
+        Listing_BundleDetails: components["schemas"]["Page_BundleDetails"] | {
+            /**
+             * Format: int64
+             * @description Upstream metadata from the version's hot update list.
+             */
+            abSize?: number | null;
+            /** Format: int32 */
+            cid?: number | null;
+            fileHash: string;
+            /** Format: int32 */
+            fileId: number;
+            /** Format: int64 */
+            fileSize: number;
+            /** Format: int32 */
+            id: number;
+            md5?: string | null;
+            path: string;
+            pid?: string | null;
+            /** Format: int64 */
+            totalSize?: number | null;
+            type?: string | null;
+            upstreamHash?: string | null;
+            versionChannel: string;
+            versionClient: string;
+            /** Format: int32 */
+            versionId: number;
+            versionIsReady: boolean;
+            versionRes: string;
+        }[];
+        /** @description A `Page` of rows, or every row as a plain array with `legacy`. */
+        Listing_VersionSummary: components["schemas"]["Page_VersionSummary"] | {
+            assetMappingStatus: string;
+            channel: string;
+            clientVersion: string;
+            downloadStatus: string;
+            /** Format: int32 */
+            id: number;
+            isReady: boolean;
+            resVersion: string;
+        }[];
+        /** @description Asset-level diff between two versions. The counts cover every matching
+         *     asset while `entries` holds the requested page. */
+        ManifestDiff: {
+            added: number;
+            entries: components["schemas"]["ManifestDiffEntry"][];
+            /** Format: int32 */
+            fromVersionId: number;
+            modified: number;
+            moved: number;
+            removed: number;
+            /** Format: int32 */
+            toVersionId: number;
+            total: number;
+        };
+        ManifestDiffEntry: {
+            assetName: string;
+            assetPath?: string | null;
+            change: components["schemas"]["HistoryChange"];
+            fromBundlePath?: string | null;
+            fromHash?: string | null;
+            toBundlePath?: string | null;
+            toHash?: string | null;
+        };
+        ManifestNode: {
+            name: string;
+            nodeType: string;
+            path: string;
+        };
+        ManifestSearchHit: {
+            assetPath?: string | null;
+            name: string;
+            nodeType: string;
+            path: string;
+            resVersion: string;
+            /**
+             * Format: float
+             * @description Trigram similarity to the query, higher is better.
+             */
+            score: number;
+            shortName?: string | null;
+            /**
+             * Format: int32
+             * @description Latest searched version containing the asset.
+             */
+            versionId: number;
+        };
+        PackBundle: {
+            /** Format: int64 */
+            abSize: number;
+            /**
+             * Format: int32
+             * @description Stored file, missing until the bundle has been downloaded.
+             */
+            fileId?: number | null;
+            hash: string;
+            md5: string;
+            path: string;
+            /** Format: int64 */
+            totalSize: number;
+        };
+        PackDetails: components["schemas"]["PackSummary"] & {
+            bundles: components["schemas"]["PackBundle"][];
+        };
+        /** @description Changes of one pack between two versions. Bundles outside any pack are
+         *     grouped under a `null` pid. */
+        PackDiffSummary: {
+            added: number;
+            changed: number;
+            pid?: string | null;
+            removed: number;
+            /** Format: int64 */
+            sizeDelta: number;
+        };
+        PackSummary: {
+            /** Format: int64 */
+            abSize?: number | null;
+            /** Format: int64 */
+            bundleCount: number;
+            /** Format: int32 */
+            cid?: number | null;
+            hash?: string | null;
+            md5?: string | null;
+            pid: string;
+            /**
+             * Format: int64
+             * @description Download size advertised by the hot update list.
+             */
+            totalSize: number;
+        };
+        /** @description One page of a list, `next_cursor` is absent on the last page. */
+        Page_BundleDetails: {
+            items: {
+                /**
+                 * Format: int64
+                 * @description Upstream metadata from the version's hot update list.
+                 */
+                abSize?: number | null;
+                /** Format: int32 */
+                cid?: number | null;
+                fileHash: string;
+                /** Format: int32 */
+                fileId: number;
+                /** Format: int64 */
+                fileSize: number;
+                /** Format: int32 */
+                id: number;
+                md5?: string | null;
+                path: string;
+                pid?: string | null;
+                /** Format: int64 */
+                totalSize?: number | null;
+                type?: string | null;
+                upstreamHash?: string | null;
+                versionChannel: string;
+                versionClient: string;
+                /** Format: int32 */
+                versionId: number;
+                versionIsReady: boolean;
+                versionRes: string;
+            }[];
+            nextCursor?: string | null;
+            /** Format: int64 */
+            total: number;
+        };
+        /** @description One page of a list, `next_cursor` is absent on the last page. */
+        Page_BundleSearchHit: {
+            items: {
+                fileHash: string;
+                /** Format: int32 */
+                fileId: number;
+                /** Format: int64 */
+                fileSize: number;
+                /** Format: int32 */
+                id: number;
+                path: string;
+                /**
+                 * Format: float
+                 * @description Trigram similarity to the query, higher is better.
+                 */
+                score: number;
+                versionChannel: string;
+                versionClient: string;
+                /**
+                 * Format: int32
+                 * @description Latest searched version containing the bundle.
+                 */
+                versionId: number;
+                versionRes: string;
+            }[];
+            nextCursor?: string | null;
+            /** Format: int64 */
+            total: number;
+        };
+        /** @description One page of a list, `next_cursor` is absent on the last page. */
+        Page_ManifestSearchHit: {
+            items: {
+                assetPath?: string | null;
+                name: string;
+                nodeType: string;
+                path: string;
+                resVersion: string;
+                /**
+                 * Format: float
+                 * @description Trigram similarity to the query, higher is better.
+                 */
+                score: number;
+                shortName?: string | null;
+                /**
+                 * Format: int32
+                 * @description Latest searched version containing the asset.
+                 */
+                versionId: number;
+            }[];
+            nextCursor?: string | null;
+            /** Format: int64 */
+            total: number;
+        };
+        /** @description One page of a list, `next_cursor` is absent on the last page. */
+        Page_VersionSummary: {
+            items: {
+                assetMappingStatus: string;
+                channel: string;
+                clientVersion: string;
+                downloadStatus: string;
+                /** Format: int32 */
+                id: number;
+                isReady: boolean;
+                resVersion: string;
+            }[];
+            nextCursor?: string | null;
+            /** Format: int64 */
+            total: number;
+        };
+        ReplicaStatus: {
+            /** Format: int64 */
+            failing: number;
+            /**
+             * Format: int64
+             * @description Age of the oldest queued object, in seconds.
+             */
+            lagSeconds: number;
+            lastError?: string | null;
+            /** Format: date-time */
+            oldestPendingAt?: string | null;
+            /** Format: int64 */
+            pending: number;
+            replica: string;
+        };
+        /** @enum {string} */
+        SortOrder: "asc" | "desc";
+        StorageStats: {
+            /** Format: int64 */
+            bundleCount: number;
+            /**
+             * Format: double
+             * @description `logical_bytes / unique_bytes`, 1.0 when nothing is stored yet.
+             */
+            dedupeRatio: number;
+            /** Format: int64 */
+            fileCount: number;
+            growth: components["schemas"]["VersionGrowth"][];
+            /**
+             * Format: int64
+             * @description Bytes referenced by all bundles of all versions.
+             */
+            logicalBytes: number;
+            /**
+             * Format: int64
+             * @description Bytes actually stored, each file counted once.
+             */
+            uniqueBytes: number;
+            /** Format: int64 */
+            versionCount: number;
+        };
+        VersionDetails: {
+            channel: string;
+            clientVersion: string;
+            downloadStatus: string;
+            hotUpdateList: string;
+            /** Format: int32 */
+            id: number;
+            isReady: boolean;
+            resVersion: string;
+        };
+        VersionDiff: {
+            added: components["schemas"]["BundleDiffEntry"][];
+            changed: components["schemas"]["BundleDiffEntry"][];
+            /** Format: int32 */
+            fromVersionId: number;
+            packs: components["schemas"]["PackDiffSummary"][];
+            removed: components["schemas"]["BundleDiffEntry"][];
+            /** Format: int32 */
+            toVersionId: number;
+        };
+        VersionGrowth: {
+            channel: string;
+            /**
+             * Format: int64
+             * @description Unique bytes stored once this version and all earlier ones are included.
+             */
+            cumulativeUniqueBytes: number;
+            /** Format: int64 */
+            newBytes: number;
+            resVersion: string;
+            /** Format: int64 */
+            totalSize: number;
+            /** Format: int32 */
+            versionId: number;
+        };
+        VersionProgress: {
+            /** Format: int64 */
+            bytesDeduplicated: number;
+            /** Format: int64 */
+            bytesFetched: number;
+            /** Format: int64 */
+            bytesStored: number;
+            /** Format: int32 */
+            doneBundles: number;
+            /** Format: int32 */
+            failedBundles: number;
+            /** Format: date-time */
+            finishedAt?: string | null;
+            /** Format: date-time */
+            startedAt: string;
+            /** Format: int32 */
+            totalBundles: number;
+            /** Format: date-time */
+            updatedAt: string;
+            /** Format: int32 */
+            versionId: number;
+        };
+        /** @enum {string} */
+        VersionSort: "id" | "resVersion" | "clientVersion";
+        VersionStats: {
+            /** Format: int64 */
+            bundleCount: number;
+            largestNewBundles: components["schemas"]["BundleSize"][];
+            /**
+             * Format: int64
+             * @description Bundles whose file was not stored by any earlier version.
+             */
+            newBundleCount: number;
+            /**
+             * Format: int64
+             * @description Size of the distinct files first stored by this version.
+             */
+            newBytes: number;
+            /** Format: int64 */
+            reusedBytes: number;
+            /**
+             * Format: int64
+             * @description Sum of the sizes of all bundles in the version.
+             */
+            totalSize: number;
+            /** Format: int32 */
+            versionId: number;
+        };
+        VersionSummary: {
+            assetMappingStatus: string;
+            channel: string;
+            clientVersion: string;
+            downloadStatus: string;
+            /** Format: int32 */
+            id: number;
+            isReady: boolean;
+            resVersion: string;
+        };
+        /** @description The worker currently holding the leader lock. */
+        WorkerLeader: {
+            /**
+             * Format: date-time
+             * @description When the worker took the lock.
+             */
+            electedAt: string;
+            /** @description Worker id, as `host:pid`. */
+            worker: string;
+        };
+        /** @description Heartbeat and last run of one worker component. */
+        WorkerStatus: {
+            /** @description `version_check`, `manifest_watcher` or `item_demand_watcher`. */
+            component: string;
+            /** Format: date-time */
+            heartbeatAt: string;
+            lastError?: string | null;
+            /** Format: date-time */
+            lastErrorAt?: string | null;
+            /** @description Summary of the last run, successful or not. */
+            lastOutcome?: string | null;
+            /** Format: date-time */
+            lastRunAt?: string | null;
+            /** Format: date-time */
+            lastSuccessAt?: string | null;
+            stale: boolean;
+            /**
+             * Format: int32
+             * @description How long the component may go without a heartbeat before it counts as
+             *     dead.
+             */
+            staleAfterSeconds: number;
+            /**
+             * Format: date-time
+             * @description Set when the worker shut down cleanly, cleared once a worker runs the
+             *     component again.
+             */
+            stoppedAt?: string | null;
+            /** @description Worker id, as `host:pid`. */
+            worker: string;
+        };
+    };
+    responses: never;
+    parameters: never;
+    requestBodies: never;
+    headers: never;
+    pathItems: never;
+}
+export type $defs = Record<string, never>;
+export interface operations {
+    health: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["HealthReport"];
+                };
+            };
+            /** @description The database is unreachable or a worker component stopped sending heartbeats or was shut down */
+            503: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["HealthReport"];
+                };
+            };
+        };
+    };
+    ping: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Health"];
+                };
+            };
+        };
+    };
+    list_admin_commands: {
+        parameters: {
+            query?: {
+                /** @description Number of most recent commands, 50 by default and at most 500. */
+                limit?: number | null;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AdminCommand"][];
+                };
+            };
+            /** @description Unauthorized - invalid or missing authentication token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    get_admin_command: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AdminCommand"];
+                };
+            };
+            /** @description Unauthorized - invalid or missing authentication token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Command not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    queue_item_demand_import: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Item demand reimport queued for the worker */
+            202: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AdminCommand"];
+                };
+            };
+            /** @description Unauthorized - invalid or missing authentication token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    queue_asset_mapping_import: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Res version of the primary channel */
+                res_version: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Asset mapping reimport queued for the worker */
+            202: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AdminCommand"];
+                };
+            };
+            /** @description Unauthorized - invalid or missing authentication token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Version not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    queue_version_check: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Version check queued for the worker */
+            202: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AdminCommand"];
+                };
+            };
+            /** @description Unauthorized - invalid or missing authentication token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    queue_version_download: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Download queued for the worker */
+            202: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AdminCommand"];
+                };
+            };
+            /** @description Unauthorized - invalid or missing authentication token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Version not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    queue_version_reset: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Reset of the download and asset mapping status queued for the worker */
+            202: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AdminCommand"];
+                };
+            };
+            /** @description Unauthorized - invalid or missing authentication token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Version not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    get_asset_history: {
+        parameters: {
+            query: {
+                asset_name: string;
+                /** @description Defaults to the primary channel. */
+                channel?: string | null;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["HistoryEntry"][];
+                };
+            };
+        };
+    };
+    filter_bundle: {
+        parameters: {
+            query?: {
+                path?: string | null;
+                hash?: string | null;
+                file?: number | null;
+                version?: number | null;
+                channel?: string | null;
+                md5?: string | null;
+                upstream_hash?: string | null;
+                pid?: string | null;
+                type?: string | null;
+                cid?: number | null;
+                min_total_size?: number | null;
+                max_total_size?: number | null;
+                sort?: null | components["schemas"]["BundleSort"];
+                order?: null | components["schemas"]["SortOrder"];
+                /** @description Page size, 100 by default and at most 1000. */
+                limit?: number | null;
+                /** @description `nextCursor` of the previous page. */
+                cursor?: string | null;
+                /** @description Return every row as a plain array instead of a page, for the bundled
+                 *     frontend. */
+                legacy?: boolean;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Listing_BundleDetails"];
+                };
+            };
+        };
+    };
+    get_bundle_history: {
+        parameters: {
+            query: {
+                path: string;
+                /** @description Defaults to the primary channel. */
+                channel?: string | null;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["HistoryEntry"][];
+                };
+            };
+        };
+    };
+    search_bundles: {
+        parameters: {
+            query: {
+                q: string;
+                /** @description Searches every version when omitted. */
+                version?: number | null;
+                cursor?: string | null;
+                /** @description Page size, 50 by default and at most 500. */
+                limit?: number | null;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Page_BundleSearchHit"];
+                };
+            };
+        };
+    };
+    get_bundle: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["BundleDetails"];
+                };
+            };
+        };
+    };
+    get_bundle_content: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Stored bundle content */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/octet-stream": unknown;
+                };
+            };
+            /** @description Requested byte range of the stored bundle content */
+            206: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/octet-stream": unknown;
+                };
+            };
+            /** @description Bundle or stored object not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Requested range is outside the stored object */
+            416: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    list_channels: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Channel"][];
+                };
+            };
+        };
+    };
+    diff_versions: {
+        parameters: {
+            query: {
+                from: number;
+                to: number;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["VersionDiff"];
+                };
+            };
+            /** @description Version not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    launch_container: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody: {
+            content: {
+                "application/json": components["schemas"]["DockerLaunchRequest"];
+            };
+        };
+        responses: {
+            /** @description Container launched successfully */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["DockerLaunchResponse"];
+                };
+            };
+            /** @description Bad request - invalid parameters */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Unauthorized - invalid or missing authentication token */
+            401: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Internal server error */
+            500: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    get_file_content: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description SHA-256 of the stored file */
+                hash: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Stored file content */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/octet-stream": unknown;
+                };
+            };
+            /** @description Requested byte range of the stored file content */
+            206: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/octet-stream": unknown;
+                };
+            };
+            /** @description Malformed hash */
+            400: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description File or stored object not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Requested range is outside the stored object */
+            416: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    search_assets_by_path: {
+        parameters: {
+            query: {
+                /** @description Search path pattern */
+                path: string;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description List of matching entries */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    list_asset: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                /** @description Directory path to list */
+                path: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Directory listing */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    get_item_demand: {
+        parameters: {
+            query?: never;
+            header?: never;
+            path: {
+                item_name: string;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Item demand found */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": string;
+                };
+            };
+            /** @description Item demand not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    diff_manifests: {
+        parameters: {
+            query: {
+                from: number;
+                to: number;
+                /** @description Only compare assets whose name or asset path starts with this prefix. */
+                prefix?: string | null;
+                offset?: number | null;
+                /** @description Page size, 100 by default and at most 1000. */
+                limit?: number | null;
+            };
+            header?: never;
+            path?: never;
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            /** @description Asset-level diff */
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ManifestDiff"];
+                };
+            };
+            /** @description Version not found */
+            404: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+            /** @description Asset mappings of a version are not imported */
+            409: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content?: never;
+            };
+        };
+    };
+    list_manifest_children: {
+        parameters: {
+            query?: {
+                dir?: string | null;
+            };
+            header?: never;
+            path: {
+                version_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["ManifestNode"][];
+                };
+            };
+        };
+    };
+    get_manifest_dependencies: {
+        parameters: {
+            query: {
+                asset_name: string;
+            };
+            header?: never;
+            path: {
+                version_id: number;
+            };
+            cookie?: never;
+        };
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["BundleClosure"];
+                };
+            };
+        };
     };
-    "/api/v1/version/{id}": {
+    get_manifest_detail: {
         parameters: {
-            query?: never;
+            query: {
+                asset_name: string;
+            };
             header?: never;
-            path?: never;
+            path: {
+                version_id: number;
+            };
             cookie?: never;
         };
-        get: operations["get_version"];
-        put?: never;
-        post?: never;
-        delete?: never;
-        options?: never;
-        head?: never;
-        patch?: never;
-        trace?: never;
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["AssetMappingDetails"];
+                };
+            };
+        };
     };
-    "/api/v1/version/{id}/files": {
+    search_manifest: {
         parameters: {
-            query?: never;
+            query: {
+                q: string;
+                /** @description Search every version instead of the one in the path. */
+                all_versions?: boolean;
+                cursor?: string | null;
+                /** @description Page size, 50 by default and at most 500. */
+                limit?: number | null;
+            };
             header?: never;
-            path?: never;
+            path: {
+                version_id: number;
+            };
             cookie?: never;
         };
-        get: operations["get_files_by_version"];
-        put?: never;
-        post?: never;
-        delete?: never;
-        options?: never;
-        head?: never;
-        patch?: never;
-        trace?: never;
-    };
-}
-export type webhooks = Record<string, never>;
-export interface components {
-    schemas: {
-        AssetMappingDetails: {
-            assetName: string;
-            assetPath?: string | null;
-            bundleHash?: string | null;
-            bundlePath: string;
-            /** Format: int32 */
-            bundleSize?: number | null;
-            shortName?: string | null;
-        };
-        BundleDetails: {
-            fileHash: string;
-            /** Format: int32 */
-            fileId: number;
-            /** Format: int32 */
-            fileSize: number;
-            /** Format: int32 */
-            id: number;
-            path: string;
-            versionClient: string;
-            /** Format: int32 */
-            versionId: number;
-            versionIsReady: boolean;
-            versionRes: string;
-        };
-        DockerLaunchRequest: {
-            client_version: string;
-            prev_client_version: string;
-            prev_res_version: string;
-            res_version: string;
-            include?: string;
-            exclude?: string;
-        };
-        DockerLaunchResponse: {
-            container_name: string;
-            status: string;
-        };
-        Health: {
-            ok: boolean;
-        };
-        ManifestNode: {
-            name: string;
-            nodeType: string;
-            path: string;
-        };
-        VersionDetails: {
-            clientVersion: string;
-            hotUpdateList: string;
-            /** Format: int32 */
-            id: number;
-            isReady: boolean;
-            resVersion: string;
-        };
-        VersionSummary: {
-            assetMappingStatus: string;
-            clientVersion: string;
-            /** Format: int32 */
-            id: number;
-            isReady: boolean;
-            resVersion: string;
+        requestBody?: never;
+        responses: {
+            200: {
+                headers: {
+                    [name: string]: unknown;
+                };
+                content: {
+                    "application/json": components["schemas"]["Page_ManifestSearchHit"];
+                };
+            };
         };
     };
-    responses: never;
-    parameters: never;
-    requestBodies: never;
-    headers: never;
-    pathItems: never;
-}
-export type $defs = Record<string, never>;
-export interface operations {
-    health: {
+    get_stats: {
         parameters: {
             query?: never;
             header?: never;
@@ -315,12 +2077,12 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["Health"];
+                    "application/json": components["schemas"]["StorageStats"];
                 };
             };
         };
     };
-    ping: {
+    get_replication_status: {
         parameters: {
             query?: never;
             header?: never;
@@ -334,18 +2096,24 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["Health"];
+                    "application/json": components["schemas"]["ReplicaStatus"][];
                 };
             };
         };
     };
-    filter_bundle: {
+    list_version: {
         parameters: {
             query?: {
-                path?: string | null;
-                hash?: string | null;
-                file?: number | null;
-                version?: number | null;
+                channel?: string | null;
+                sort?: null | components["schemas"]["VersionSort"];
+                order?: null | components["schemas"]["SortOrder"];
+                /** @description Page size, 100 by default and at most 1000. */
+                limit?: number | null;
+                /** @description `nextCursor` of the previous page. */
+                cursor?: string | null;
+                /** @description Return every row as a plain array instead of a page, for the bundled
+                 *     frontend. */
+                legacy?: boolean;
             };
             header?: never;
             path?: never;
@@ -358,12 +2126,12 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["BundleDetails"][];
+                    "application/json": components["schemas"]["Listing_VersionSummary"];
                 };
             };
         };
     };
-    get_bundle: {
+    get_version: {
         parameters: {
             query?: never;
             header?: never;
@@ -379,49 +2147,47 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["BundleDetails"];
+                    "application/json": components["schemas"]["VersionDetails"];
                 };
             };
         };
     };
-    launch_container: {
+    delete_version: {
         parameters: {
             query?: never;
             header?: never;
-            path?: never;
-            cookie?: never;
-        };
-        requestBody: {
-            content: {
-                "application/json": components["schemas"]["DockerLaunchRequest"];
+            path: {
+                id: number;
             };
+            cookie?: never;
         };
+        requestBody?: never;
         responses: {
-            /** @description Container launched successfully */
+            /** @description Version deleted, run gc to reclaim storage */
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["DockerLaunchResponse"];
+                    "application/json": components["schemas"]["DeletedVersion"];
                 };
             };
-            /** @description Bad request - invalid parameters */
-            400: {
+            /** @description Unauthorized - invalid or missing authentication token */
+            401: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content?: never;
             };
-            /** @description Unauthorized - invalid or missing authentication token */
-            401: {
+            /** @description Version not found */
+            404: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content?: never;
             };
-            /** @description Internal server error */
-            500: {
+            /** @description Version is still downloading */
+            409: {
                 headers: {
                     [name: string]: unknown;
                 };
@@ -429,85 +2195,86 @@ export interface operations {
             };
         };
     };
-    search_assets_by_path: {
+    get_version_failures: {
         parameters: {
-            query: {
-                /** @description Search path pattern */
-                path: string;
-            };
+            query?: never;
             header?: never;
-            path?: never;
+            path: {
+                id: number;
+            };
             cookie?: never;
         };
         requestBody?: never;
         responses: {
-            /** @description List of matching entries */
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
-                content?: never;
+                content: {
+                    "application/json": components["schemas"]["BundleFailure"][];
+                };
             };
         };
     };
-    list_asset: {
+    get_files_by_version: {
         parameters: {
-            query?: never;
+            query?: {
+                sort?: null | components["schemas"]["BundleSort"];
+                order?: null | components["schemas"]["SortOrder"];
+                /** @description Page size, 100 by default and at most 1000. */
+                limit?: number | null;
+                /** @description `nextCursor` of the previous page. */
+                cursor?: string | null;
+                /** @description Return every row as a plain array instead of a page, for the bundled
+                 *     frontend. */
+                legacy?: boolean;
+            };
             header?: never;
             path: {
-                /** @description Directory path to list */
-                path: string;
+                id: number;
             };
             cookie?: never;
         };
         requestBody?: never;
         responses: {
-            /** @description Directory listing */
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
-                content?: never;
+                content: {
+                    "application/json": components["schemas"]["Listing_BundleDetails"];
+                };
             };
         };
     };
-    get_item_demand: {
+    get_version_packs: {
         parameters: {
             query?: never;
             header?: never;
             path: {
-                item_name: string;
+                id: number;
             };
             cookie?: never;
         };
         requestBody?: never;
         responses: {
-            /** @description Item demand found */
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": string;
-                };
-            };
-            /** @description Item demand not found */
-            404: {
-                headers: {
-                    [name: string]: unknown;
+                    "application/json": components["schemas"]["PackSummary"][];
                 };
-                content?: never;
             };
         };
     };
-    list_manifest_children: {
+    get_version_pack: {
         parameters: {
-            query?: {
-                dir?: string | null;
-            };
+            query?: never;
             header?: never;
             path: {
-                version_id: number;
+                id: number;
+                pid: string;
             };
             cookie?: never;
         };
@@ -518,19 +2285,17 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["ManifestNode"][];
+                    "application/json": components["schemas"]["PackDetails"];
                 };
             };
         };
     };
-    get_manifest_detail: {
+    get_version_progress: {
         parameters: {
-            query: {
-                asset_name: string;
-            };
+            query?: never;
             header?: never;
             path: {
-                version_id: number;
+                id: number;
             };
             cookie?: never;
         };
@@ -541,19 +2306,17 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["AssetMappingDetails"];
+                    "application/json": components["schemas"]["VersionProgress"];
                 };
             };
         };
     };
-    search_manifest: {
+    stream_version_progress: {
         parameters: {
-            query: {
-                q: string;
-            };
+            query?: never;
             header?: never;
             path: {
-                version_id: number;
+                id: number;
             };
             cookie?: never;
         };
@@ -564,16 +2327,18 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["ManifestNode"][];
+                    "text/event-stream": components["schemas"]["VersionProgress"];
                 };
             };
         };
     };
-    list_version: {
+    get_version_stats: {
         parameters: {
             query?: never;
             header?: never;
-            path?: never;
+            path: {
+                id: number;
+            };
             cookie?: never;
         };
         requestBody?: never;
@@ -583,49 +2348,54 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["VersionSummary"][];
+                    "application/json": components["schemas"]["VersionStats"];
                 };
             };
         };
     };
-    get_version: {
+    get_worker_leader: {
         parameters: {
             query?: never;
             header?: never;
-            path: {
-                id: number;
-            };
+            path?: never;
             cookie?: never;
         };
         requestBody?: never;
         responses: {
+            /** @description Worker currently running the singleton duties */
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["VersionDetails"];
+                    "application/json": components["schemas"]["WorkerLeader"];
+                };
+            };
+            /** @description No worker holds the leader lock */
+            404: {
+                headers: {
+                    [name: string]: unknown;
                 };
+                content?: never;
             };
         };
     };
-    get_files_by_version: {
+    get_worker_status: {
         parameters: {
             query?: never;
             header?: never;
-            path: {
-                id: number;
-            };
+            path?: never;
             cookie?: never;
         };
         requestBody?: never;
         responses: {
+            /** @description Heartbeat and last run of each worker component */
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["BundleDetails"][];
+                    "application/json": components["schemas"]["WorkerStatus"][];
                 };
             };
         };
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_bundles_path_upstream_hash;
+
+ALTER TABLE bundles
+DROP COLUMN IF EXISTS upstream_md5,
+DROP COLUMN IF EXISTS upstream_hash;
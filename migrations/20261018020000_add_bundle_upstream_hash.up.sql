@@ -0,0 +1,14 @@
+ALTER TABLE bundles
+ADD COLUMN upstream_hash VARCHAR(64),
+ADD COLUMN upstream_md5 VARCHAR(64);
+
+UPDATE bundles b
+SET upstream_hash = info->>'hash',
+    upstream_md5 = info->>'md5'
+FROM versions v,
+     jsonb_array_elements(v.hot_update_list::jsonb->'abInfos') AS info
+WHERE b.version = v.id
+  AND info->>'name' = b.path;
+
+CREATE INDEX idx_bundles_path_upstream_hash
+    ON bundles(path, upstream_hash);
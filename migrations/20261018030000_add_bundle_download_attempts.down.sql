@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS bundle_download_attempts;
+
+ALTER TABLE versions
+DROP COLUMN IF EXISTS download_status;
+
+DROP TYPE IF EXISTS download_status;
@@ -0,0 +1,17 @@
+CREATE TYPE download_status AS ENUM ('pending', 'partial', 'ready');
+
+ALTER TABLE versions
+ADD COLUMN download_status download_status NOT NULL DEFAULT 'pending';
+
+UPDATE versions SET download_status = 'ready' WHERE is_ready;
+
+CREATE TABLE bundle_download_attempts (
+    id SERIAL PRIMARY KEY,
+    version_id INTEGER NOT NULL REFERENCES versions(id),
+    path VARCHAR(256) NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT NOT NULL,
+    first_failed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_failed_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (version_id, path)
+);
@@ -0,0 +1,12 @@
+CREATE TABLE version_download_progress (
+    version_id INTEGER PRIMARY KEY REFERENCES versions(id),
+    total_bundles INTEGER NOT NULL,
+    done_bundles INTEGER NOT NULL DEFAULT 0,
+    failed_bundles INTEGER NOT NULL DEFAULT 0,
+    bytes_fetched BIGINT NOT NULL DEFAULT 0,
+    bytes_stored BIGINT NOT NULL DEFAULT 0,
+    bytes_deduplicated BIGINT NOT NULL DEFAULT 0,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ
+);
@@ -0,0 +1,8 @@
+ALTER TABLE versions
+DROP CONSTRAINT IF EXISTS versions_channel_res_unique;
+
+ALTER TABLE versions
+ADD CONSTRAINT versions_res_unique UNIQUE (res);
+
+ALTER TABLE versions
+DROP COLUMN IF EXISTS channel;
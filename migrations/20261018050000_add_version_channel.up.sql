@@ -0,0 +1,11 @@
+ALTER TABLE versions
+ADD COLUMN channel VARCHAR(64) NOT NULL DEFAULT 'official-android';
+
+ALTER TABLE versions
+ALTER COLUMN channel DROP DEFAULT;
+
+ALTER TABLE versions
+DROP CONSTRAINT IF EXISTS versions_res_unique;
+
+ALTER TABLE versions
+ADD CONSTRAINT versions_channel_res_unique UNIQUE (channel, res);
@@ -0,0 +1,12 @@
+CREATE TABLE replication_queue (
+    id BIGSERIAL PRIMARY KEY,
+    replica VARCHAR(64) NOT NULL,
+    file_id INTEGER NOT NULL REFERENCES files(id) ON DELETE CASCADE,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (replica, file_id)
+);
+
+CREATE INDEX idx_replication_queue_next_attempt ON replication_queue(next_attempt_at);
@@ -0,0 +1 @@
+DROP INDEX IF EXISTS idx_bundles_file_version;
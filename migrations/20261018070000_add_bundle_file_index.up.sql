@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS idx_bundles_file_version ON bundles(file, version);
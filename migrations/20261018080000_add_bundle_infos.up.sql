@@ -0,0 +1,31 @@
+CREATE TABLE bundle_infos (
+    id SERIAL PRIMARY KEY,
+    version_id INTEGER NOT NULL REFERENCES versions(id),
+    path VARCHAR(256) NOT NULL,
+    ab_size BIGINT NOT NULL,
+    total_size BIGINT NOT NULL,
+    md5 VARCHAR(64) NOT NULL,
+    hash VARCHAR(64) NOT NULL,
+    pid VARCHAR(256),
+    type VARCHAR(64),
+    cid INTEGER,
+    UNIQUE (version_id, path)
+);
+
+CREATE INDEX idx_bundle_infos_pid ON bundle_infos(pid);
+CREATE INDEX idx_bundle_infos_hash ON bundle_infos(hash);
+
+INSERT INTO bundle_infos (version_id, path, ab_size, total_size, md5, hash, pid, type, cid)
+SELECT
+    v.id,
+    info->>'name',
+    (info->>'abSize')::bigint,
+    (info->>'totalSize')::bigint,
+    info->>'md5',
+    info->>'hash',
+    info->>'pid',
+    info->>'type',
+    (info->>'cid')::int
+FROM versions v,
+     jsonb_array_elements(v.hot_update_list::jsonb->'abInfos') AS info
+ON CONFLICT (version_id, path) DO NOTHING;
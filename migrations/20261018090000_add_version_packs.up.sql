@@ -0,0 +1,24 @@
+CREATE TABLE version_packs (
+    id SERIAL PRIMARY KEY,
+    version_id INTEGER NOT NULL REFERENCES versions(id),
+    pid VARCHAR(256) NOT NULL,
+    total_size BIGINT NOT NULL,
+    ab_size BIGINT,
+    md5 VARCHAR(64),
+    hash VARCHAR(64),
+    cid INTEGER,
+    UNIQUE (version_id, pid)
+);
+
+INSERT INTO version_packs (version_id, pid, total_size, ab_size, md5, hash, cid)
+SELECT
+    v.id,
+    pack->>'name',
+    COALESCE((pack->>'totalSize')::bigint, 0),
+    (pack->>'abSize')::bigint,
+    pack->>'md5',
+    pack->>'hash',
+    (pack->>'cid')::int
+FROM versions v,
+     jsonb_array_elements(COALESCE(v.hot_update_list::jsonb->'packInfos', '[]'::jsonb)) AS pack
+ON CONFLICT (version_id, pid) DO NOTHING;
@@ -0,0 +1,6 @@
+CREATE TABLE bundle_dependencies (
+    version_id INTEGER NOT NULL REFERENCES versions(id),
+    bundle_path VARCHAR(512) NOT NULL,
+    dependency_path VARCHAR(512) NOT NULL,
+    PRIMARY KEY (version_id, bundle_path, dependency_path)
+);
@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_bundles_path_trgm;
+DROP INDEX IF EXISTS idx_atb_asset_path_trgm;
+DROP INDEX IF EXISTS idx_atb_short_name_trgm;
+DROP INDEX IF EXISTS idx_atb_asset_name_trgm;
+DROP EXTENSION IF EXISTS pg_trgm;
@@ -0,0 +1,13 @@
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX idx_atb_asset_name_trgm
+    ON asset_to_bundle_mappings USING gin (asset_name gin_trgm_ops);
+
+CREATE INDEX idx_atb_short_name_trgm
+    ON asset_to_bundle_mappings USING gin (short_name gin_trgm_ops);
+
+CREATE INDEX idx_atb_asset_path_trgm
+    ON asset_to_bundle_mappings USING gin (asset_path gin_trgm_ops);
+
+CREATE INDEX idx_bundles_path_trgm
+    ON bundles USING gin (path gin_trgm_ops);
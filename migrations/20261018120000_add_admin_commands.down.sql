@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS admin_commands;
+DROP TYPE IF EXISTS admin_command_status;
+DROP TYPE IF EXISTS admin_command_kind;
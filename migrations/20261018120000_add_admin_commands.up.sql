@@ -0,0 +1,22 @@
+CREATE TYPE admin_command_kind AS ENUM (
+    'check_version',
+    'download_version',
+    'import_asset_mapping',
+    'import_item_demand',
+    'reset_version'
+);
+CREATE TYPE admin_command_status AS ENUM ('pending', 'running', 'succeeded', 'failed');
+
+CREATE TABLE admin_commands (
+    id BIGSERIAL PRIMARY KEY,
+    kind admin_command_kind NOT NULL,
+    version_id INTEGER,
+    res_version VARCHAR(32),
+    status admin_command_status NOT NULL DEFAULT 'pending',
+    error TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    started_at TIMESTAMPTZ,
+    finished_at TIMESTAMPTZ
+);
+
+CREATE INDEX idx_admin_commands_pending ON admin_commands(id) WHERE status = 'pending';
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS download_jobs;
+DROP TYPE IF EXISTS download_job_status;
@@ -0,0 +1,17 @@
+CREATE TYPE download_job_status AS ENUM ('pending', 'running', 'done', 'failed');
+
+CREATE TABLE download_jobs (
+    id BIGSERIAL PRIMARY KEY,
+    version_id INTEGER NOT NULL REFERENCES versions(id),
+    path VARCHAR(256) NOT NULL,
+    status download_job_status NOT NULL DEFAULT 'pending',
+    worker VARCHAR(128),
+    lease_expires_at TIMESTAMPTZ,
+    heartbeat_at TIMESTAMPTZ,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    finished_at TIMESTAMPTZ,
+    UNIQUE (version_id, path)
+);
+
+CREATE INDEX idx_download_jobs_active ON download_jobs(id) WHERE status IN ('pending', 'running');
@@ -0,0 +1,11 @@
+CREATE TABLE worker_status (
+    component VARCHAR(64) PRIMARY KEY,
+    worker VARCHAR(128) NOT NULL,
+    heartbeat_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    stale_after_seconds INTEGER NOT NULL,
+    last_run_at TIMESTAMPTZ,
+    last_success_at TIMESTAMPTZ,
+    last_outcome TEXT,
+    last_error_at TIMESTAMPTZ,
+    last_error TEXT
+);
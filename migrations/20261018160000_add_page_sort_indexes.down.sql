@@ -0,0 +1,7 @@
+DROP INDEX IF EXISTS idx_versions_channel_client_id;
+DROP INDEX IF EXISTS idx_versions_channel_res_id;
+DROP INDEX IF EXISTS idx_versions_client_id;
+DROP INDEX IF EXISTS idx_versions_res_id;
+DROP INDEX IF EXISTS idx_bundles_version_path_id;
+DROP INDEX IF EXISTS idx_bundles_version_id;
+DROP INDEX IF EXISTS idx_bundles_path_id;
@@ -0,0 +1,9 @@
+-- Keyset pagination compares (sort column, id) pairs, which these indexes
+-- serve in order for bundle and version lists.
+CREATE INDEX IF NOT EXISTS idx_bundles_path_id ON bundles(path, id);
+CREATE INDEX IF NOT EXISTS idx_bundles_version_id ON bundles(version, id);
+CREATE INDEX IF NOT EXISTS idx_bundles_version_path_id ON bundles(version, path, id);
+CREATE INDEX IF NOT EXISTS idx_versions_res_id ON versions(res, id);
+CREATE INDEX IF NOT EXISTS idx_versions_client_id ON versions(client, id);
+CREATE INDEX IF NOT EXISTS idx_versions_channel_res_id ON versions(channel, res, id);
+CREATE INDEX IF NOT EXISTS idx_versions_channel_client_id ON versions(channel, client, id);
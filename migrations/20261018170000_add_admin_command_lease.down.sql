@@ -0,0 +1,3 @@
+ALTER TABLE admin_commands
+DROP COLUMN lease_expires_at,
+DROP COLUMN worker;
@@ -0,0 +1,3 @@
+ALTER TABLE admin_commands
+ADD COLUMN worker VARCHAR(128),
+ADD COLUMN lease_expires_at TIMESTAMPTZ;
@@ -0,0 +1,7 @@
+-- The leader campaigns on a long-lived session, so its start no longer marks
+-- the election; the new leader records it here when it takes the lock.
+CREATE TABLE worker_leader (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    worker VARCHAR(128) NOT NULL,
+    elected_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
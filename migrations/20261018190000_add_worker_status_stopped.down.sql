@@ -0,0 +1 @@
+ALTER TABLE worker_status DROP COLUMN stopped_at;
@@ -0,0 +1 @@
+ALTER TABLE worker_status ADD COLUMN stopped_at TIMESTAMPTZ;
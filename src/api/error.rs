@@ -1,5 +1,9 @@
 use crate::AppError;
-use axum::{Json, http::StatusCode, response::IntoResponse};
+use axum::{
+    Json,
+    http::{StatusCode, header},
+    response::IntoResponse,
+};
 use serde::Serialize;
 
 #[derive(thiserror::Error, Debug)]
@@ -12,8 +16,14 @@ pub enum WebError {
     ServiceUnavailable(anyhow::Error),
     #[error("Unauthorized: {0}")]
     Unauthorized(String),
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
     #[error("Bad Request: {0}")]
     BadRequest(String),
+    #[error("Range Not Satisfiable: object size is {0}")]
+    RangeNotSatisfiable(u64),
+    #[error("Conflict: {0}")]
+    Conflict(String),
 }
 
 #[derive(Debug, Serialize)]
@@ -58,9 +68,21 @@ impl IntoResponse for WebError {
             err @ Self::Unauthorized(..) => {
                 (StatusCode::UNAUTHORIZED, Json(ApiErrorDetail::from(err))).into_response()
             }
+            err @ Self::Forbidden(..) => {
+                (StatusCode::FORBIDDEN, Json(ApiErrorDetail::from(err))).into_response()
+            }
             err @ Self::BadRequest(..) => {
                 (StatusCode::BAD_REQUEST, Json(ApiErrorDetail::from(err))).into_response()
             }
+            err @ Self::Conflict(..) => {
+                (StatusCode::CONFLICT, Json(ApiErrorDetail::from(err))).into_response()
+            }
+            err @ Self::RangeNotSatisfiable(size) => (
+                StatusCode::RANGE_NOT_SATISFIABLE,
+                [(header::CONTENT_RANGE, format!("bytes */{size}"))],
+                Json(ApiErrorDetail::from(err)),
+            )
+                .into_response(),
         }
     }
 }
@@ -1,24 +1,65 @@
 use crate::{
+    AppError, AppResult,
     api::{
         error::{WebError, WebResult},
+        middleware::{AdminReadScope, AdminWriteScope, Authorized, DockerLaunchScope},
         state::AppState,
         types::{
-            AssetSearchQuery, BundleListQuery, DockerLaunchRequest, DockerLaunchResponse, Health,
-            ManifestChildrenQuery, ManifestDetailQuery, ManifestSearchQuery,
+            AdminCommandListQuery, AssetHistoryQuery, AssetSearchQuery, BundleHistoryQuery,
+            BundleListQuery, BundleSearchQuery, Channel, DiffQuery, DockerLaunchRequest,
+            DockerLaunchResponse, Health, HealthReport, Listing, ManifestChildrenQuery,
+            ManifestDetailQuery, ManifestDiffQuery, ManifestSearchQuery, Page, SortOrder,
+            VersionFilesQuery, VersionListQuery,
         },
-        utils::json,
+        utils::{content_disposition, decode_cursor, json, parse_byte_range},
     },
-    database::model::{
-        AssetMappingDetails, BundleDetails, ManifestNode, VersionDetails, VersionSummary,
+    database::{
+        bundle::BundleFilter,
+        model::{
+            AdminCommand, AssetMappingDetails, BundleClosure, BundleDetails, BundleFailure,
+            BundleSearchHit, DeletedVersion, HistoryEntry, ManifestDiff, ManifestNode,
+            ManifestSearchHit, PackDetails, PackSummary, ReplicaStatus, StorageStats,
+            VersionDetails, VersionDiff, VersionProgress, VersionStats, VersionSummary,
+            WorkerLeader, WorkerStatus,
+        },
+        pagination::SortCursor,
+        row::AdminCommandKind,
+        search::SearchCursor,
+        version::VersionDeletion,
     },
+    external::storage::object_path,
+    service::types::dat_file_name,
 };
 use axum::{
-    Json, debug_handler,
+    Json,
+    body::Body,
+    debug_handler,
     extract::{Path, Query, State},
-    http::{StatusCode, header},
-    response::{IntoResponse, Response},
+    http::{HeaderMap, StatusCode, header},
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
 };
+use futures::{Stream, stream};
 use rust_embed::Embed;
+use std::time::Duration;
+use tokio::time::{Instant, sleep};
+use tracing::warn;
+
+const PROGRESS_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// A progress stream whose run stops moving for this long is closed; the
+/// client can reconnect if the run resumes.
+const PROGRESS_MAX_IDLE: Duration = Duration::from_mins(10);
+const MANIFEST_DIFF_DEFAULT_LIMIT: usize = 100;
+const MANIFEST_DIFF_MAX_LIMIT: usize = 1000;
+const PAGE_DEFAULT_LIMIT: i64 = 100;
+const PAGE_MAX_LIMIT: i64 = 1000;
+const SEARCH_DEFAULT_LIMIT: i64 = 50;
+const SEARCH_MAX_LIMIT: i64 = 500;
+const ADMIN_COMMAND_DEFAULT_LIMIT: i64 = 50;
+const ADMIN_COMMAND_MAX_LIMIT: i64 = 500;
+
 #[debug_handler]
 #[utoipa::path(get, path = "/_ping", responses((status = OK, body = Health)))]
 pub async fn ping() -> Json<Health> {
@@ -26,17 +67,80 @@ pub async fn ping() -> Json<Health> {
 }
 
 #[debug_handler]
-#[utoipa::path(get, path = "/_health", responses((status = OK, body = Health)))]
-pub async fn health(State(state): State<AppState>) -> Json<Health> {
-    Json(Health {
-        ok: state.database.health_check().await,
-    })
+#[utoipa::path(
+    get,
+    path = "/_health",
+    responses(
+        (status = OK, body = HealthReport),
+        (status = SERVICE_UNAVAILABLE, description = "The database is unreachable or a worker component stopped sending heartbeats or was shut down", body = HealthReport)
+    )
+)]
+pub async fn health(State(state): State<AppState>) -> Response {
+    let workers = state.database.query_worker_status().await;
+    let database = state.database.health_check().await && workers.is_ok();
+    let workers = workers.unwrap_or_default();
+    let ok = database
+        && workers
+            .iter()
+            .all(|worker| !worker.stale && worker.stopped_at.is_none());
+    let status = if ok {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        status,
+        Json(HealthReport {
+            ok,
+            database,
+            workers,
+        }),
+    )
+        .into_response()
+}
+
+#[debug_handler]
+#[utoipa::path(get, path = "/version", tag = "version", params(VersionListQuery), responses((status = OK, body = Listing<VersionSummary>)))]
+pub async fn list_version(
+    State(state): State<AppState>,
+    Query(query): Query<VersionListQuery>,
+) -> WebResult<Response> {
+    let channel = query.channel.as_deref();
+    if query.legacy {
+        return Ok(json(Listing::Legacy(
+            state.database.query_versions(channel).await?,
+        )));
+    }
+    let sort = query.sort.unwrap_or_default();
+    let after = decode_page_cursor(query.cursor.as_deref(), sort.is_numeric())?;
+    let limit = page_limit(query.limit);
+    let versions = state
+        .database
+        .query_version_page(
+            channel,
+            sort,
+            matches!(query.order, Some(SortOrder::Desc)),
+            after.as_ref(),
+            limit + 1,
+        )
+        .await?;
+    let total = state.database.count_versions(channel).await?;
+    Ok(json(Listing::Page(Page::from_rows(
+        versions,
+        limit,
+        total,
+        |version| sort.cursor(version),
+    ))))
 }
 
+/// Lists the tracked channels, the primary one first.
 #[debug_handler]
-#[utoipa::path(get, path = "/version", tag = "version", responses((status = OK, body = [VersionSummary])))]
-pub async fn list_version(State(state): State<AppState>) -> WebResult<Response> {
-    Ok(json(state.database.query_versions().await?))
+#[utoipa::path(get, path = "/channel", tag = "version", responses((status = OK, body = [Channel])))]
+pub async fn list_channels(State(state): State<AppState>) -> WebResult<Response> {
+    let channels = state.settings.ak.channels()?;
+    Ok(json(
+        channels.into_iter().map(Channel::from).collect::<Vec<_>>(),
+    ))
 }
 
 #[debug_handler]
@@ -54,12 +158,186 @@ pub async fn get_version(
 }
 
 #[debug_handler]
-#[utoipa::path(get, path = "/version/{id}/files", tag = "version", responses((status = OK, body = [BundleDetails])))]
+#[utoipa::path(
+    delete,
+    path = "/version/{id}",
+    tag = "version",
+    responses(
+        (status = OK, description = "Version deleted, run gc to reclaim storage", body = DeletedVersion),
+        (status = 401, description = "Unauthorized - invalid or missing authentication token"),
+        (status = 404, description = "Version not found"),
+        (status = 409, description = "Version is still downloading")
+    ),
+    security(("torappu-auth" = ["admin:write"]))
+)]
+pub async fn delete_version(
+    _: Authorized<AdminWriteScope>,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> WebResult<Response> {
+    match state.database.delete_version(id).await? {
+        VersionDeletion::Deleted(deleted) => Ok(json(deleted)),
+        VersionDeletion::NotFound => Err(WebError::NotFound),
+        VersionDeletion::Downloading => Err(WebError::Conflict(format!(
+            "version {id} is still downloading"
+        ))),
+    }
+}
+
+#[debug_handler]
+#[utoipa::path(get, path = "/version/{id}/packs", tag = "version", responses((status = OK, body = [PackSummary])))]
+pub async fn get_version_packs(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> WebResult<Response> {
+    state
+        .database
+        .get_version_by_id(id)
+        .await?
+        .ok_or(WebError::NotFound)?;
+    Ok(json(state.database.query_version_packs(id).await?))
+}
+
+#[debug_handler]
+#[utoipa::path(get, path = "/version/{id}/packs/{pid}", tag = "version", responses((status = OK, body = PackDetails)))]
+pub async fn get_version_pack(
+    State(state): State<AppState>,
+    Path((id, pid)): Path<(i32, String)>,
+) -> WebResult<Response> {
+    let pack = state
+        .database
+        .query_version_pack(id, &pid)
+        .await?
+        .ok_or(WebError::NotFound)?;
+    Ok(json(pack))
+}
+
+#[debug_handler]
+#[utoipa::path(get, path = "/version/{id}/stats", tag = "stats", responses((status = OK, body = VersionStats)))]
+pub async fn get_version_stats(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> WebResult<Response> {
+    state
+        .database
+        .get_version_by_id(id)
+        .await?
+        .ok_or(WebError::NotFound)?;
+    Ok(json(state.database.query_version_stats(id).await?))
+}
+
+#[debug_handler]
+#[utoipa::path(get, path = "/stats", tag = "stats", responses((status = OK, body = StorageStats)))]
+pub async fn get_stats(State(state): State<AppState>) -> WebResult<Response> {
+    Ok(json(state.database.query_storage_stats().await?))
+}
+
+#[debug_handler]
+#[utoipa::path(get, path = "/version/{id}/files", tag = "version", params(VersionFilesQuery), responses((status = OK, body = Listing<BundleDetails>)))]
 pub async fn get_files_by_version(
     State(state): State<AppState>,
     Path(id): Path<i32>,
+    Query(query): Query<VersionFilesQuery>,
+) -> WebResult<Response> {
+    if query.legacy {
+        return Ok(json(Listing::Legacy(
+            state.database.query_bundles_by_version_id(id).await?,
+        )));
+    }
+    let filter = BundleFilter {
+        version: Some(id),
+        sort: query.sort.unwrap_or_default(),
+        descending: matches!(query.order, Some(SortOrder::Desc)),
+        ..Default::default()
+    };
+    bundle_page(&state, &filter, query.cursor.as_deref(), query.limit).await
+}
+
+#[debug_handler]
+#[utoipa::path(get, path = "/version/{id}/failures", tag = "version", responses((status = OK, body = [BundleFailure])))]
+pub async fn get_version_failures(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> WebResult<Response> {
+    if !state.database.is_version_exist(id).await? {
+        return Err(WebError::NotFound);
+    }
+    Ok(json(state.database.query_bundle_failures(id).await?))
+}
+
+#[debug_handler]
+#[utoipa::path(get, path = "/version/{id}/progress", tag = "version", responses((status = OK, body = VersionProgress)))]
+pub async fn get_version_progress(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
 ) -> WebResult<Response> {
-    Ok(json(state.database.query_bundles_by_version_id(id).await?))
+    let result = state
+        .database
+        .query_download_progress(id)
+        .await?
+        .ok_or(WebError::NotFound)?;
+    Ok(json(result))
+}
+
+/// Emits a `progress` event whenever the stored progress changes. The stream
+/// closes once no sync run is active, the download status is settled, the
+/// progress has not moved for ten minutes or the database fails.
+#[debug_handler]
+#[utoipa::path(get, path = "/version/{id}/progress/stream", tag = "version", responses((status = OK, content_type = "text/event-stream", body = VersionProgress)))]
+pub async fn stream_version_progress(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> WebResult<Sse<impl Stream<Item = Result<Event, axum::Error>>>> {
+    if !state.database.is_version_exist(id).await? {
+        return Err(WebError::NotFound);
+    }
+
+    let stream = stream::unfold(
+        Some((state, None, Instant::now())),
+        move |cursor| async move {
+            let (state, last, changed_at) = cursor?;
+            loop {
+                let (progress, pending) = match poll_version_progress(&state, id).await {
+                    Ok(polled) => polled,
+                    Err(err) => {
+                        warn!("Failed to query progress of version {id}: {err}");
+                        return None;
+                    }
+                };
+                let active = pending
+                    && progress
+                        .as_ref()
+                        .is_some_and(|progress| progress.finished_at.is_none());
+
+                if let Some(progress) = progress.filter(|progress| last.as_ref() != Some(progress))
+                {
+                    let event = Event::default().event("progress").json_data(&progress);
+                    let next = active.then(|| (state, Some(progress), Instant::now()));
+                    return Some((event, next));
+                }
+                if !active {
+                    return None;
+                }
+                if changed_at.elapsed() >= PROGRESS_MAX_IDLE {
+                    return None;
+                }
+                sleep(PROGRESS_POLL_INTERVAL).await;
+            }
+        },
+    );
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// The stored progress of a version and whether its download is still
+/// pending.
+async fn poll_version_progress(
+    state: &AppState,
+    id: i32,
+) -> AppResult<(Option<VersionProgress>, bool)> {
+    let progress = state.database.query_download_progress(id).await?;
+    let pending = state.database.is_version_download_pending(id).await?;
+    Ok((progress, pending))
 }
 
 #[utoipa::path(get, path = "/bundle/{id}", tag="bundle", responses((status = OK, body = BundleDetails)))]
@@ -73,17 +351,269 @@ pub async fn get_bundle(State(state): State<AppState>, Path(id): Path<i32>) -> W
 }
 
 #[debug_handler]
-#[utoipa::path(get, path = "/bundle", tag="bundle", params(BundleListQuery), responses((status = OK, body = [BundleDetails])))]
+#[utoipa::path(
+    get,
+    path = "/bundle/{id}/content",
+    tag = "bundle",
+    responses(
+        (status = OK, description = "Stored bundle content", content_type = "application/octet-stream"),
+        (status = PARTIAL_CONTENT, description = "Requested byte range of the stored bundle content", content_type = "application/octet-stream"),
+        (status = NOT_FOUND, description = "Bundle or stored object not found"),
+        (status = RANGE_NOT_SATISFIABLE, description = "Requested range is outside the stored object")
+    )
+)]
+pub async fn get_bundle_content(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    headers: HeaderMap,
+) -> WebResult<Response> {
+    let bundle = state
+        .database
+        .query_bundle_by_id_with_details(id)
+        .await?
+        .ok_or(WebError::NotFound)?;
+    stored_content(
+        &state,
+        &bundle.file_hash,
+        &dat_file_name(&bundle.path),
+        &headers,
+    )
+    .await
+}
+
+#[debug_handler]
+#[utoipa::path(
+    get,
+    path = "/file/{hash}/content",
+    tag = "bundle",
+    params(("hash" = String, Path, description = "SHA-256 of the stored file")),
+    responses(
+        (status = OK, description = "Stored file content", content_type = "application/octet-stream"),
+        (status = PARTIAL_CONTENT, description = "Requested byte range of the stored file content", content_type = "application/octet-stream"),
+        (status = BAD_REQUEST, description = "Malformed hash"),
+        (status = NOT_FOUND, description = "File or stored object not found"),
+        (status = RANGE_NOT_SATISFIABLE, description = "Requested range is outside the stored object")
+    )
+)]
+pub async fn get_file_content(
+    State(state): State<AppState>,
+    Path(hash): Path<String>,
+    headers: HeaderMap,
+) -> WebResult<Response> {
+    if hash.len() != 64 || !hash.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return Err(WebError::BadRequest(format!("invalid file hash: {hash}")));
+    }
+    state
+        .database
+        .get_file_by_hash(&hash)
+        .await?
+        .ok_or(WebError::NotFound)?;
+    let file_name = state
+        .database
+        .get_latest_bundle_path_by_hash(&hash)
+        .await?
+        .map_or_else(|| format!("{hash}.dat"), |path| dat_file_name(&path));
+
+    stored_content(&state, &hash, &file_name, &headers).await
+}
+
+async fn stored_content(
+    state: &AppState,
+    hash: &str,
+    file_name: &str,
+    headers: &HeaderMap,
+) -> WebResult<Response> {
+    let path = object_path(hash);
+    let meta = state.storage.head(&path).await?.ok_or(WebError::NotFound)?;
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| parse_byte_range(value, meta.size))
+        .transpose()?
+        .flatten();
+    let stream = state.storage.get_stream(&path, range.clone()).await?;
+
+    let builder = Response::builder()
+        .header(header::CONTENT_TYPE, "application/octet-stream")
+        .header(header::ACCEPT_RANGES, "bytes")
+        .header(header::CONTENT_DISPOSITION, content_disposition(file_name));
+    let builder = match range {
+        Some(range) => builder
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(
+                header::CONTENT_RANGE,
+                format!("bytes {}-{}/{}", range.start, range.end - 1, meta.size),
+            )
+            .header(header::CONTENT_LENGTH, range.end - range.start),
+        None => builder
+            .status(StatusCode::OK)
+            .header(header::CONTENT_LENGTH, meta.size),
+    };
+
+    builder
+        .body(Body::from_stream(stream))
+        .map_err(|err| WebError::CustomApiError(AppError::Application(err.into())))
+}
+
+#[debug_handler]
+#[utoipa::path(get, path = "/bundle/history", tag = "bundle", params(BundleHistoryQuery), responses((status = OK, body = [HistoryEntry])))]
+pub async fn get_bundle_history(
+    State(state): State<AppState>,
+    Query(query): Query<BundleHistoryQuery>,
+) -> WebResult<Response> {
+    let channel = match query.channel {
+        Some(channel) => channel,
+        None => state.settings.ak.channels()?.swap_remove(0).name,
+    };
+    let history = state
+        .database
+        .query_bundle_history(&query.path, &channel)
+        .await?;
+    if history.is_empty() {
+        return Err(WebError::NotFound);
+    }
+    Ok(json(history))
+}
+
+#[debug_handler]
+#[utoipa::path(get, path = "/bundle/search", tag = "bundle", params(BundleSearchQuery), responses((status = OK, body = Page<BundleSearchHit>)))]
+pub async fn search_bundles(
+    State(state): State<AppState>,
+    Query(params): Query<BundleSearchQuery>,
+) -> WebResult<Response> {
+    let after = params
+        .cursor
+        .as_deref()
+        .map(decode_cursor::<SearchCursor>)
+        .transpose()?;
+    let limit = params
+        .limit
+        .unwrap_or(SEARCH_DEFAULT_LIMIT)
+        .clamp(1, SEARCH_MAX_LIMIT);
+    let (hits, total) = state
+        .database
+        .search_bundles(params.version, &params.q, after.as_ref(), limit + 1)
+        .await?;
+    Ok(json(Page::from_rows(hits, limit, total, |hit| {
+        SearchCursor::from(hit)
+    })))
+}
+
+#[debug_handler]
+#[utoipa::path(get, path = "/bundle", tag="bundle", params(BundleListQuery), responses((status = OK, body = Listing<BundleDetails>)))]
 pub async fn filter_bundle(
     State(state): State<AppState>,
-    Query(query): Query<BundleListQuery>,
+    Query(mut query): Query<BundleListQuery>,
 ) -> WebResult<Response> {
-    Ok(json(
-        state
-            .database
-            .query_bundles_with_details(&query.into())
-            .await?,
-    ))
+    let (legacy, cursor, limit) = (query.legacy, query.cursor.take(), query.limit);
+    let filter = query.into();
+    if legacy {
+        return Ok(json(Listing::Legacy(
+            state.database.query_bundles_with_details(&filter).await?,
+        )));
+    }
+    bundle_page(&state, &filter, cursor.as_deref(), limit).await
+}
+
+async fn bundle_page(
+    state: &AppState,
+    filter: &BundleFilter,
+    cursor: Option<&str>,
+    limit: Option<i64>,
+) -> WebResult<Response> {
+    let after = decode_page_cursor(cursor, filter.sort.is_numeric())?;
+    let limit = page_limit(limit);
+    let bundles = state
+        .database
+        .query_bundle_page(filter, after.as_ref(), limit + 1)
+        .await?;
+    let total = state.database.count_bundles(filter).await?;
+    Ok(json(Listing::Page(Page::from_rows(
+        bundles,
+        limit,
+        total,
+        |bundle| filter.sort.cursor(bundle),
+    ))))
+}
+
+fn page_limit(limit: Option<i64>) -> i64 {
+    limit.unwrap_or(PAGE_DEFAULT_LIMIT).clamp(1, PAGE_MAX_LIMIT)
+}
+
+/// Decodes the cursor of a sorted list, rejecting one whose key does not fit
+/// the requested sort.
+fn decode_page_cursor(cursor: Option<&str>, numeric: bool) -> WebResult<Option<SortCursor>> {
+    let after = cursor.map(decode_cursor::<SortCursor>).transpose()?;
+    if after.as_ref().is_some_and(|after| !after.fits(numeric)) {
+        return Err(WebError::BadRequest(
+            "cursor does not match the requested sort".to_string(),
+        ));
+    }
+    Ok(after)
+}
+
+#[debug_handler]
+#[utoipa::path(
+    get,
+    path = "/diff",
+    tag = "diff",
+    params(DiffQuery),
+    responses(
+        (status = OK, body = VersionDiff),
+        (status = NOT_FOUND, description = "Version not found")
+    )
+)]
+pub async fn diff_versions(
+    State(state): State<AppState>,
+    Query(DiffQuery { from, to }): Query<DiffQuery>,
+) -> WebResult<Response> {
+    for id in [from, to] {
+        if !state.database.is_version_exist(id).await? {
+            return Err(WebError::NotFound);
+        }
+    }
+    Ok(json(state.database.query_bundle_diff(from, to).await?))
+}
+
+#[debug_handler]
+#[utoipa::path(
+    get,
+    path = "/manifest/diff",
+    tag = "manifest",
+    params(ManifestDiffQuery),
+    responses(
+        (status = OK, description = "Asset-level diff", body = ManifestDiff),
+        (status = NOT_FOUND, description = "Version not found"),
+        (status = CONFLICT, description = "Asset mappings of a version are not imported")
+    )
+)]
+pub async fn diff_manifests(
+    State(state): State<AppState>,
+    Query(query): Query<ManifestDiffQuery>,
+) -> WebResult<Response> {
+    for id in [query.from, query.to] {
+        if !state.database.is_version_exist(id).await? {
+            return Err(WebError::NotFound);
+        }
+    }
+    let limit = query
+        .limit
+        .unwrap_or(MANIFEST_DIFF_DEFAULT_LIMIT)
+        .clamp(1, MANIFEST_DIFF_MAX_LIMIT);
+    let diff = state
+        .database
+        .query_manifest_diff(
+            query.from,
+            query.to,
+            query.prefix.as_deref(),
+            query.offset.unwrap_or_default(),
+            limit,
+        )
+        .await?
+        .ok_or_else(|| {
+            WebError::Conflict("asset mappings of both versions must be imported".to_string())
+        })?;
+    Ok(json(diff))
 }
 
 #[debug_handler]
@@ -118,20 +648,102 @@ pub async fn get_manifest_detail(
 }
 
 #[debug_handler]
-#[utoipa::path(get, path = "/manifest/{version_id}/search", tag = "manifest", params(ManifestSearchQuery), responses((status = OK, body = [ManifestNode])))]
+#[utoipa::path(get, path = "/manifest/{version_id}/dependencies", tag = "manifest", params(ManifestDetailQuery), responses((status = OK, body = BundleClosure)))]
+pub async fn get_manifest_dependencies(
+    State(state): State<AppState>,
+    Path(version_id): Path<i32>,
+    Query(params): Query<ManifestDetailQuery>,
+) -> WebResult<Response> {
+    let closure = state
+        .database
+        .query_bundle_closure(version_id, &params.asset_name)
+        .await?
+        .ok_or(WebError::NotFound)?;
+    Ok(json(closure))
+}
+
+#[debug_handler]
+#[utoipa::path(get, path = "/asset/history", tag = "manifest", params(AssetHistoryQuery), responses((status = OK, body = [HistoryEntry])))]
+pub async fn get_asset_history(
+    State(state): State<AppState>,
+    Query(query): Query<AssetHistoryQuery>,
+) -> WebResult<Response> {
+    let channel = match query.channel {
+        Some(channel) => channel,
+        None => state.settings.ak.channels()?.swap_remove(0).name,
+    };
+    let history = state
+        .database
+        .query_asset_history(&query.asset_name, &channel)
+        .await?;
+    if history.is_empty() {
+        return Err(WebError::NotFound);
+    }
+    Ok(json(history))
+}
+
+#[debug_handler]
+#[utoipa::path(get, path = "/manifest/{version_id}/search", tag = "manifest", params(ManifestSearchQuery), responses((status = OK, body = Page<ManifestSearchHit>)))]
 pub async fn search_manifest(
     State(state): State<AppState>,
     Path(version_id): Path<i32>,
     Query(params): Query<ManifestSearchQuery>,
 ) -> WebResult<Response> {
+    let after = params
+        .cursor
+        .as_deref()
+        .map(decode_cursor::<SearchCursor>)
+        .transpose()?;
+    let limit = params
+        .limit
+        .unwrap_or(SEARCH_DEFAULT_LIMIT)
+        .clamp(1, SEARCH_MAX_LIMIT);
+    let version_id = (!params.all_versions).then_some(version_id);
+    let (hits, total) = state
+        .database
+        .search_manifest(version_id, &params.q, after.as_ref(), limit + 1)
+        .await?;
+    Ok(json(Page::from_rows(hits, limit, total, |hit| {
+        SearchCursor::from(hit)
+    })))
+}
+
+#[debug_handler]
+#[utoipa::path(get, path = "/storage/replication", tag = "storage", responses((status = OK, body = [ReplicaStatus])))]
+pub async fn get_replication_status(State(state): State<AppState>) -> WebResult<Response> {
     Ok(json(
         state
             .database
-            .search_manifest(version_id, &params.q)
+            .query_replication_status(&state.settings.storage.replica_names())
             .await?,
     ))
 }
 
+#[debug_handler]
+#[utoipa::path(
+    get,
+    path = "/worker/leader",
+    tag = "worker",
+    responses(
+        (status = OK, description = "Worker currently running the singleton duties", body = WorkerLeader),
+        (status = NOT_FOUND, description = "No worker holds the leader lock")
+    )
+)]
+pub async fn get_worker_leader(State(state): State<AppState>) -> WebResult<Response> {
+    let leader = state
+        .database
+        .query_worker_leader()
+        .await?
+        .ok_or(WebError::NotFound)?;
+    Ok(json(leader))
+}
+
+#[debug_handler]
+#[utoipa::path(get, path = "/worker/status", tag = "worker", responses((status = OK, description = "Heartbeat and last run of each worker component", body = [WorkerStatus])))]
+pub async fn get_worker_status(State(state): State<AppState>) -> WebResult<Response> {
+    Ok(json(state.database.query_worker_status().await?))
+}
+
 #[debug_handler]
 #[utoipa::path(
     get,
@@ -155,6 +767,193 @@ pub async fn get_item_demand(
     Ok(([(header::CONTENT_TYPE, "application/json")], usage).into_response())
 }
 
+#[debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/version/check",
+    tag = "admin",
+    responses(
+        (status = ACCEPTED, description = "Version check queued for the worker", body = AdminCommand),
+        (status = 401, description = "Unauthorized - invalid or missing authentication token")
+    ),
+    security(("torappu-auth" = ["admin:write"]))
+)]
+pub async fn queue_version_check(
+    _: Authorized<AdminWriteScope>,
+    State(state): State<AppState>,
+) -> WebResult<Response> {
+    let command = state
+        .database
+        .enqueue_admin_command(AdminCommandKind::CheckVersion, None, None)
+        .await?;
+    Ok(accepted(command))
+}
+
+#[debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/version/{id}/download",
+    tag = "admin",
+    responses(
+        (status = ACCEPTED, description = "Download queued for the worker", body = AdminCommand),
+        (status = 401, description = "Unauthorized - invalid or missing authentication token"),
+        (status = 404, description = "Version not found")
+    ),
+    security(("torappu-auth" = ["admin:write"]))
+)]
+pub async fn queue_version_download(
+    _: Authorized<AdminWriteScope>,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> WebResult<Response> {
+    queue_version_command(&state, AdminCommandKind::DownloadVersion, id).await
+}
+
+#[debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/version/{id}/reset",
+    tag = "admin",
+    responses(
+        (status = ACCEPTED, description = "Reset of the download and asset mapping status queued for the worker", body = AdminCommand),
+        (status = 401, description = "Unauthorized - invalid or missing authentication token"),
+        (status = 404, description = "Version not found")
+    ),
+    security(("torappu-auth" = ["admin:write"]))
+)]
+pub async fn queue_version_reset(
+    _: Authorized<AdminWriteScope>,
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> WebResult<Response> {
+    queue_version_command(&state, AdminCommandKind::ResetVersion, id).await
+}
+
+async fn queue_version_command(
+    state: &AppState,
+    kind: AdminCommandKind,
+    id: i32,
+) -> WebResult<Response> {
+    if !state.database.is_version_exist(id).await? {
+        return Err(WebError::NotFound);
+    }
+    let command = state
+        .database
+        .enqueue_admin_command(kind, Some(id), None)
+        .await?;
+    Ok(accepted(command))
+}
+
+#[debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/manifest/{res_version}/import",
+    tag = "admin",
+    params(("res_version" = String, Path, description = "Res version of the primary channel")),
+    responses(
+        (status = ACCEPTED, description = "Asset mapping reimport queued for the worker", body = AdminCommand),
+        (status = 401, description = "Unauthorized - invalid or missing authentication token"),
+        (status = 404, description = "Version not found")
+    ),
+    security(("torappu-auth" = ["admin:write"]))
+)]
+pub async fn queue_asset_mapping_import(
+    _: Authorized<AdminWriteScope>,
+    State(state): State<AppState>,
+    Path(res_version): Path<String>,
+) -> WebResult<Response> {
+    let channel = state.settings.ak.channels()?.swap_remove(0).name;
+    state
+        .database
+        .get_version_by_res(&channel, &res_version)
+        .await?
+        .ok_or(WebError::NotFound)?;
+    let command = state
+        .database
+        .enqueue_admin_command(
+            AdminCommandKind::ImportAssetMapping,
+            None,
+            Some(&res_version),
+        )
+        .await?;
+    Ok(accepted(command))
+}
+
+#[debug_handler]
+#[utoipa::path(
+    post,
+    path = "/admin/item-demand/import",
+    tag = "admin",
+    responses(
+        (status = ACCEPTED, description = "Item demand reimport queued for the worker", body = AdminCommand),
+        (status = 401, description = "Unauthorized - invalid or missing authentication token")
+    ),
+    security(("torappu-auth" = ["admin:write"]))
+)]
+pub async fn queue_item_demand_import(
+    _: Authorized<AdminWriteScope>,
+    State(state): State<AppState>,
+) -> WebResult<Response> {
+    let command = state
+        .database
+        .enqueue_admin_command(AdminCommandKind::ImportItemDemand, None, None)
+        .await?;
+    Ok(accepted(command))
+}
+
+#[debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/command",
+    tag = "admin",
+    params(AdminCommandListQuery),
+    responses(
+        (status = OK, body = [AdminCommand]),
+        (status = 401, description = "Unauthorized - invalid or missing authentication token")
+    ),
+    security(("torappu-auth" = ["admin:read"]))
+)]
+pub async fn list_admin_commands(
+    _: Authorized<AdminReadScope>,
+    State(state): State<AppState>,
+    Query(query): Query<AdminCommandListQuery>,
+) -> WebResult<Response> {
+    let limit = query
+        .limit
+        .unwrap_or(ADMIN_COMMAND_DEFAULT_LIMIT)
+        .clamp(1, ADMIN_COMMAND_MAX_LIMIT);
+    Ok(json(state.database.query_admin_commands(limit).await?))
+}
+
+#[debug_handler]
+#[utoipa::path(
+    get,
+    path = "/admin/command/{id}",
+    tag = "admin",
+    responses(
+        (status = OK, body = AdminCommand),
+        (status = 401, description = "Unauthorized - invalid or missing authentication token"),
+        (status = 404, description = "Command not found")
+    ),
+    security(("torappu-auth" = ["admin:read"]))
+)]
+pub async fn get_admin_command(
+    _: Authorized<AdminReadScope>,
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> WebResult<Response> {
+    let command = state
+        .database
+        .query_admin_command(id)
+        .await?
+        .ok_or(WebError::NotFound)?;
+    Ok(json(command))
+}
+
+fn accepted(command: AdminCommand) -> Response {
+    (StatusCode::ACCEPTED, Json(command)).into_response()
+}
+
 #[utoipa::path(
     post,
     path = "/docker/launch",
@@ -166,28 +965,13 @@ pub async fn get_item_demand(
         (status = 400, description = "Bad request - invalid parameters"),
         (status = 500, description = "Internal server error")
     ),
-    security(("torappu-auth" = []))
+    security(("torappu-auth" = ["docker:launch"]))
 )]
 pub async fn launch_container(
+    _: Authorized<DockerLaunchScope>,
     State(state): State<AppState>,
-    headers: axum::http::HeaderMap,
     Json(payload): Json<DockerLaunchRequest>,
 ) -> Result<Json<DockerLaunchResponse>, WebError> {
-    let auth_header = headers
-        .get("torappu-auth")
-        .ok_or(WebError::Unauthorized(
-            "Missing torappu-auth header".to_string(),
-        ))?
-        .to_str()
-        .map_err(|_| WebError::Unauthorized("Invalid torappu-auth header format".to_string()))?;
-
-    let expected_token = state.settings.torappu.token.as_str();
-    if auth_header != expected_token {
-        return Err(WebError::Unauthorized(
-            "Invalid authentication token".to_string(),
-        ));
-    }
-
     if payload.client_version.is_empty() || payload.res_version.is_empty() {
         return Err(WebError::BadRequest(
             "client_version and res_version cannot be empty".to_string(),
@@ -1,19 +1,39 @@
+use crate::{
+    api::{
+        AppState,
+        error::{WebError, WebResult},
+    },
+    database::api_key::ApiScope,
+    service::api_key::{api_key_prefix, scopes_grant, verify_api_key},
+};
 use axum::{
     Router,
-    http::{HeaderValue, Response, header::CONTENT_TYPE},
+    extract::FromRequestParts,
+    http::{HeaderValue, Response, header::CONTENT_TYPE, request::Parts},
     routing::{MethodRouter, get_service},
 };
-use std::{path::PathBuf, time::Duration};
+use chrono::Utc;
+use std::{marker::PhantomData, path::PathBuf, time::Duration};
+use subtle::ConstantTimeEq;
 use tower::ServiceBuilder;
 use tower_http::{
-    compression::CompressionLayer, services::ServeDir, set_header::SetResponseHeaderLayer,
+    compression::{
+        CompressionLayer,
+        predicate::{DefaultPredicate, NotForContentType, Predicate},
+    },
+    services::ServeDir,
+    set_header::SetResponseHeaderLayer,
     timeout::RequestBodyTimeoutLayer,
 };
 
+pub const API_KEY_HEADER: &str = "torappu-auth";
+
 pub fn apply_axum_middleware(router: Router) -> Router {
     router
         .layer(RequestBodyTimeoutLayer::new(Duration::from_secs(10)))
-        .layer(CompressionLayer::new())
+        .layer(CompressionLayer::new().compress_when(
+            DefaultPredicate::new().and(NotForContentType::const_new("application/octet-stream")),
+        ))
 }
 
 fn set_text_plain_charset<B>(response: &Response<B>) -> Option<HeaderValue> {
@@ -33,3 +53,94 @@ pub fn serve_dir_with_charset(path: PathBuf) -> MethodRouter {
             .service(ServeDir::new(path)),
     )
 }
+
+/// Scope a route requires, as a type so routes can state it in their
+/// extractor list.
+pub trait RequiredScope {
+    const SCOPE: ApiScope;
+}
+
+pub struct DockerLaunchScope;
+pub struct AdminReadScope;
+pub struct AdminWriteScope;
+
+impl RequiredScope for DockerLaunchScope {
+    const SCOPE: ApiScope = ApiScope::DockerLaunch;
+}
+
+impl RequiredScope for AdminReadScope {
+    const SCOPE: ApiScope = ApiScope::AdminRead;
+}
+
+impl RequiredScope for AdminWriteScope {
+    const SCOPE: ApiScope = ApiScope::AdminWrite;
+}
+
+/// Rejects the request unless its `torappu-auth` header carries an active
+/// API key holding the scope `S`.
+pub struct Authorized<S>(PhantomData<S>);
+
+impl<S: RequiredScope> FromRequestParts<AppState> for Authorized<S> {
+    type Rejection = WebError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> WebResult<Self> {
+        let key = parts
+            .headers
+            .get(API_KEY_HEADER)
+            .ok_or_else(|| WebError::Unauthorized(format!("Missing {API_KEY_HEADER} header")))?
+            .to_str()
+            .map_err(|_| {
+                WebError::Unauthorized(format!("Invalid {API_KEY_HEADER} header format"))
+            })?;
+        authorize(state, key, S::SCOPE).await?;
+        Ok(Self(PhantomData))
+    }
+}
+
+async fn authorize(state: &AppState, key: &str, scope: ApiScope) -> WebResult<()> {
+    if let Some(token) = state.settings.torappu.token.as_deref()
+        && bool::from(token.as_bytes().ct_eq(key.as_bytes()))
+    {
+        return if scope == ApiScope::DockerLaunch {
+            Ok(())
+        } else {
+            Err(WebError::Forbidden(format!(
+                "The torappu token lacks the {scope} scope"
+            )))
+        };
+    }
+
+    let invalid = || WebError::Unauthorized("Invalid API key".to_string());
+    let prefix = api_key_prefix(key).ok_or_else(invalid)?;
+    let api_key = state
+        .database
+        .find_api_key(prefix)
+        .await?
+        .filter(|api_key| verify_api_key(key, &api_key.key_hash))
+        .ok_or_else(invalid)?;
+
+    if api_key.revoked_at.is_some() {
+        return Err(WebError::Unauthorized(format!(
+            "API key {} is revoked",
+            api_key.name
+        )));
+    }
+    if api_key
+        .expires_at
+        .is_some_and(|expires_at| expires_at <= Utc::now())
+    {
+        return Err(WebError::Unauthorized(format!(
+            "API key {} has expired",
+            api_key.name
+        )));
+    }
+    if !scopes_grant(&api_key.scopes, scope) {
+        return Err(WebError::Forbidden(format!(
+            "API key {} lacks the {scope} scope",
+            api_key.name
+        )));
+    }
+
+    state.database.touch_api_key(api_key.id).await?;
+    Ok(())
+}
@@ -1,29 +1,61 @@
 #![allow(clippy::needless_for_each)]
 
-use crate::api::{
-    AppState, handlers,
-    middleware::{apply_axum_middleware, serve_dir_with_charset},
+use crate::{
+    api::{
+        AppState, handlers,
+        middleware::{API_KEY_HEADER, apply_axum_middleware, serve_dir_with_charset},
+        types::SortOrder,
+    },
+    database::{bundle::BundleSort, version::VersionSort},
 };
 use axum::{Json, Router, routing::get};
 use std::path::PathBuf;
-use utoipa::OpenApi;
+use utoipa::{
+    Modify, OpenApi,
+    openapi::security::{ApiKey, ApiKeyValue, SecurityScheme},
+};
 use utoipa_axum::{router::OpenApiRouter, routes};
 use utoipa_scalar::{Scalar, Servable};
 
 #[derive(OpenApi)]
 #[openapi(
+    modifiers(&SecurityAddon),
+    // Query parameters only reference these, so the routes do not collect them.
+    components(schemas(BundleSort, SortOrder, VersionSort)),
     tags(
         (name = "version", description = "Version management endpoints"),
         (name = "bundle", description = "Bundle management endpoints"),
+        (name = "diff", description = "Version diff endpoints"),
         (name = "item", description = "Item demand endpoints"),
         (name = "health", description = "Health check endpoints"),
         (name = "files", description = "File system endpoints"),
         (name = "docker", description = "Docker container management endpoints"),
         (name = "manifest", description = "Manifest browser endpoints"),
+        (name = "storage", description = "Storage replication endpoints"),
+        (name = "stats", description = "Storage statistics endpoints"),
+        (name = "admin", description = "Worker operations queued for the worker process"),
+        (name = "worker", description = "Worker process endpoints"),
     ),
 )]
 pub struct ApiDoc;
 
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                API_KEY_HEADER,
+                SecurityScheme::ApiKey(ApiKey::Header(ApiKeyValue::with_description(
+                    API_KEY_HEADER,
+                    "API key created with `create-api-key`. Each operation lists the scope it requires.",
+                ))),
+            );
+    }
+}
+
 pub fn build_router(state: AppState) -> Router {
     let (api_routes, mut openapi) = OpenApiRouter::with_openapi(ApiDoc::openapi())
         .routes(routes!(handlers::ping))
@@ -31,16 +63,42 @@ pub fn build_router(state: AppState) -> Router {
         .routes(routes!(handlers::list_asset))
         .route("/files/", get(handlers::list_root_asset))
         .routes(routes!(handlers::search_assets_by_path))
+        .routes(routes!(handlers::list_channels))
         .routes(routes!(handlers::list_version))
-        .routes(routes!(handlers::get_version))
+        .routes(routes!(handlers::get_version, handlers::delete_version))
         .routes(routes!(handlers::get_files_by_version))
+        .routes(routes!(handlers::get_version_failures))
+        .routes(routes!(handlers::get_version_progress))
+        .routes(routes!(handlers::get_version_packs))
+        .routes(routes!(handlers::get_version_pack))
+        .routes(routes!(handlers::get_version_stats))
+        .routes(routes!(handlers::get_stats))
+        .routes(routes!(handlers::stream_version_progress))
         .routes(routes!(handlers::get_bundle))
+        .routes(routes!(handlers::get_bundle_history))
+        .routes(routes!(handlers::search_bundles))
+        .routes(routes!(handlers::get_bundle_content))
+        .routes(routes!(handlers::get_file_content))
         .routes(routes!(handlers::filter_bundle))
+        .routes(routes!(handlers::diff_versions))
+        .routes(routes!(handlers::diff_manifests))
         .routes(routes!(handlers::list_manifest_children))
         .routes(routes!(handlers::get_manifest_detail))
+        .routes(routes!(handlers::get_manifest_dependencies))
         .routes(routes!(handlers::search_manifest))
+        .routes(routes!(handlers::get_asset_history))
+        .routes(routes!(handlers::get_replication_status))
+        .routes(routes!(handlers::get_worker_leader))
+        .routes(routes!(handlers::get_worker_status))
         .routes(routes!(handlers::get_item_demand))
         .routes(routes!(handlers::launch_container))
+        .routes(routes!(handlers::queue_version_check))
+        .routes(routes!(handlers::queue_version_download))
+        .routes(routes!(handlers::queue_version_reset))
+        .routes(routes!(handlers::queue_asset_mapping_import))
+        .routes(routes!(handlers::queue_item_demand_import))
+        .routes(routes!(handlers::list_admin_commands))
+        .routes(routes!(handlers::get_admin_command))
         .split_for_parts();
 
     openapi.paths.paths = openapi
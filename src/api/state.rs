@@ -2,7 +2,7 @@ use crate::{
     AppResult,
     config::AppSettings,
     database::Database,
-    external::{docker::DockerClient, torappu::TorappuClient},
+    external::{docker::DockerClient, storage::Storage, torappu::TorappuClient},
 };
 use std::{path::PathBuf, sync::Arc};
 use tracing::{info, warn};
@@ -13,11 +13,13 @@ pub struct AppState {
     pub settings: Arc<AppSettings>,
     pub torappu: TorappuClient,
     pub docker: Option<DockerClient>,
+    pub storage: Storage,
 }
 
 impl AppState {
     pub async fn from_settings(settings: Arc<AppSettings>) -> AppResult<Self> {
         let database = Database::connect(&settings.database).await?;
+        let storage = Storage::from_settings(&settings)?;
         let docker = settings.torappu.docker.as_ref().map_or_else(
             || {
                 info!("Docker configuration not found, skipping Docker service");
@@ -41,6 +43,7 @@ impl AppState {
             },
             settings,
             docker,
+            storage,
         })
     }
 }
@@ -1,4 +1,15 @@
-use crate::database::bundle::BundleFilter;
+// `ToSchema` expands generic fields of `Page` into `if let` chains.
+#![allow(clippy::option_if_let_else)]
+
+use crate::{
+    api::utils::encode_cursor,
+    config::AkChannelConfig,
+    database::{
+        bundle::{BundleFilter, BundleSort},
+        model::WorkerStatus,
+        version::VersionSort,
+    },
+};
 use serde::Deserialize;
 use utoipa::{IntoParams, ToSchema};
 
@@ -15,6 +26,22 @@ pub struct ManifestChildrenQuery {
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct ManifestSearchQuery {
     pub q: String,
+    /// Search every version instead of the one in the path.
+    #[serde(default)]
+    pub all_versions: bool,
+    pub cursor: Option<String>,
+    /// Page size, 50 by default and at most 500.
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BundleSearchQuery {
+    pub q: String,
+    /// Searches every version when omitted.
+    pub version: Option<i32>,
+    pub cursor: Option<String>,
+    /// Page size, 50 by default and at most 500.
+    pub limit: Option<i64>,
 }
 
 #[derive(Debug, Deserialize, IntoParams)]
@@ -22,12 +49,105 @@ pub struct ManifestDetailQuery {
     pub asset_name: String,
 }
 
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AssetHistoryQuery {
+    pub asset_name: String,
+    /// Defaults to the primary channel.
+    pub channel: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct BundleHistoryQuery {
+    pub path: String,
+    /// Defaults to the primary channel.
+    pub channel: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct VersionListQuery {
+    pub channel: Option<String>,
+    pub sort: Option<VersionSort>,
+    pub order: Option<SortOrder>,
+    /// Page size, 100 by default and at most 1000.
+    pub limit: Option<i64>,
+    /// `nextCursor` of the previous page.
+    pub cursor: Option<String>,
+    /// Return every row as a plain array instead of a page, for the bundled
+    /// frontend.
+    #[serde(default)]
+    pub legacy: bool,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct VersionFilesQuery {
+    pub sort: Option<BundleSort>,
+    pub order: Option<SortOrder>,
+    /// Page size, 100 by default and at most 1000.
+    pub limit: Option<i64>,
+    /// `nextCursor` of the previous page.
+    pub cursor: Option<String>,
+    /// Return every row as a plain array instead of a page, for the bundled
+    /// frontend.
+    #[serde(default)]
+    pub legacy: bool,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct DiffQuery {
+    pub from: i32,
+    pub to: i32,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ManifestDiffQuery {
+    pub from: i32,
+    pub to: i32,
+    /// Only compare assets whose name or asset path starts with this prefix.
+    pub prefix: Option<String>,
+    pub offset: Option<usize>,
+    /// Page size, 100 by default and at most 1000.
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct AdminCommandListQuery {
+    /// Number of most recent commands, 50 by default and at most 500.
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
 #[derive(Debug, Deserialize, IntoParams)]
 pub struct BundleListQuery {
     pub path: Option<String>,
     pub hash: Option<String>,
     pub file: Option<i32>,
     pub version: Option<i32>,
+    pub channel: Option<String>,
+    pub md5: Option<String>,
+    pub upstream_hash: Option<String>,
+    pub pid: Option<String>,
+    #[serde(rename = "type")]
+    pub bundle_type: Option<String>,
+    pub cid: Option<i32>,
+    pub min_total_size: Option<i64>,
+    pub max_total_size: Option<i64>,
+    pub sort: Option<BundleSort>,
+    pub order: Option<SortOrder>,
+    /// Page size, 100 by default and at most 1000.
+    pub limit: Option<i64>,
+    /// `nextCursor` of the previous page.
+    pub cursor: Option<String>,
+    /// Return every row as a plain array instead of a page, for the bundled
+    /// frontend.
+    #[serde(default)]
+    pub legacy: bool,
 }
 
 impl From<BundleListQuery> for BundleFilter {
@@ -37,6 +157,57 @@ impl From<BundleListQuery> for BundleFilter {
             hash: value.hash,
             file: value.file,
             version: value.version,
+            channel: value.channel,
+            md5: value.md5,
+            upstream_hash: value.upstream_hash,
+            pid: value.pid,
+            bundle_type: value.bundle_type,
+            cid: value.cid,
+            min_total_size: value.min_total_size,
+            max_total_size: value.max_total_size,
+            sort: value.sort.unwrap_or_default(),
+            descending: matches!(value.order, Some(SortOrder::Desc)),
+        }
+    }
+}
+
+/// One page of a list, `next_cursor` is absent on the last page.
+#[derive(Debug, serde::Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub total: i64,
+}
+
+/// A `Page` of rows, or every row as a plain array with `legacy`.
+#[derive(Debug, serde::Serialize, ToSchema)]
+#[serde(untagged)]
+pub enum Listing<T> {
+    Page(Page<T>),
+    Legacy(Vec<T>),
+}
+
+impl<T> Page<T> {
+    /// Builds a page from up to `limit + 1` fetched rows; the extra row only
+    /// signals that another page follows.
+    pub fn from_rows<C: serde::Serialize>(
+        mut items: Vec<T>,
+        limit: i64,
+        total: i64,
+        cursor: impl Fn(&T) -> C,
+    ) -> Self {
+        let limit = usize::try_from(limit).unwrap_or_default();
+        let next_cursor = if items.len() > limit {
+            items.truncate(limit);
+            items.last().map(|item| encode_cursor(&cursor(item)))
+        } else {
+            None
+        };
+        Self {
+            items,
+            next_cursor,
+            total,
         }
     }
 }
@@ -46,6 +217,15 @@ pub struct Health {
     pub ok: bool,
 }
 
+#[derive(ToSchema, serde::Serialize)]
+pub struct HealthReport {
+    /// Whether the database is reachable and no worker component is stale or
+    /// stopped.
+    pub ok: bool,
+    pub database: bool,
+    pub workers: Vec<WorkerStatus>,
+}
+
 #[allow(clippy::struct_field_names)]
 #[derive(Debug, Deserialize, serde::Serialize, ToSchema)]
 pub struct DockerLaunchRequest {
@@ -57,6 +237,24 @@ pub struct DockerLaunchRequest {
     pub exclude: Option<String>,
 }
 
+/// A tracked AK channel, without its upstream URLs.
+#[derive(Debug, serde::Serialize, ToSchema)]
+pub struct Channel {
+    pub name: String,
+    pub server: String,
+    pub platform: String,
+}
+
+impl From<AkChannelConfig> for Channel {
+    fn from(value: AkChannelConfig) -> Self {
+        Self {
+            name: value.name,
+            server: value.server,
+            platform: value.platform,
+        }
+    }
+}
+
 #[derive(Debug, serde::Serialize, ToSchema)]
 pub struct DockerLaunchResponse {
     pub container_name: String,
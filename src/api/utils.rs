@@ -1,9 +1,75 @@
+use crate::api::error::{WebError, WebResult};
 use axum::{
     Json,
     response::{IntoResponse, Response},
 };
-use serde::Serialize;
+use serde::{Serialize, de::DeserializeOwned};
+use std::{fmt::Write, ops::Range};
 
 pub fn json<T: Serialize>(json: T) -> Response {
     Json(json).into_response()
 }
+
+/// Encodes a pagination cursor as an opaque hex string.
+pub fn encode_cursor<T: Serialize>(cursor: &T) -> String {
+    let bytes = serde_json::to_vec(cursor).unwrap_or_default();
+    bytes.iter().fold(String::new(), |mut out, byte| {
+        let _ = write!(out, "{byte:02x}");
+        out
+    })
+}
+
+pub fn decode_cursor<T: DeserializeOwned>(cursor: &str) -> WebResult<T> {
+    let invalid = || WebError::BadRequest(format!("invalid cursor: {cursor}"));
+    if !cursor.len().is_multiple_of(2) {
+        return Err(invalid());
+    }
+    let bytes = (0..cursor.len())
+        .step_by(2)
+        .map(|index| {
+            cursor
+                .get(index..index + 2)
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+        })
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(invalid)?;
+    serde_json::from_slice(&bytes).map_err(|_| invalid())
+}
+
+/// Parses a single `bytes=` range against an object of `size` bytes.
+///
+/// Unsupported forms (other units, multiple ranges, malformed values) yield `None`
+/// so the caller falls back to serving the whole object.
+pub fn parse_byte_range(value: &str, size: u64) -> WebResult<Option<Range<u64>>> {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return Ok(None);
+    };
+    if spec.contains(',') {
+        return Ok(None);
+    }
+    let Some((start, end)) = spec.trim().split_once('-') else {
+        return Ok(None);
+    };
+
+    let range = match (start.parse::<u64>().ok(), end.parse::<u64>().ok()) {
+        (Some(start), Some(end)) if start <= end => start..end.saturating_add(1).min(size),
+        (Some(start), None) if end.is_empty() => start..size,
+        (None, Some(suffix)) if start.is_empty() => {
+            if suffix == 0 {
+                return Err(WebError::RangeNotSatisfiable(size));
+            }
+            size.saturating_sub(suffix)..size
+        }
+        _ => return Ok(None),
+    };
+
+    if range.start >= size {
+        return Err(WebError::RangeNotSatisfiable(size));
+    }
+    Ok(Some(range))
+}
+
+pub fn content_disposition(file_name: &str) -> String {
+    let file_name = file_name.replace(['"', '\\'], "_");
+    format!("attachment; filename=\"{file_name}\"")
+}
@@ -0,0 +1,52 @@
+use crate::{
+    AppResult,
+    config::AppSettings,
+    database::{Database, api_key::ApiScope},
+    service::api_key::generate_api_key,
+};
+use anyhow::anyhow;
+use chrono::{Duration, Utc};
+use tracing::info;
+
+/// Creates a key and prints it, the only time it is shown.
+pub async fn create(
+    settings: &AppSettings,
+    name: &str,
+    scopes: &[ApiScope],
+    expires_in_days: Option<u32>,
+) -> AppResult<()> {
+    let database = Database::connect(&settings.database).await?;
+    let generated = generate_api_key()?;
+    let expires_at = expires_in_days.map(|days| Utc::now() + Duration::days(i64::from(days)));
+    database
+        .create_api_key(name, &generated.prefix, &generated.hash, scopes, expires_at)
+        .await?
+        .ok_or_else(|| anyhow!("API key {name} already exists"))?;
+    info!(
+        "Created API key {name} ({}) with scopes {}",
+        generated.prefix,
+        scopes
+            .iter()
+            .map(|scope| scope.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    println!("{}", generated.key);
+    Ok(())
+}
+
+pub async fn list(settings: &AppSettings) -> AppResult<()> {
+    let database = Database::connect(&settings.database).await?;
+    let keys = database.query_api_keys().await?;
+    println!("{}", serde_json::to_string_pretty(&keys)?);
+    Ok(())
+}
+
+pub async fn revoke(settings: &AppSettings, name: &str) -> AppResult<()> {
+    let database = Database::connect(&settings.database).await?;
+    if !database.revoke_api_key(name).await? {
+        return Err(anyhow!("No active API key named {name}").into());
+    }
+    info!("Revoked API key {name}");
+    Ok(())
+}
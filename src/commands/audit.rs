@@ -0,0 +1,48 @@
+use crate::{
+    AppResult,
+    config::AppSettings,
+    database::Database,
+    external::storage::Storage,
+    service::storage_audit::{RehashMode, StorageAuditService},
+};
+use std::{fs, path::Path};
+use tracing::{info, warn};
+
+/// Runs the audit and writes the JSON report to `output` or stdout. Returns
+/// whether the database and the bucket are consistent.
+pub async fn execute(
+    settings: &AppSettings,
+    rehash: RehashMode,
+    concurrent: usize,
+    output: Option<&Path>,
+) -> AppResult<bool> {
+    info!("Starting storage audit...");
+    let service = StorageAuditService {
+        database: Database::connect(&settings.database).await?,
+        storage: Storage::from_settings(settings)?,
+        concurrent,
+    };
+
+    let report = service.audit(rehash).await?;
+    let content = serde_json::to_string_pretty(&report)?;
+    if let Some(path) = output {
+        fs::write(path, content)?;
+        info!("Audit report written to {}", path.display());
+    } else {
+        println!("{content}");
+    }
+
+    let consistent = report.is_consistent();
+    if consistent {
+        info!("Storage audit finished, no inconsistencies found");
+    } else {
+        warn!(
+            "Storage audit found {} missing, {} orphan, {} size mismatched and {} hash mismatched objects",
+            report.missing_objects.len(),
+            report.orphan_objects.len(),
+            report.size_mismatches.len(),
+            report.hash_mismatches.len()
+        );
+    }
+    Ok(consistent)
+}
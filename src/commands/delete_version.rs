@@ -0,0 +1,51 @@
+use crate::{
+    AppResult,
+    config::AppSettings,
+    database::{Database, version::VersionDeletion},
+};
+use anyhow::anyhow;
+use tracing::info;
+
+/// Deletes a version picked either by id or by channel and resource version.
+/// Files stay in storage until the next `gc` run. A version that is still
+/// downloading is left alone.
+pub async fn execute(
+    settings: &AppSettings,
+    id: Option<i32>,
+    channel: Option<&str>,
+    res_version: Option<&str>,
+) -> AppResult<()> {
+    let database = Database::connect(&settings.database).await?;
+    let id = match (id, res_version) {
+        (Some(id), _) => id,
+        (None, Some(res_version)) => {
+            let channel = match channel {
+                Some(channel) => channel.to_string(),
+                None => settings.ak.channels()?.swap_remove(0).name,
+            };
+            database
+                .get_version_by_res(&channel, res_version)
+                .await?
+                .and_then(|version| version.id)
+                .ok_or_else(|| anyhow!("Version [{channel}] {res_version} not found"))?
+        }
+        (None, None) => return Err(anyhow!("Either --id or --res-version is required").into()),
+    };
+
+    let deleted = match database.delete_version(id).await? {
+        VersionDeletion::Deleted(deleted) => deleted,
+        VersionDeletion::NotFound => return Err(anyhow!("Version {id} not found").into()),
+        VersionDeletion::Downloading => {
+            return Err(anyhow!("Version {id} is still downloading, retry once it settles").into());
+        }
+    };
+    info!(
+        "Deleted version {} [{}] {} with {} bundles and {} asset mappings, run `gc` to reclaim storage",
+        deleted.version_id,
+        deleted.channel,
+        deleted.res_version,
+        deleted.bundles,
+        deleted.asset_mappings
+    );
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::{
+    AppResult, config::AppSettings, database::Database, external::storage::Storage,
+    service::garbage_collection::GarbageCollectionService,
+};
+use std::{fs, path::Path};
+use tracing::info;
+
+/// Runs a collection pass and writes the JSON report to `output` or stdout.
+pub async fn execute(
+    settings: &AppSettings,
+    dry_run: bool,
+    concurrent: usize,
+    output: Option<&Path>,
+) -> AppResult<()> {
+    let service = GarbageCollectionService {
+        database: Database::connect(&settings.database).await?,
+        storage: Storage::from_settings(settings)?,
+        replicas: Storage::replicas_from_settings(settings)?,
+        concurrent,
+    };
+
+    let report = service.collect(dry_run).await?;
+    let content = serde_json::to_string_pretty(&report)?;
+    if let Some(path) = output {
+        fs::write(path, content)?;
+    } else {
+        println!("{content}");
+    }
+    if dry_run {
+        info!(
+            "Dry run: {} files ({} bytes) can be reclaimed",
+            report.files, report.bytes
+        );
+    } else {
+        info!(
+            "Reclaimed {} files ({} bytes), skipped {}",
+            report.files,
+            report.bytes,
+            report.skipped.len()
+        );
+    }
+    Ok(())
+}
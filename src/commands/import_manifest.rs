@@ -5,8 +5,10 @@ use crate::{
 
 pub async fn execute(settings: &AppSettings, res_version: &str) -> AppResult<()> {
     let database = Database::connect(&settings.database).await?;
+    let channel = settings.ak.channels()?.swap_remove(0).name;
     let service = AssetMappingImportService {
         database,
+        channel,
         gamedata_root: std::path::PathBuf::from(&settings.torappu.asset_base_path).join("gamedata"),
     };
 
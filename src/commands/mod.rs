@@ -1,9 +1,17 @@
+mod api_key;
+mod audit;
+mod delete_version;
+mod gc;
 mod import_item_demand;
 mod import_manifest;
+mod replicate;
 mod seed;
 mod worker;
 
-use crate::{api, config::AppSettings, runtime};
+use crate::{
+    api, config::AppSettings, database::api_key::ApiScope, runtime,
+    service::storage_audit::RehashMode,
+};
 use anyhow::Result;
 use clap::Parser;
 use std::{
@@ -64,6 +72,86 @@ pub enum Commands {
         #[arg(short, long, default_value = "config.toml")]
         config: String,
     },
+    /// Cross-check stored objects against the `files` table and exit non-zero
+    /// on inconsistencies.
+    Audit {
+        #[arg(short, long, default_value = "config.toml")]
+        config: String,
+        /// Re-hash this many objects to detect corrupted content.
+        #[arg(long, conflicts_with = "rehash_all")]
+        rehash_sample: Option<usize>,
+        /// Re-hash every object.
+        #[arg(long)]
+        rehash_all: bool,
+        #[arg(long, default_value = "5")]
+        concurrent: usize,
+        /// Write the JSON report to this file instead of stdout.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Delete a version with its bundles and asset mappings. Stored files are
+    /// only removed by `gc`.
+    DeleteVersion {
+        #[arg(short, long, default_value = "config.toml")]
+        config: String,
+        #[arg(long, conflicts_with = "res_version")]
+        id: Option<i32>,
+        /// Channel of `--res-version`, defaults to the primary channel.
+        #[arg(long, requires = "res_version")]
+        channel: Option<String>,
+        #[arg(long)]
+        res_version: Option<String>,
+    },
+    /// Delete files and objects no longer referenced by any bundle.
+    Gc {
+        #[arg(short, long, default_value = "config.toml")]
+        config: String,
+        /// Only report what would be reclaimed.
+        #[arg(long)]
+        dry_run: bool,
+        #[arg(long, default_value = "5")]
+        concurrent: usize,
+        /// Write the JSON report to this file instead of stdout.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Copy objects missing from storage replicas, e.g. to backfill a newly
+    /// added replica.
+    Replicate {
+        #[arg(short, long, default_value = "config.toml")]
+        config: String,
+        /// Only backfill this replica.
+        #[arg(long)]
+        replica: Option<String>,
+        #[arg(long, default_value = "5")]
+        concurrent: usize,
+    },
+    /// Create an API key and print it. The key cannot be shown again.
+    CreateApiKey {
+        #[arg(short, long, default_value = "config.toml")]
+        config: String,
+        #[arg(long)]
+        name: String,
+        /// Scope granted to the key, one of `docker:launch`, `admin:read` or
+        /// `admin:write`. Repeat for several scopes.
+        #[arg(long = "scope", required = true)]
+        scopes: Vec<ApiScope>,
+        /// Days until the key expires, never by default.
+        #[arg(long)]
+        expires_in_days: Option<u32>,
+    },
+    /// List API keys as JSON, without their secrets.
+    ListApiKeys {
+        #[arg(short, long, default_value = "config.toml")]
+        config: String,
+    },
+    /// Revoke an API key by name.
+    RevokeApiKey {
+        #[arg(short, long, default_value = "config.toml")]
+        config: String,
+        #[arg(long)]
+        name: String,
+    },
     Version,
 }
 
@@ -73,6 +161,7 @@ fn init(config: &str) -> Result<(Arc<AppSettings>, sentry::ClientInitGuard)> {
     Ok((settings, sentry))
 }
 
+#[allow(clippy::too_many_lines)]
 pub async fn run(cli: Cli) -> Result<()> {
     match cli.command {
         Commands::Server { config } => {
@@ -121,6 +210,87 @@ pub async fn run(cli: Cli) -> Result<()> {
                 .await
                 .map_err(anyhow::Error::from)
         }
+        Commands::Audit {
+            config,
+            rehash_sample,
+            rehash_all,
+            concurrent,
+            output,
+        } => {
+            let (settings, _sentry) = init(&config)?;
+            let rehash = match (rehash_all, rehash_sample) {
+                (true, _) => RehashMode::All,
+                (false, Some(count)) => RehashMode::Sample(count),
+                (false, None) => RehashMode::None,
+            };
+            let consistent =
+                audit::execute(settings.as_ref(), rehash, concurrent, output.as_deref()).await?;
+            if !consistent {
+                anyhow::bail!("storage audit found inconsistencies");
+            }
+            Ok(())
+        }
+        Commands::DeleteVersion {
+            config,
+            id,
+            channel,
+            res_version,
+        } => {
+            let (settings, _sentry) = init(&config)?;
+            delete_version::execute(
+                settings.as_ref(),
+                id,
+                channel.as_deref(),
+                res_version.as_deref(),
+            )
+            .await
+            .map_err(anyhow::Error::from)
+        }
+        Commands::Gc {
+            config,
+            dry_run,
+            concurrent,
+            output,
+        } => {
+            let (settings, _sentry) = init(&config)?;
+            gc::execute(settings.as_ref(), dry_run, concurrent, output.as_deref())
+                .await
+                .map_err(anyhow::Error::from)
+        }
+        Commands::Replicate {
+            config,
+            replica,
+            concurrent,
+        } => {
+            let (settings, _sentry) = init(&config)?;
+            if !replicate::execute(settings.as_ref(), replica.as_deref(), concurrent).await? {
+                anyhow::bail!("some objects could not be replicated");
+            }
+            Ok(())
+        }
+        Commands::CreateApiKey {
+            config,
+            name,
+            scopes,
+            expires_in_days,
+        } => {
+            let (settings, _sentry) = init(&config)?;
+            api_key::create(settings.as_ref(), &name, &scopes, expires_in_days)
+                .await
+                .map_err(anyhow::Error::from)
+        }
+        Commands::ListApiKeys { config } => {
+            let (settings, _sentry) = init(&config)?;
+            api_key::list(settings.as_ref())
+                .await
+                .map_err(anyhow::Error::from)
+        }
+        Commands::RevokeApiKey { config, name } => {
+            let (settings, _sentry) = init(&config)?;
+            api_key::revoke(settings.as_ref(), &name)
+                .await
+                .map_err(anyhow::Error::from)
+        }
         Commands::Version => {
             println!(
                 "{} ({})",
@@ -0,0 +1,58 @@
+use crate::{
+    AppResult, config::AppSettings, database::Database, external::storage::Storage,
+    service::replication::ReplicationService,
+};
+use anyhow::anyhow;
+use tracing::{info, warn};
+
+/// Copies every object missing from the selected replica (or all replicas)
+/// and returns whether all copies succeeded.
+pub async fn execute(
+    settings: &AppSettings,
+    replica: Option<&str>,
+    concurrent: usize,
+) -> AppResult<bool> {
+    let replicas = Storage::replicas_from_settings(settings)?;
+    let targets = match replica {
+        Some(name) => vec![
+            replicas
+                .iter()
+                .find(|replica| replica.name == name)
+                .cloned()
+                .ok_or_else(|| anyhow!("Replica {name} is not configured"))?,
+        ],
+        None if replicas.is_empty() => {
+            return Err(anyhow!("No storage replicas are configured").into());
+        }
+        None => replicas.clone(),
+    };
+
+    let service = ReplicationService {
+        database: Database::connect(&settings.database).await?,
+        primary: Storage::from_settings(settings)?,
+        replicas,
+        concurrent,
+    };
+
+    let mut success = true;
+    for target in &targets {
+        let report = service.backfill(target).await?;
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        if report.failed.is_empty() {
+            info!(
+                "Replica {} checked {} files, copied {}",
+                report.replica, report.checked, report.copied
+            );
+        } else {
+            warn!(
+                "Replica {} checked {} files, copied {}, failed {}",
+                report.replica,
+                report.checked,
+                report.copied,
+                report.failed.len()
+            );
+            success = false;
+        }
+    }
+    Ok(success)
+}
@@ -2,7 +2,8 @@ use crate::{
     AppResult,
     config::AppSettings,
     database::Database,
-    external::{ak_api::AkApi, notification::NotificationClient, s3::S3Storage},
+    external::{ak_api::AkApi, notification::NotificationClient, storage::Storage},
+    runtime,
     service::{
         asset_download::AssetDownloadService, types::RemoteVersion,
         version_check::VersionCheckService,
@@ -23,39 +24,51 @@ pub async fn execute(
         .filter(|line| !line.is_empty())
         .map(|line| {
             let parts = line.split(',').collect::<Vec<&str>>();
-            RemoteVersion {
+            let remote = RemoteVersion {
                 res_version: parts[0].to_string(),
                 client_version: parts[1].to_string(),
-            }
+            };
+            (
+                parts.get(2).map(|channel| channel.trim().to_string()),
+                remote,
+            )
         })
         .collect::<Vec<_>>();
 
     info!("Seeding database from CSV file: {:?}", csv_path);
     let database = Database::connect(&settings.database).await?;
-    let ak_api = AkApi::new(&settings.ak)?;
-    let storage = S3Storage::new(&settings.s3)?;
+    let ak_apis = AkApi::from_config(&settings.ak)?;
+    let storage = Storage::from_settings(settings)?;
     let notification = NotificationClient::new(&settings.mailer)?;
     let version_check = VersionCheckService {
         database: database.clone(),
-        ak_api: ak_api.clone(),
+        ak_apis: ak_apis.clone(),
         notification: notification.clone(),
         docker: None,
         github: None,
     };
     let download = AssetDownloadService {
         database,
-        ak_api,
+        ak_apis,
         notification,
         storage,
+        replicas: settings.storage.replica_names(),
         concurrent,
+        worker_id: runtime::worker_id(),
     };
 
-    for remote in versions {
+    for (channel, remote) in versions {
+        let ak_api = match channel {
+            Some(channel) => version_check.find_channel(&channel)?,
+            None => &version_check.ak_apis[0],
+        };
         info!(
-            "Inserting new version: {}-{}",
-            remote.client_version, remote.res_version
+            "Inserting new version [{}]: {}-{}",
+            ak_api.channel(),
+            remote.client_version,
+            remote.res_version
         );
-        version_check.check_and_save(remote).await?;
+        version_check.check_and_save(ak_api, remote).await?;
     }
 
     loop {
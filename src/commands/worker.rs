@@ -4,30 +4,110 @@ use crate::{
     database::Database,
     external::{
         ak_api::AkApi, docker::DockerClient, github::GithubClient,
-        notification::NotificationClient, s3::S3Storage,
+        notification::NotificationClient, storage::Storage,
     },
     runtime,
     service::{
         asset_download::AssetDownloadService, asset_mapping_import::AssetMappingImportService,
-        item_demand_import::ItemDemandImportService, version_check::VersionCheckService,
+        item_demand_import::ItemDemandImportService, replication::ReplicationService,
+        version_check::VersionCheckService,
     },
     worker::{
-        item_demand_watcher::ItemDemandWatcher, manifest_watcher::ManifestWatcher, sync::SyncWorker,
+        command::CommandWorker,
+        item_demand_watcher::{self, ItemDemandWatcher},
+        leader::LeaderElection,
+        manifest_watcher::{self, ManifestWatcher},
+        replication::ReplicationWorker,
+        status::{self, StatusReporter},
+        sync::{self, SyncWorker},
     },
 };
-use std::{path::PathBuf, time::Duration};
-use tracing::info;
+use std::{path::PathBuf, sync::Arc, time::Duration};
+use tracing::{info, warn};
+
+/// Duties that must run on exactly one worker: the version poll (which sends
+/// the update email and launches Docker/GitHub), the manifest and item demand
+/// watchers, and the admin command queue.
+struct LeaderDuties {
+    database: Database,
+    worker_id: String,
+    sync_worker: Arc<SyncWorker>,
+    import_service: AssetMappingImportService,
+    item_demand_service: ItemDemandImportService,
+    gamedata_root: PathBuf,
+    item_demand_path: PathBuf,
+}
+
+impl LeaderDuties {
+    /// Runs the duties until leadership is lost, then stops them so the new
+    /// leader does not run them twice.
+    async fn run(&self, election: &LeaderElection) -> AppResult<()> {
+        loop {
+            let mut session = election.acquire().await;
+
+            let manifest_watcher = ManifestWatcher::new(
+                self.import_service.clone(),
+                &self.gamedata_root,
+                self.status_reporter(
+                    status::MANIFEST_WATCHER,
+                    manifest_watcher::HEARTBEAT_INTERVAL,
+                ),
+            )
+            .map_err(crate::AppError::Application)?;
+            let item_demand_watcher = ItemDemandWatcher::new(
+                self.item_demand_service.clone(),
+                &self.item_demand_path,
+                self.status_reporter(
+                    status::ITEM_DEMAND_WATCHER,
+                    item_demand_watcher::HEARTBEAT_INTERVAL,
+                ),
+            )
+            .map_err(crate::AppError::Application)?;
+            let command_worker = CommandWorker::new(
+                self.database.clone(),
+                self.worker_id.clone(),
+                self.sync_worker.clone(),
+                self.import_service.clone(),
+                self.item_demand_service.clone(),
+            );
+
+            tokio::select! {
+                () = self.sync_worker.run() => {
+                    info!("Worker loop exited.");
+                    return Ok(());
+                }
+                () = election.wait_until_lost(&mut session) => {}
+            }
+
+            drop(command_worker);
+            drop(item_demand_watcher);
+            drop(manifest_watcher);
+        }
+    }
+
+    fn status_reporter(&self, component: &'static str, interval: Duration) -> StatusReporter {
+        StatusReporter::new(
+            self.database.clone(),
+            self.worker_id.clone(),
+            component,
+            interval,
+        )
+    }
+}
 
 pub async fn execute(
     settings: &AppSettings,
     concurrent: usize,
     poll_interval_seconds: u64,
 ) -> AppResult<()> {
-    info!("Starting worker...");
+    let worker_id = runtime::worker_id();
+    info!("Starting worker {worker_id}...");
     let database = Database::connect(&settings.database).await?;
-    let ak_api = AkApi::new(&settings.ak)?;
+    let ak_apis = AkApi::from_config(&settings.ak)?;
+    let primary_channel = ak_apis[0].channel().to_string();
     let notification = NotificationClient::new(&settings.mailer)?;
-    let s3 = S3Storage::new(&settings.s3)?;
+    let storage = Storage::from_settings(settings)?;
+    let replicas = Storage::replicas_from_settings(settings)?;
 
     let docker = if let Some(docker_config) = &settings.torappu.docker {
         info!("Docker configuration found, creating Docker client");
@@ -45,55 +125,79 @@ pub async fn execute(
         None
     };
 
-    let sync_worker = SyncWorker::new(
+    let sync_worker = Arc::new(SyncWorker::new(
         VersionCheckService {
             database: database.clone(),
-            ak_api: ak_api.clone(),
+            ak_apis: ak_apis.clone(),
             notification: notification.clone(),
             docker,
             github,
         },
         AssetDownloadService {
             database: database.clone(),
-            ak_api,
+            ak_apis,
             notification,
-            storage: s3,
+            storage: storage.clone(),
+            replicas: settings.storage.replica_names(),
             concurrent,
+            worker_id: worker_id.clone(),
         },
         Duration::from_secs(poll_interval_seconds),
-    );
+        StatusReporter::new(
+            database.clone(),
+            worker_id.clone(),
+            status::VERSION_CHECK,
+            sync::HEARTBEAT_INTERVAL,
+        ),
+    ));
 
     let gamedata_root = PathBuf::from(&settings.torappu.asset_base_path).join("gamedata");
-    let import_service = AssetMappingImportService {
-        database: database.clone(),
-        gamedata_root: gamedata_root.clone(),
+    let replication_worker = if replicas.is_empty() {
+        None
+    } else {
+        Some(ReplicationWorker::new(ReplicationService {
+            database: database.clone(),
+            primary: storage,
+            replicas,
+            concurrent,
+        }))
     };
-    let manifest_watcher = ManifestWatcher::new(import_service, &gamedata_root)
-        .map_err(crate::AppError::Application)?;
 
     let item_demand_path = PathBuf::from(&settings.torappu.asset_base_path)
         .join("raw")
         .join("itemDemand.json");
-    let item_demand_service = ItemDemandImportService {
-        database,
-        file_path: item_demand_path.clone(),
+    let duties = LeaderDuties {
+        database: database.clone(),
+        worker_id: worker_id.clone(),
+        sync_worker: sync_worker.clone(),
+        import_service: AssetMappingImportService {
+            database: database.clone(),
+            channel: primary_channel,
+            gamedata_root: gamedata_root.clone(),
+        },
+        item_demand_service: ItemDemandImportService {
+            database: database.clone(),
+            file_path: item_demand_path.clone(),
+        },
+        gamedata_root,
+        item_demand_path,
     };
-    let item_demand_watcher = ItemDemandWatcher::new(item_demand_service, &item_demand_path)
-        .map_err(crate::AppError::Application)?;
+    let election = LeaderElection::new(database.clone(), worker_id.clone());
 
     info!("Worker is running. Press Ctrl+C to stop.");
-    tokio::select! {
-        () = sync_worker.run() => {
-            info!("Worker loop exited.");
-        }
+    let result = tokio::select! {
+        result = duties.run(&election) => result,
         () = runtime::shutdown_signal() => {
             info!("Shutdown signal received, stopping worker...");
-            sync_worker.stop();
+            Ok(())
         }
-    }
+    };
 
-    drop(item_demand_watcher);
-    drop(manifest_watcher);
+    sync_worker.stop().await;
+    drop(replication_worker);
+    if let Err(err) = database.mark_worker_stopped(&worker_id).await {
+        warn!("failed to mark worker status stopped: {err:?}");
+    }
     info!("Worker has stopped.");
-    Ok(())
+    result
 }
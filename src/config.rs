@@ -30,12 +30,70 @@ impl ServerConfig {
     }
 }
 
+pub const DEFAULT_CHANNEL: &str = "official-android";
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
-pub struct AkApiConfig {
+pub struct AkChannelConfig {
+    pub name: String,
+    pub server: String,
+    pub platform: String,
     pub conf_url: String,
     pub asset_url: String,
 }
 
+/// `[ak]` lists the tracked channels under `[[ak.channels]]`. The legacy
+/// top-level `conf_url`/`asset_url` pair is still accepted and becomes the
+/// default channel. The first channel is the primary one.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct AkApiConfig {
+    pub conf_url: Option<String>,
+    pub asset_url: Option<String>,
+    #[serde(default)]
+    pub channels: Vec<AkChannelConfig>,
+}
+
+impl AkApiConfig {
+    pub fn channels(&self) -> AppResult<Vec<AkChannelConfig>> {
+        let legacy = match (&self.conf_url, &self.asset_url) {
+            (Some(conf_url), Some(asset_url)) => Some(AkChannelConfig {
+                name: DEFAULT_CHANNEL.to_string(),
+                server: "official".to_string(),
+                platform: "Android".to_string(),
+                conf_url: conf_url.clone(),
+                asset_url: asset_url.clone(),
+            }),
+            (None, None) => None,
+            _ => {
+                return Err(AppError::Application(anyhow::anyhow!(
+                    "ak.conf_url and ak.asset_url must be set together"
+                )));
+            }
+        };
+
+        let channels = legacy
+            .into_iter()
+            .chain(self.channels.iter().cloned())
+            .collect::<Vec<_>>();
+        if channels.is_empty() {
+            return Err(AppError::Application(anyhow::anyhow!(
+                "at least one ak channel must be configured"
+            )));
+        }
+        for (index, channel) in channels.iter().enumerate() {
+            if channels[..index]
+                .iter()
+                .any(|other| other.name == channel.name)
+            {
+                return Err(AppError::Application(anyhow::anyhow!(
+                    "duplicate ak channel: {}",
+                    channel.name
+                )));
+            }
+        }
+        Ok(channels)
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct S3Config {
     pub endpoint: String,
@@ -45,6 +103,57 @@ pub struct S3Config {
     pub with_virtual_hosted_style_request: bool,
 }
 
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageBackend {
+    #[default]
+    S3,
+    Local,
+    Memory,
+}
+
+impl std::fmt::Display for StorageBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        to_variant_name(self).expect("only enum supported").fmt(f)
+    }
+}
+
+/// `[storage]` selects where objects are kept.
+///
+/// Without it the `[s3]` bucket is used; `local` stores objects under `path`
+/// and `memory` keeps them only for the lifetime of the process, so it only
+/// works when the server and worker run in the same process.
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
+pub struct StorageConfig {
+    #[serde(default)]
+    pub backend: StorageBackend,
+    pub path: Option<String>,
+    #[serde(default)]
+    pub replicas: Vec<ReplicaConfig>,
+}
+
+impl StorageConfig {
+    #[must_use]
+    pub fn replica_names(&self) -> Vec<String> {
+        self.replicas
+            .iter()
+            .map(|replica| replica.name.clone())
+            .collect()
+    }
+}
+
+/// A `[[storage.replicas]]` entry. Replicas receive a copy of every stored
+/// object through the replication queue; an `s3` replica carries its own
+/// bucket settings.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ReplicaConfig {
+    pub name: String,
+    #[serde(default)]
+    pub backend: StorageBackend,
+    pub path: Option<String>,
+    pub s3: Option<S3Config>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SmtpConfig {
     pub host: String,
@@ -103,7 +212,9 @@ pub struct SentryConfig {
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TorappuConfig {
-    pub token: String,
+    /// Shared secret accepted in place of a `docker:launch` key, kept for
+    /// clients that have not moved to API keys yet.
+    pub token: Option<String>,
     pub asset_base_path: String,
     pub docker: Option<DockerConfig>,
     pub github: Option<GithubConfig>,
@@ -137,7 +248,9 @@ pub struct AppSettings {
     pub database: DatabaseConfig,
     pub mailer: Option<SmtpConfig>,
     pub ak: AkApiConfig,
-    pub s3: S3Config,
+    pub s3: Option<S3Config>,
+    #[serde(default)]
+    pub storage: StorageConfig,
     pub sentry: SentryConfig,
     pub torappu: TorappuConfig,
 }
@@ -0,0 +1,173 @@
+use crate::{
+    AppError, AppResult,
+    database::{
+        Database,
+        model::AdminCommand,
+        row::{AdminCommandKind, AdminCommandRow},
+    },
+};
+use sqlx::{query, query_as};
+
+impl Database {
+    pub async fn enqueue_admin_command(
+        &self,
+        kind: AdminCommandKind,
+        version_id: Option<i32>,
+        res_version: Option<&str>,
+    ) -> AppResult<AdminCommand> {
+        query_as!(
+            AdminCommand,
+            r#"
+INSERT INTO admin_commands (kind, version_id, res_version)
+VALUES ($1, $2, $3)
+RETURNING id, kind::text AS "kind!", version_id, res_version, status::text AS "status!",
+          error, created_at, started_at, finished_at
+            "#,
+            kind as AdminCommandKind,
+            version_id,
+            res_version
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    pub async fn query_admin_command(&self, id: i64) -> AppResult<Option<AdminCommand>> {
+        query_as!(
+            AdminCommand,
+            r#"
+SELECT id, kind::text AS "kind!", version_id, res_version, status::text AS "status!",
+       error, created_at, started_at, finished_at
+FROM admin_commands
+WHERE id = $1
+            "#,
+            id
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    /// Lists the most recently queued commands, newest first.
+    pub async fn query_admin_commands(&self, limit: i64) -> AppResult<Vec<AdminCommand>> {
+        query_as!(
+            AdminCommand,
+            r#"
+SELECT id, kind::text AS "kind!", version_id, res_version, status::text AS "status!",
+       error, created_at, started_at, finished_at
+FROM admin_commands
+ORDER BY id DESC
+LIMIT $1
+            "#,
+            limit
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    /// Marks the oldest pending command as running under `worker` for
+    /// `lease_seconds` and returns it.
+    pub async fn claim_admin_command(
+        &self,
+        worker: &str,
+        lease_seconds: f64,
+    ) -> AppResult<Option<AdminCommandRow>> {
+        query_as!(
+            AdminCommandRow,
+            r#"
+UPDATE admin_commands
+SET status = 'running',
+    started_at = NOW(),
+    worker = $1,
+    lease_expires_at = NOW() + make_interval(secs => $2)
+WHERE id = (
+    SELECT id FROM admin_commands
+    WHERE status = 'pending'
+    ORDER BY id
+    LIMIT 1
+    FOR UPDATE SKIP LOCKED
+)
+RETURNING id, kind AS "kind: AdminCommandKind", version_id, res_version
+            "#,
+            worker,
+            lease_seconds
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    /// Extends the lease `worker` holds on a running command.
+    pub async fn heartbeat_admin_command(
+        &self,
+        id: i64,
+        worker: &str,
+        lease_seconds: f64,
+    ) -> AppResult<()> {
+        query!(
+            r#"
+UPDATE admin_commands
+SET lease_expires_at = NOW() + make_interval(secs => $3)
+WHERE id = $1 AND worker = $2 AND status = 'running'
+            "#,
+            id,
+            worker,
+            lease_seconds
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(())
+    }
+
+    /// Records the outcome of a claimed command, failed when `error` is set.
+    /// Returns `false` when `worker` no longer holds the command because its
+    /// lease ran out and it was failed in the meantime.
+    pub async fn finish_admin_command(
+        &self,
+        id: i64,
+        worker: &str,
+        error: Option<&str>,
+    ) -> AppResult<bool> {
+        let result = query!(
+            r#"
+UPDATE admin_commands
+SET status = CASE WHEN $3::text IS NULL THEN 'succeeded' ELSE 'failed' END::admin_command_status,
+    error = $3,
+    lease_expires_at = NULL,
+    finished_at = NOW()
+WHERE id = $1 AND worker = $2 AND status = 'running'
+            "#,
+            id,
+            worker,
+            error
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Fails running commands whose worker stopped mid-command: those whose
+    /// lease ran out, and those still held under the name of `worker`, which
+    /// only runs one command at a time and is not running one now.
+    pub async fn fail_orphaned_admin_commands(&self, worker: &str) -> AppResult<u64> {
+        let result = query!(
+            r"
+UPDATE admin_commands
+SET status = 'failed',
+    error = 'worker stopped before finishing the command',
+    lease_expires_at = NULL,
+    finished_at = NOW()
+WHERE status = 'running'
+  AND (worker IS NULL OR worker = $1 OR lease_expires_at < NOW())
+            ",
+            worker
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{
+    AppError, AppResult,
+    database::{Database, model::ApiKeySummary, row::ApiKeyRow},
+};
+use chrono::{DateTime, Utc};
+use sqlx::{query, query_as, query_scalar};
+use std::{fmt, str::FromStr};
+
+/// Permission granted to an API key and required by authenticated routes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiScope {
+    DockerLaunch,
+    AdminRead,
+    AdminWrite,
+}
+
+impl ApiScope {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::DockerLaunch => "docker:launch",
+            Self::AdminRead => "admin:read",
+            Self::AdminWrite => "admin:write",
+        }
+    }
+
+    /// Whether a key holding this scope may use a route requiring `required`.
+    /// `admin:write` implies `admin:read`.
+    #[must_use]
+    pub fn grants(self, required: Self) -> bool {
+        self == required || (self == Self::AdminWrite && required == Self::AdminRead)
+    }
+}
+
+impl fmt::Display for ApiScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ApiScope {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        [Self::DockerLaunch, Self::AdminRead, Self::AdminWrite]
+            .into_iter()
+            .find(|scope| scope.as_str() == value)
+            .ok_or_else(|| format!("unknown scope {value}"))
+    }
+}
+
+impl Database {
+    /// Stores a new key, returning `None` when the name is already taken.
+    pub async fn create_api_key(
+        &self,
+        name: &str,
+        prefix: &str,
+        key_hash: &str,
+        scopes: &[ApiScope],
+        expires_at: Option<DateTime<Utc>>,
+    ) -> AppResult<Option<i32>> {
+        let scopes = scopes
+            .iter()
+            .map(|scope| scope.as_str().to_string())
+            .collect::<Vec<_>>();
+        query_scalar!(
+            r#"
+INSERT INTO api_keys (name, prefix, key_hash, scopes, expires_at)
+VALUES ($1, $2, $3, $4, $5)
+ON CONFLICT (name) DO NOTHING
+RETURNING id
+            "#,
+            name,
+            prefix,
+            key_hash,
+            &scopes,
+            expires_at
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    pub async fn find_api_key(&self, prefix: &str) -> AppResult<Option<ApiKeyRow>> {
+        query_as!(
+            ApiKeyRow,
+            r#"
+SELECT id, name, key_hash, scopes AS "scopes: Vec<String>", expires_at, revoked_at
+FROM api_keys
+WHERE prefix = $1
+            "#,
+            prefix
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    /// Records a use of the key, at most once a minute to spare writes.
+    pub async fn touch_api_key(&self, id: i32) -> AppResult<()> {
+        query!(
+            r"
+UPDATE api_keys
+SET last_used_at = NOW()
+WHERE id = $1 AND (last_used_at IS NULL OR last_used_at < NOW() - INTERVAL '1 minute')
+            ",
+            id
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(())
+    }
+
+    pub async fn query_api_keys(&self) -> AppResult<Vec<ApiKeySummary>> {
+        query_as!(
+            ApiKeySummary,
+            r#"
+SELECT id, name, prefix, scopes AS "scopes: Vec<String>", created_at, expires_at,
+       last_used_at, revoked_at
+FROM api_keys
+ORDER BY id
+            "#
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    /// Revokes the named key, returning whether an active key was found.
+    pub async fn revoke_api_key(&self, name: &str) -> AppResult<bool> {
+        let result = query!(
+            "UPDATE api_keys SET revoked_at = NOW() WHERE name = $1 AND revoked_at IS NULL",
+            name
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(result.rows_affected() > 0)
+    }
+}
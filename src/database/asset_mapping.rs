@@ -2,17 +2,84 @@ use crate::{
     AppError, AppResult,
     database::{
         Database,
-        model::{AssetMappingDetails, ManifestNode},
-        row::{AssetMappingRow, AssetMappingStatus, NodeType},
+        model::{
+            AssetMappingDetails, BundleClosure, ClosureBundle, HistoryChange, ManifestDiff,
+            ManifestDiffEntry, ManifestNode,
+        },
+        row::{AssetMappingRow, AssetMappingStatus, BundleDependencyRow, NodeType},
     },
 };
 use sqlx::{Acquire, Postgres, pool::PoolConnection, query_as};
 
+/// One changed asset of the requested page next to the counts over all
+/// changes; the asset columns are empty when the page is.
+struct ManifestDiffRow {
+    total: i64,
+    added: i64,
+    removed: i64,
+    moved: i64,
+    modified: i64,
+    asset_name: Option<String>,
+    asset_path: Option<String>,
+    from_bundle_path: Option<String>,
+    to_bundle_path: Option<String>,
+    from_hash: Option<String>,
+    to_hash: Option<String>,
+}
+
+/// Splits the rows of the diff query into the counts and the page entries.
+fn build_manifest_diff(from_id: i32, to_id: i32, rows: Vec<ManifestDiffRow>) -> ManifestDiff {
+    let count = |value: i64| usize::try_from(value).unwrap_or_default();
+    let (total, added, removed, moved, modified) = rows
+        .first()
+        .map(|row| {
+            (
+                count(row.total),
+                count(row.added),
+                count(row.removed),
+                count(row.moved),
+                count(row.modified),
+            )
+        })
+        .unwrap_or_default();
+    let entries = rows
+        .into_iter()
+        .filter_map(|row| {
+            let change = match (&row.from_bundle_path, &row.to_bundle_path) {
+                (None, _) => HistoryChange::Added,
+                (_, None) => HistoryChange::Removed,
+                (Some(from), Some(to)) if from != to => HistoryChange::Moved,
+                _ => HistoryChange::Modified,
+            };
+            Some(ManifestDiffEntry {
+                asset_name: row.asset_name?,
+                asset_path: row.asset_path,
+                change,
+                from_bundle_path: row.from_bundle_path,
+                to_bundle_path: row.to_bundle_path,
+                from_hash: row.from_hash,
+                to_hash: row.to_hash,
+            })
+        })
+        .collect();
+    ManifestDiff {
+        from_version_id: from_id,
+        to_version_id: to_id,
+        total,
+        added,
+        removed,
+        moved,
+        modified,
+        entries,
+    }
+}
+
 impl Database {
     async fn import_asset_mappings_with_lock(
         conn: &mut PoolConnection<Postgres>,
         version_id: i32,
         mappings: &[AssetMappingRow],
+        dependencies: &[BundleDependencyRow],
     ) -> AppResult<()> {
         let mut tx = conn
             .begin()
@@ -57,6 +124,30 @@ VALUES
             .map_err(|err| AppError::ExternalService(err.into()))?;
         }
 
+        sqlx::query!(
+            "DELETE FROM bundle_dependencies WHERE version_id = $1",
+            version_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        for dependency in dependencies {
+            sqlx::query!(
+                r#"
+INSERT INTO bundle_dependencies (version_id, bundle_path, dependency_path)
+VALUES ($1, $2, $3)
+ON CONFLICT DO NOTHING
+                "#,
+                version_id,
+                &dependency.bundle_path,
+                &dependency.dependency_path
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+        }
+
         sqlx::query!(
             "UPDATE versions SET asset_mapping_status = $2::asset_mapping_status WHERE id = $1",
             version_id,
@@ -76,6 +167,7 @@ VALUES
         &self,
         version_id: i32,
         mappings: &[AssetMappingRow],
+        dependencies: &[BundleDependencyRow],
     ) -> AppResult<bool> {
         let mut conn = self
             .pool()
@@ -93,7 +185,9 @@ VALUES
             return Ok(false);
         }
 
-        let result = Self::import_asset_mappings_with_lock(&mut conn, version_id, mappings).await;
+        let result =
+            Self::import_asset_mappings_with_lock(&mut conn, version_id, mappings, dependencies)
+                .await;
 
         sqlx::query!("SELECT pg_advisory_unlock($1)", i64::from(version_id))
             .fetch_one(&mut *conn)
@@ -159,29 +253,158 @@ WHERE m.version_id = $1 AND m.asset_name = $2 AND m.node_type IN ('file', 'both'
         .map_err(|err| AppError::ExternalService(err.into()))
     }
 
-    pub async fn search_manifest(
+    /// Resolves the bundles needed to load `asset_name`: the bundle holding it
+    /// followed by all of its transitive dependencies.
+    pub async fn query_bundle_closure(
         &self,
         version_id: i32,
-        query: &str,
-    ) -> AppResult<Vec<ManifestNode>> {
-        let pattern = format!("%{query}%");
-        query_as!(
-            ManifestNode,
+        asset_name: &str,
+    ) -> AppResult<Option<BundleClosure>> {
+        let Some(bundle_path) = sqlx::query_scalar!(
             r#"
-SELECT
-    split_part(asset_name, '/', array_length(string_to_array(asset_name, '/'), 1)) AS "name!",
-    asset_name AS "path!",
-    node_type::text AS "node_type!"
-FROM asset_to_bundle_mappings
-WHERE version_id = $1 AND node_type IN ('file', 'both') AND asset_name ILIKE $2
-ORDER BY asset_name
-LIMIT 200
+SELECT bundle_path FROM asset_to_bundle_mappings
+WHERE version_id = $1 AND asset_name = $2 AND node_type IN ('file', 'both')
+            "#,
+            version_id,
+            asset_name
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?
+        else {
+            return Ok(None);
+        };
+
+        let bundles = query_as!(
+            ClosureBundle,
+            r#"
+WITH RECURSIVE closure(path) AS (
+    SELECT $2::varchar
+    UNION
+    SELECT d.dependency_path
+    FROM closure c
+    JOIN bundle_dependencies d ON d.version_id = $1 AND d.bundle_path = c.path
+)
+SELECT c.path AS "path!", f.hash AS "hash?", f.size AS "size?"
+FROM closure c
+LEFT JOIN bundles b ON b.version = $1 AND b.path = c.path
+LEFT JOIN files f ON f.id = b.file
+ORDER BY c.path <> $2, c.path
             "#,
             version_id,
-            pattern
+            bundle_path
         )
         .fetch_all(self.pool())
         .await
-        .map_err(|err| AppError::ExternalService(err.into()))
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        let total_size = bundles.iter().filter_map(|bundle| bundle.size).sum();
+        Ok(Some(BundleClosure {
+            asset_name: asset_name.to_string(),
+            bundle_path,
+            bundles,
+            total_size,
+        }))
+    }
+
+    /// Compares the asset mappings of two versions. Only assets whose name or
+    /// asset path starts with `prefix` are considered; `offset` and `limit`
+    /// page through the entries ordered by asset name. Returns `None` unless
+    /// the mappings of both versions are imported.
+    pub async fn query_manifest_diff(
+        &self,
+        from_id: i32,
+        to_id: i32,
+        prefix: Option<&str>,
+        offset: usize,
+        limit: usize,
+    ) -> AppResult<Option<ManifestDiff>> {
+        let ready = sqlx::query_scalar!(
+            r#"
+SELECT bool_and(COALESCE(v.asset_mapping_status = 'ready', false)) AS "ready!"
+FROM unnest(ARRAY[$1::int4, $2::int4]) AS ids(id)
+LEFT JOIN versions v ON v.id = ids.id
+            "#,
+            from_id,
+            to_id
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        if !ready {
+            return Ok(None);
+        }
+
+        let rows = query_as!(
+            ManifestDiffRow,
+            r#"
+WITH m AS (
+    SELECT m.version_id, m.asset_name, m.asset_path, m.bundle_path, f.hash
+    FROM asset_to_bundle_mappings m
+    LEFT JOIN bundles b ON m.bundle_path = b.path AND m.version_id = b.version
+    LEFT JOIN files f ON b.file = f.id
+    WHERE m.version_id IN ($1, $2) AND m.node_type IN ('file', 'both')
+        AND ($3::text IS NULL OR starts_with(m.asset_name, $3) OR starts_with(m.asset_path, $3))
+),
+d AS (
+    SELECT
+        COALESCE(n.asset_name, o.asset_name) AS asset_name,
+        COALESCE(n.asset_path, o.asset_path) AS asset_path,
+        o.asset_name IS NULL AS added,
+        n.asset_name IS NULL AS removed,
+        o.bundle_path AS from_bundle_path,
+        n.bundle_path AS to_bundle_path,
+        o.hash AS from_hash,
+        n.hash AS to_hash
+    FROM (SELECT * FROM m WHERE version_id = $1) o
+    FULL OUTER JOIN (SELECT * FROM m WHERE version_id = $2) n
+        ON o.asset_name = n.asset_name
+    WHERE
+        o.asset_name IS NULL
+        OR n.asset_name IS NULL
+        OR o.bundle_path <> n.bundle_path
+        OR o.hash <> n.hash
+),
+counts AS (
+    SELECT
+        COUNT(*) AS total,
+        COUNT(*) FILTER (WHERE added) AS added,
+        COUNT(*) FILTER (WHERE removed) AS removed,
+        COUNT(*) FILTER (WHERE from_bundle_path <> to_bundle_path) AS moved,
+        COUNT(*) FILTER (WHERE from_bundle_path = to_bundle_path) AS modified
+    FROM d
+),
+page AS (
+    SELECT * FROM d
+    ORDER BY asset_name
+    LIMIT $4 OFFSET $5
+)
+SELECT
+    counts.total AS "total!",
+    counts.added AS "added!",
+    counts.removed AS "removed!",
+    counts.moved AS "moved!",
+    counts.modified AS "modified!",
+    page.asset_name AS "asset_name?",
+    page.asset_path AS "asset_path?",
+    page.from_bundle_path AS "from_bundle_path?",
+    page.to_bundle_path AS "to_bundle_path?",
+    page.from_hash AS "from_hash?",
+    page.to_hash AS "to_hash?"
+FROM counts
+LEFT JOIN page ON TRUE
+ORDER BY page.asset_name
+            "#,
+            from_id,
+            to_id,
+            prefix,
+            i64::try_from(limit).unwrap_or(i64::MAX),
+            i64::try_from(offset).unwrap_or(i64::MAX)
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        Ok(Some(build_manifest_diff(from_id, to_id, rows)))
     }
 }
@@ -1,24 +1,259 @@
 use crate::{
     AppError, AppResult,
-    database::{Database, model::BundleDetails, row::BundleRow},
+    database::{
+        Database,
+        model::{BundleDetails, BundleDiffEntry, PackDiffSummary, VersionDiff},
+        pagination::SortCursor,
+        row::{BundleRow, FileRow},
+    },
 };
-use sqlx::{query, query_as};
+use serde::Deserialize;
+use sqlx::{Postgres, QueryBuilder, query, query_as};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum BundleSort {
+    #[default]
+    Id,
+    Path,
+    FileSize,
+    AbSize,
+    TotalSize,
+    Md5,
+    Hash,
+    Pid,
+    Type,
+    Cid,
+}
+
+impl BundleSort {
+    /// Column the page query sorts on ahead of the id tie breaker, absent
+    /// when sorting by id alone.
+    const fn column(self) -> Option<&'static str> {
+        match self {
+            Self::Id => None,
+            Self::Path => Some("b.path"),
+            Self::FileSize => Some("f.size"),
+            Self::AbSize => Some("bi.ab_size"),
+            Self::TotalSize => Some("bi.total_size"),
+            Self::Md5 => Some("bi.md5"),
+            Self::Hash => Some("bi.hash"),
+            Self::Pid => Some("bi.pid"),
+            Self::Type => Some("bi.type"),
+            Self::Cid => Some("bi.cid"),
+        }
+    }
+
+    /// Whether the sort key is numeric rather than textual.
+    #[must_use]
+    pub const fn is_numeric(self) -> bool {
+        matches!(
+            self,
+            Self::Id | Self::FileSize | Self::AbSize | Self::TotalSize | Self::Cid
+        )
+    }
+
+    /// Cursor positioned right after `bundle` in this sort.
+    #[must_use]
+    pub fn cursor(self, bundle: &BundleDetails) -> SortCursor {
+        let (num, text) = match self {
+            Self::Id => (Some(i64::from(bundle.id)), None),
+            Self::FileSize => (Some(bundle.file_size), None),
+            Self::AbSize => (bundle.ab_size, None),
+            Self::TotalSize => (bundle.total_size, None),
+            Self::Cid => (bundle.cid.map(i64::from), None),
+            Self::Path => (None, Some(bundle.path.clone())),
+            Self::Md5 => (None, bundle.md5.clone()),
+            Self::Hash => (None, bundle.upstream_hash.clone()),
+            Self::Pid => (None, bundle.pid.clone()),
+            Self::Type => (None, bundle.bundle_type.clone()),
+        };
+        SortCursor {
+            num,
+            text,
+            id: bundle.id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
 pub struct BundleFilter {
     pub path: Option<String>,
     pub hash: Option<String>,
     pub file: Option<i32>,
     pub version: Option<i32>,
+    pub channel: Option<String>,
+    /// Filters on the upstream hot update list metadata.
+    pub md5: Option<String>,
+    pub upstream_hash: Option<String>,
+    pub pid: Option<String>,
+    pub bundle_type: Option<String>,
+    pub cid: Option<i32>,
+    pub min_total_size: Option<i64>,
+    pub max_total_size: Option<i64>,
+    pub sort: BundleSort,
+    pub descending: bool,
+}
+
+impl BundleFilter {
+    const fn filters_bundle_infos(&self) -> bool {
+        self.md5.is_some()
+            || self.upstream_hash.is_some()
+            || self.pid.is_some()
+            || self.bundle_type.is_some()
+            || self.cid.is_some()
+            || self.min_total_size.is_some()
+            || self.max_total_size.is_some()
+    }
+}
+
+/// Appends the `WHERE` clause of `query`, expecting `bundles b` joined with
+/// `files f`, `versions v` and `bundle_infos bi` as far as the set filters
+/// need them.
+fn push_bundle_filter(builder: &mut QueryBuilder<Postgres>, query: &BundleFilter) {
+    builder.push(" WHERE TRUE");
+    if let Some(path) = &query.path {
+        builder
+            .push(" AND b.path LIKE CONCAT('%', ")
+            .push_bind(path.as_str())
+            .push(", '%')");
+    }
+    if let Some(hash) = &query.hash {
+        builder.push(" AND f.hash = ").push_bind(hash.as_str());
+    }
+    if let Some(file) = query.file {
+        builder.push(" AND b.file = ").push_bind(file);
+    }
+    if let Some(version) = query.version {
+        builder.push(" AND b.version = ").push_bind(version);
+    }
+    if let Some(channel) = &query.channel {
+        builder
+            .push(" AND v.channel = ")
+            .push_bind(channel.as_str());
+    }
+    if let Some(md5) = &query.md5 {
+        builder.push(" AND bi.md5 = ").push_bind(md5.as_str());
+    }
+    if let Some(upstream_hash) = &query.upstream_hash {
+        builder
+            .push(" AND bi.hash = ")
+            .push_bind(upstream_hash.as_str());
+    }
+    if let Some(pid) = &query.pid {
+        builder.push(" AND bi.pid = ").push_bind(pid.as_str());
+    }
+    if let Some(bundle_type) = &query.bundle_type {
+        builder
+            .push(" AND bi.type = ")
+            .push_bind(bundle_type.as_str());
+    }
+    if let Some(cid) = query.cid {
+        builder.push(" AND bi.cid = ").push_bind(cid);
+    }
+    if let Some(min_total_size) = query.min_total_size {
+        builder
+            .push(" AND bi.total_size >= ")
+            .push_bind(min_total_size);
+    }
+    if let Some(max_total_size) = query.max_total_size {
+        builder
+            .push(" AND bi.total_size <= ")
+            .push_bind(max_total_size);
+    }
+}
+
+/// Selects the bundles matching `query` in its sort order, starting right
+/// after `after` when given.
+fn bundle_details_query(
+    query: &BundleFilter,
+    after: Option<&SortCursor>,
+) -> QueryBuilder<Postgres> {
+    let mut builder = QueryBuilder::new(
+        r"
+SELECT
+    b.id,
+    b.path,
+    b.file AS file_id,
+    b.version AS version_id,
+    f.hash AS file_hash,
+    f.size AS file_size,
+    v.channel AS version_channel,
+    v.client AS version_client,
+    v.res AS version_res,
+    v.is_ready AS version_is_ready,
+    bi.ab_size,
+    bi.total_size,
+    bi.md5,
+    bi.hash AS upstream_hash,
+    bi.pid,
+    bi.type AS bundle_type,
+    bi.cid
+FROM
+    bundles b
+INNER JOIN
+    files f ON b.file = f.id
+INNER JOIN
+    versions v ON b.version = v.id
+LEFT JOIN
+    bundle_infos bi ON bi.version_id = b.version AND bi.path = b.path",
+    );
+    push_bundle_filter(&mut builder, query);
+    let column = query.sort.column();
+    let (cmp, order) = if query.descending {
+        ("<", "DESC")
+    } else {
+        (">", "ASC")
+    };
+    match (column, after) {
+        (_, None) => {}
+        (None, Some(cursor)) => {
+            builder
+                .push(format_args!(" AND b.id {cmp} "))
+                .push_bind(cursor.id);
+        }
+        (Some(column), Some(cursor)) if cursor.is_missing() => {
+            builder
+                .push(format_args!(" AND {column} IS NULL AND b.id {cmp} "))
+                .push_bind(cursor.id);
+        }
+        (Some(column), Some(cursor)) => {
+            builder.push(format_args!(" AND (({column}, b.id) {cmp} ("));
+            match &cursor.text {
+                Some(text) => builder.push_bind(text.clone()),
+                None => builder.push_bind(cursor.num),
+            };
+            builder
+                .push(", ")
+                .push_bind(cursor.id)
+                .push(format_args!(") OR {column} IS NULL)"));
+        }
+    }
+    builder.push(" ORDER BY ");
+    if let Some(column) = column {
+        builder.push(format_args!("{column} {order} NULLS LAST, "));
+    }
+    builder.push(format_args!("b.id {order}"));
+    builder
 }
 
 impl Database {
     pub async fn create_bundle(&self, bundle: BundleRow) -> AppResult<i32> {
         let row = query!(
-            "INSERT INTO bundles (path, version, file) VALUES ($1, $2, $3) RETURNING id",
+            r#"
+INSERT INTO bundles (path, version, file, upstream_hash, upstream_md5)
+VALUES ($1, $2, $3, $4, $5)
+ON CONFLICT (path, version, file) DO UPDATE
+SET upstream_hash = EXCLUDED.upstream_hash, upstream_md5 = EXCLUDED.upstream_md5
+RETURNING id
+            "#,
             bundle.path.as_str(),
             bundle.version_id,
-            bundle.file_id
+            bundle.file_id,
+            bundle.upstream_hash,
+            bundle.upstream_md5
         )
         .fetch_one(self.pool())
         .await
@@ -33,7 +268,7 @@ impl Database {
     ) -> AppResult<Option<BundleRow>> {
         query_as!(
             BundleRow,
-            r#"SELECT id, path, version as "version_id", file as "file_id" FROM bundles WHERE version = $1 AND path = $2"#,
+            r#"SELECT id, path, version as "version_id", file as "file_id", upstream_hash, upstream_md5 FROM bundles WHERE version = $1 AND path = $2"#,
             version_id,
             path
         )
@@ -42,6 +277,55 @@ impl Database {
         .map_err(|err| AppError::ExternalService(err.into()))
     }
 
+    /// Finds the file stored for `path` by an earlier version whose hot update
+    /// list advertised the same upstream hash and md5.
+    pub async fn get_reusable_file(
+        &self,
+        version_id: i32,
+        path: &str,
+        upstream_hash: &str,
+        upstream_md5: &str,
+    ) -> AppResult<Option<FileRow>> {
+        sqlx::query_as!(
+            FileRow,
+            r#"
+SELECT f.id, f.hash, f.size
+FROM bundles b
+JOIN files f ON f.id = b.file
+WHERE b.path = $1
+  AND b.upstream_hash = $2
+  AND b.upstream_md5 = $3
+  AND b.version <> $4
+ORDER BY b.version DESC
+LIMIT 1
+            "#,
+            path,
+            upstream_hash,
+            upstream_md5,
+            version_id
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    pub async fn get_latest_bundle_path_by_hash(&self, hash: &str) -> AppResult<Option<String>> {
+        sqlx::query_scalar!(
+            r#"
+SELECT b.path
+FROM bundles b
+INNER JOIN files f ON b.file = f.id
+WHERE f.hash = $1
+ORDER BY b.version DESC
+LIMIT 1
+            "#,
+            hash
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
     pub async fn query_bundle_by_id_with_details(
         &self,
         id: i32,
@@ -56,15 +340,25 @@ SELECT
     b.version as "version_id!",
     f.hash as "file_hash",
     f.size as "file_size",
+    v.channel as "version_channel",
     v.client as "version_client",
     v.res as "version_res",
-    v.is_ready as "version_is_ready!"
+    v.is_ready as "version_is_ready!",
+    bi.ab_size as "ab_size?",
+    bi.total_size as "total_size?",
+    bi.md5 as "md5?",
+    bi.hash as "upstream_hash?",
+    bi.pid as "pid?",
+    bi.type as "bundle_type?",
+    bi.cid as "cid?"
 FROM
     bundles b
 INNER JOIN
     files f ON b.file = f.id
 INNER JOIN
     versions v ON b.version = v.id
+LEFT JOIN
+    bundle_infos bi ON bi.version_id = b.version AND bi.path = b.path
 WHERE
     b.id = $1
             "#,
@@ -79,39 +373,50 @@ WHERE
         &self,
         query: &BundleFilter,
     ) -> AppResult<Vec<BundleDetails>> {
-        query_as!(
-            BundleDetails,
-            r#"
-SELECT
-    b.id as "id!",
-    b.path as "path!",
-    b.file as "file_id!",
-    b.version as "version_id!",
-    f.hash as "file_hash",
-    f.size as "file_size",
-    v.client as "version_client",
-    v.res as "version_res",
-    v.is_ready as "version_is_ready!"
-FROM
-    bundles b
-INNER JOIN
-    files f ON b.file = f.id
-INNER JOIN
-    versions v ON b.version = v.id
-WHERE
-    ($1::varchar IS NULL OR b.path LIKE CONCAT('%', $1, '%'))
-    AND ($2::varchar IS NULL OR f.hash = $2)
-    AND ($3::int IS NULL OR b.file = $3)
-    AND ($4::int IS NULL OR b.version = $4)
-            "#,
-            query.path,
-            query.hash,
-            query.file,
-            query.version
-        )
-        .fetch_all(self.pool())
-        .await
-        .map_err(|err| AppError::ExternalService(err.into()))
+        let mut builder = bundle_details_query(query, None);
+        builder
+            .build_query_as()
+            .fetch_all(self.pool())
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    pub async fn count_bundles(&self, query: &BundleFilter) -> AppResult<i64> {
+        let mut builder = QueryBuilder::new("SELECT COUNT(*) FROM bundles b");
+        if query.hash.is_some() {
+            builder.push(" INNER JOIN files f ON b.file = f.id");
+        }
+        if query.channel.is_some() {
+            builder.push(" INNER JOIN versions v ON b.version = v.id");
+        }
+        if query.filters_bundle_infos() {
+            builder.push(
+                " INNER JOIN bundle_infos bi ON bi.version_id = b.version AND bi.path = b.path",
+            );
+        }
+        push_bundle_filter(&mut builder, query);
+        builder
+            .build_query_scalar()
+            .fetch_one(self.pool())
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    /// Up to `limit` bundles matching `query` that come after `after` in the
+    /// requested sort. Bundles without a value for the sort column come last.
+    pub async fn query_bundle_page(
+        &self,
+        query: &BundleFilter,
+        after: Option<&SortCursor>,
+        limit: i64,
+    ) -> AppResult<Vec<BundleDetails>> {
+        let mut builder = bundle_details_query(query, after);
+        builder.push(" LIMIT ").push_bind(limit);
+        builder
+            .build_query_as()
+            .fetch_all(self.pool())
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))
     }
 
     pub async fn query_bundles_by_version_id(
@@ -128,15 +433,25 @@ WHERE
             b.version as "version_id!",
             f.hash as "file_hash",
             f.size as "file_size",
+            v.channel as "version_channel",
             v.client as "version_client",
             v.res as "version_res",
-            v.is_ready as "version_is_ready!"
+            v.is_ready as "version_is_ready!",
+            bi.ab_size as "ab_size?",
+            bi.total_size as "total_size?",
+            bi.md5 as "md5?",
+            bi.hash as "upstream_hash?",
+            bi.pid as "pid?",
+            bi.type as "bundle_type?",
+            bi.cid as "cid?"
         FROM
             bundles b
         INNER JOIN
             files f ON b.file = f.id
         INNER JOIN
             versions v ON b.version = v.id
+        LEFT JOIN
+            bundle_infos bi ON bi.version_id = b.version AND bi.path = b.path
         WHERE
             b.version = $1
             "#,
@@ -146,4 +461,78 @@ WHERE
         .await
         .map_err(|err| AppError::ExternalService(err.into()))
     }
+
+    pub async fn query_bundle_diff(&self, from_id: i32, to_id: i32) -> AppResult<VersionDiff> {
+        let entries = query_as!(
+            BundleDiffEntry,
+            r#"
+SELECT
+    COALESCE(o.path, n.path) as "path!",
+    CASE WHEN n.path IS NULL THEN o.pid ELSE n.pid END as "pid?",
+    o.file as "old_file_id?",
+    n.file as "new_file_id?",
+    o.size as "old_size?",
+    n.size as "new_size?",
+    COALESCE(n.size, 0)::bigint - COALESCE(o.size, 0)::bigint as "size_delta!"
+FROM
+    (
+        SELECT b.path, b.file, f.hash, f.size, bi.pid
+        FROM bundles b INNER JOIN files f ON b.file = f.id
+        LEFT JOIN bundle_infos bi ON bi.version_id = b.version AND bi.path = b.path
+        WHERE b.version = $1
+    ) o
+FULL OUTER JOIN
+    (
+        SELECT b.path, b.file, f.hash, f.size, bi.pid
+        FROM bundles b INNER JOIN files f ON b.file = f.id
+        LEFT JOIN bundle_infos bi ON bi.version_id = b.version AND bi.path = b.path
+        WHERE b.version = $2
+    ) n ON o.path = n.path
+WHERE
+    o.path IS NULL OR n.path IS NULL OR o.hash <> n.hash
+ORDER BY
+    1
+            "#,
+            from_id,
+            to_id
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        let mut diff = VersionDiff {
+            from_version_id: from_id,
+            to_version_id: to_id,
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+            packs: Vec::new(),
+        };
+        let mut packs = BTreeMap::<Option<String>, PackDiffSummary>::new();
+        for entry in entries {
+            let pack = packs
+                .entry(entry.pid.clone())
+                .or_insert_with(|| PackDiffSummary {
+                    pid: entry.pid.clone(),
+                    ..Default::default()
+                });
+            pack.size_delta += entry.size_delta;
+            match (entry.old_file_id, entry.new_file_id) {
+                (None, _) => {
+                    pack.added += 1;
+                    diff.added.push(entry);
+                }
+                (_, None) => {
+                    pack.removed += 1;
+                    diff.removed.push(entry);
+                }
+                _ => {
+                    pack.changed += 1;
+                    diff.changed.push(entry);
+                }
+            }
+        }
+        diff.packs = packs.into_values().collect();
+        Ok(diff)
+    }
 }
@@ -0,0 +1,88 @@
+use crate::{
+    AppError, AppResult,
+    database::{Database, model::BundleFailure},
+};
+use sqlx::{query, query_as, query_scalar};
+
+impl Database {
+    pub async fn get_bundle_download_attempts(
+        &self,
+        version_id: i32,
+        path: &str,
+    ) -> AppResult<i32> {
+        let attempts = query_scalar!(
+            "SELECT attempts FROM bundle_download_attempts WHERE version_id = $1 AND path = $2",
+            version_id,
+            path
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(attempts.unwrap_or(0))
+    }
+
+    /// Records a failed download of `path` and returns the attempts made so far.
+    pub async fn record_bundle_failure(
+        &self,
+        version_id: i32,
+        path: &str,
+        error: &str,
+    ) -> AppResult<i32> {
+        query_scalar!(
+            r#"
+INSERT INTO bundle_download_attempts (version_id, path, attempts, last_error)
+VALUES ($1, $2, 1, $3)
+ON CONFLICT (version_id, path) DO UPDATE
+SET attempts = bundle_download_attempts.attempts + 1,
+    last_error = EXCLUDED.last_error,
+    last_failed_at = NOW()
+RETURNING attempts
+            "#,
+            version_id,
+            path,
+            error
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    pub async fn clear_bundle_failure(&self, version_id: i32, path: &str) -> AppResult<()> {
+        query!(
+            "DELETE FROM bundle_download_attempts WHERE version_id = $1 AND path = $2",
+            version_id,
+            path
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(())
+    }
+
+    pub async fn clear_bundle_failures(&self, version_id: i32) -> AppResult<()> {
+        query!(
+            "DELETE FROM bundle_download_attempts WHERE version_id = $1",
+            version_id
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(())
+    }
+
+    pub async fn query_bundle_failures(&self, version_id: i32) -> AppResult<Vec<BundleFailure>> {
+        query_as!(
+            BundleFailure,
+            r#"
+SELECT path, attempts, last_error, first_failed_at, last_failed_at
+FROM bundle_download_attempts
+WHERE version_id = $1
+ORDER BY path
+            "#,
+            version_id
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+}
@@ -0,0 +1,282 @@
+use crate::{
+    AppError, AppResult,
+    database::{Database, download_progress::start_download_progress, row::DownloadJob},
+};
+use sqlx::{PgConnection, query, query_as, query_scalar};
+
+/// Queues one job per bundle of the version's hot update list, putting jobs
+/// of an earlier run back to pending, and restarts its progress.
+pub(super) async fn queue_version_jobs(conn: &mut PgConnection, version_id: i32) -> AppResult<()> {
+    let queued = query!(
+        r#"
+INSERT INTO download_jobs (version_id, path)
+SELECT v.id, info->>'name'
+FROM versions v,
+     jsonb_array_elements(COALESCE(v.hot_update_list::jsonb->'abInfos', '[]'::jsonb)) AS info
+WHERE v.id = $1
+ON CONFLICT (version_id, path) DO UPDATE
+SET status = 'pending',
+    worker = NULL,
+    lease_expires_at = NULL,
+    heartbeat_at = NULL,
+    next_attempt_at = NOW(),
+    finished_at = NULL
+        "#,
+        version_id
+    )
+    .execute(&mut *conn)
+    .await
+    .map_err(|err| AppError::ExternalService(err.into()))?
+    .rows_affected();
+
+    let total_bundles = i32::try_from(queued)
+        .map_err(|err| AppError::Application(anyhow::anyhow!("Too many bundles: {err}")))?;
+    start_download_progress(conn, version_id, total_bundles).await
+}
+
+impl Database {
+    /// Picks the oldest pending version of `channels` once their queue has
+    /// drained and queues its bundles, unless an earlier run already did.
+    /// Returns the version so the caller can finish it when all of its jobs
+    /// are already done.
+    pub async fn plan_download_jobs(&self, channels: &[String]) -> AppResult<Option<i32>> {
+        let mut tx = self
+            .pool()
+            .begin()
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        let Some(version_id) = query_scalar!(
+            r#"
+SELECT id FROM versions
+WHERE download_status = 'pending'
+  AND channel = ANY($1)
+  AND NOT EXISTS (
+      SELECT 1
+      FROM download_jobs j
+      JOIN versions jv ON jv.id = j.version_id
+      WHERE j.status IN ('pending', 'running')
+        AND jv.channel = ANY($1)
+  )
+ORDER BY id
+LIMIT 1
+FOR UPDATE SKIP LOCKED
+            "#,
+            channels
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?
+        else {
+            return Ok(None);
+        };
+
+        let planned = query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM download_jobs WHERE version_id = $1) AS "planned!""#,
+            version_id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        if !planned {
+            queue_version_jobs(&mut tx, version_id).await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(Some(version_id))
+    }
+
+    /// Puts every bundle of the version back into the queue and marks the
+    /// version pending again. Returns `false` when the version does not exist.
+    pub async fn requeue_version_download(&self, version_id: i32) -> AppResult<bool> {
+        let mut tx = self
+            .pool()
+            .begin()
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        let found = query!(
+            "UPDATE versions SET download_status = 'pending' WHERE id = $1",
+            version_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?
+        .rows_affected()
+            > 0;
+        if found {
+            queue_version_jobs(&mut tx, version_id).await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(found)
+    }
+
+    /// Claims up to `limit` due jobs of versions in `channels`, including
+    /// running jobs whose lease ran out because their worker stopped
+    /// heartbeating.
+    pub async fn claim_download_jobs(
+        &self,
+        worker: &str,
+        channels: &[String],
+        limit: i64,
+        lease_seconds: f64,
+    ) -> AppResult<Vec<DownloadJob>> {
+        query_as!(
+            DownloadJob,
+            r#"
+UPDATE download_jobs
+SET status = 'running',
+    worker = $1,
+    lease_expires_at = NOW() + make_interval(secs => $4),
+    heartbeat_at = NOW()
+WHERE id IN (
+    SELECT j.id FROM download_jobs j
+    INNER JOIN versions v ON v.id = j.version_id
+    WHERE ((j.status = 'pending' AND j.next_attempt_at <= NOW())
+           OR (j.status = 'running' AND j.lease_expires_at < NOW()))
+      AND v.channel = ANY($2)
+    ORDER BY j.id
+    LIMIT $3
+    FOR UPDATE OF j SKIP LOCKED
+)
+RETURNING id, version_id, path
+            "#,
+            worker,
+            channels,
+            limit,
+            lease_seconds
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    /// Extends the leases `worker` still holds among `ids`.
+    pub async fn heartbeat_download_jobs(
+        &self,
+        ids: &[i64],
+        worker: &str,
+        lease_seconds: f64,
+    ) -> AppResult<()> {
+        query!(
+            r#"
+UPDATE download_jobs
+SET lease_expires_at = NOW() + make_interval(secs => $3),
+    heartbeat_at = NOW()
+WHERE id = ANY($1) AND worker = $2 AND status = 'running'
+            "#,
+            ids,
+            worker,
+            lease_seconds
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(())
+    }
+
+    /// Marks a job done or failed. Returns `false` when `worker` lost the
+    /// lease in the meantime, so the outcome is left to the new holder.
+    pub async fn finish_download_job(
+        &self,
+        id: i64,
+        worker: &str,
+        failed: bool,
+    ) -> AppResult<bool> {
+        let result = query!(
+            r#"
+UPDATE download_jobs
+SET status = CASE WHEN $3 THEN 'failed' ELSE 'done' END::download_job_status,
+    lease_expires_at = NULL,
+    finished_at = NOW()
+WHERE id = $1 AND worker = $2 AND status = 'running'
+            "#,
+            id,
+            worker,
+            failed
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Releases a failed job so any worker retries it after `retry_seconds`.
+    pub async fn retry_download_job(
+        &self,
+        id: i64,
+        worker: &str,
+        retry_seconds: f64,
+    ) -> AppResult<()> {
+        query!(
+            r#"
+UPDATE download_jobs
+SET status = 'pending',
+    worker = NULL,
+    lease_expires_at = NULL,
+    next_attempt_at = NOW() + make_interval(secs => $3)
+WHERE id = $1 AND worker = $2 AND status = 'running'
+            "#,
+            id,
+            worker,
+            retry_seconds
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(())
+    }
+
+    pub async fn has_active_download_jobs(&self) -> AppResult<bool> {
+        query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM download_jobs WHERE status IN ('pending', 'running')) AS "active!""#
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    /// Settles the download status of a pending version once none of its jobs
+    /// is left. Only one caller gets `Some`, with whether every bundle was
+    /// downloaded.
+    pub async fn finish_version_download(&self, version_id: i32) -> AppResult<Option<bool>> {
+        query_scalar!(
+            r#"
+UPDATE versions v
+SET download_status = CASE WHEN f.failed > 0 THEN 'partial' ELSE 'ready' END::download_status,
+    is_ready = v.is_ready OR f.failed = 0
+FROM (
+    SELECT COUNT(*) FILTER (WHERE status = 'failed') AS failed
+    FROM download_jobs
+    WHERE version_id = $1
+) f
+WHERE v.id = $1
+  AND v.download_status = 'pending'
+  AND NOT EXISTS (
+    SELECT 1 FROM download_jobs
+    WHERE version_id = $1 AND status IN ('pending', 'running')
+  )
+RETURNING f.failed = 0 AS "ready!"
+            "#,
+            version_id
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    pub async fn is_version_download_pending(&self, version_id: i32) -> AppResult<bool> {
+        query_scalar!(
+            r#"SELECT EXISTS (SELECT 1 FROM versions WHERE id = $1 AND download_status = 'pending') AS "pending!""#,
+            version_id
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{
+    AppError, AppResult,
+    database::{Database, model::VersionProgress},
+};
+use sqlx::{PgConnection, query, query_as};
+
+/// Resets the progress of `version_id` at the start of a sync run.
+pub(super) async fn start_download_progress(
+    conn: &mut PgConnection,
+    version_id: i32,
+    total_bundles: i32,
+) -> AppResult<()> {
+    query!(
+        r#"
+INSERT INTO version_download_progress (version_id, total_bundles)
+VALUES ($1, $2)
+ON CONFLICT (version_id) DO UPDATE
+SET total_bundles = EXCLUDED.total_bundles,
+    done_bundles = 0,
+    failed_bundles = 0,
+    bytes_fetched = 0,
+    bytes_stored = 0,
+    bytes_deduplicated = 0,
+    started_at = NOW(),
+    updated_at = NOW(),
+    finished_at = NULL
+        "#,
+        version_id,
+        total_bundles
+    )
+    .execute(conn)
+    .await
+    .map_err(|err| AppError::ExternalService(err.into()))?;
+    Ok(())
+}
+
+impl Database {
+    pub async fn record_bundle_progress(
+        &self,
+        version_id: i32,
+        failed: bool,
+        bytes_fetched: i64,
+        bytes_stored: i64,
+        bytes_deduplicated: i64,
+    ) -> AppResult<()> {
+        query!(
+            r#"
+UPDATE version_download_progress
+SET done_bundles = done_bundles + CASE WHEN $2 THEN 0 ELSE 1 END,
+    failed_bundles = failed_bundles + CASE WHEN $2 THEN 1 ELSE 0 END,
+    bytes_fetched = bytes_fetched + $3,
+    bytes_stored = bytes_stored + $4,
+    bytes_deduplicated = bytes_deduplicated + $5,
+    updated_at = NOW()
+WHERE version_id = $1
+            "#,
+            version_id,
+            failed,
+            bytes_fetched,
+            bytes_stored,
+            bytes_deduplicated
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(())
+    }
+
+    pub async fn finish_download_progress(&self, version_id: i32) -> AppResult<()> {
+        query!(
+            "UPDATE version_download_progress SET finished_at = NOW(), updated_at = NOW() WHERE version_id = $1",
+            version_id
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(())
+    }
+
+    pub async fn query_download_progress(
+        &self,
+        version_id: i32,
+    ) -> AppResult<Option<VersionProgress>> {
+        query_as!(
+            VersionProgress,
+            r#"
+SELECT
+    version_id,
+    total_bundles,
+    done_bundles,
+    failed_bundles,
+    bytes_fetched,
+    bytes_stored,
+    bytes_deduplicated,
+    started_at,
+    updated_at,
+    finished_at
+FROM version_download_progress
+WHERE version_id = $1
+            "#,
+            version_id
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+}
@@ -2,9 +2,49 @@ use crate::{
     AppError, AppResult,
     database::{Database, row::FileRow},
 };
-use sqlx::{query, query_as};
+use sqlx::{Postgres, Transaction, query, query_as};
+
+// Two-key advisory lock space for per-hash file locks, next to the leader
+// lock in space 1.
+const FILE_LOCK_SPACE: i32 = 2;
+
+/// Serializes storing and collecting the object of one hash. The lock is
+/// held by a transaction and released when the guard is released or dropped.
+pub struct FileLock {
+    tx: Transaction<'static, Postgres>,
+}
+
+impl FileLock {
+    pub async fn release(self) -> AppResult<()> {
+        self.tx
+            .commit()
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))
+    }
+}
 
 impl Database {
+    /// Waits for the lock on `hash`. Sync holds it from looking up the file
+    /// until a bundle references it, garbage collection from deleting the row
+    /// until the object is gone, and the storage audit while it re-checks a
+    /// hash.
+    pub async fn lock_file(&self, hash: &str) -> AppResult<FileLock> {
+        let mut tx = self
+            .pool()
+            .begin()
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+        query!(
+            "SELECT pg_advisory_xact_lock($1, hashtext($2))",
+            FILE_LOCK_SPACE,
+            hash
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(FileLock { tx })
+    }
+
     pub async fn create_file(&self, file: FileRow) -> AppResult<i32> {
         let row = query!(
             "INSERT INTO files (hash, size) VALUES ($1, $2) RETURNING id",
@@ -27,4 +67,34 @@ impl Database {
         .await
         .map_err(|err| AppError::ExternalService(err.into()))
     }
+
+    pub async fn list_files(&self) -> AppResult<Vec<FileRow>> {
+        query_as!(FileRow, "SELECT id, hash, size FROM files ORDER BY id")
+            .fetch_all(self.pool())
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    pub async fn query_unreferenced_files(&self) -> AppResult<Vec<FileRow>> {
+        query_as!(
+            FileRow,
+            "SELECT id, hash, size FROM files f WHERE NOT EXISTS (SELECT 1 FROM bundles b WHERE b.file = f.id) ORDER BY id"
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    /// Deletes the file row unless a bundle started referencing it in the
+    /// meantime. Returns whether the row was removed.
+    pub async fn delete_unreferenced_file(&self, id: i32) -> AppResult<bool> {
+        let result = query!(
+            "DELETE FROM files f WHERE f.id = $1 AND NOT EXISTS (SELECT 1 FROM bundles b WHERE b.file = f.id)",
+            id
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(result.rows_affected() > 0)
+    }
 }
@@ -0,0 +1,131 @@
+use crate::{
+    AppError, AppResult,
+    database::{
+        Database,
+        model::{HistoryChange, HistoryEntry},
+    },
+};
+use sqlx::query_as;
+
+/// Presence of an asset or bundle in one version, before being folded into
+/// change events.
+struct HistoryRow {
+    version_id: i32,
+    channel: String,
+    res_version: String,
+    client_version: String,
+    bundle_path: Option<String>,
+    file_hash: Option<String>,
+}
+
+impl Database {
+    /// Versions of `channel` with imported asset mappings where `asset_name`
+    /// appeared, moved to another bundle, had its bundle content change or
+    /// disappeared.
+    pub async fn query_asset_history(
+        &self,
+        asset_name: &str,
+        channel: &str,
+    ) -> AppResult<Vec<HistoryEntry>> {
+        let rows = query_as!(
+            HistoryRow,
+            r#"
+SELECT
+    v.id AS version_id,
+    v.channel,
+    v.res AS res_version,
+    v.client AS client_version,
+    m.bundle_path AS "bundle_path?",
+    f.hash AS "file_hash?"
+FROM versions v
+LEFT JOIN asset_to_bundle_mappings m
+    ON m.version_id = v.id AND m.asset_name = $1 AND m.node_type IN ('file', 'both')
+LEFT JOIN bundles b ON b.version = v.id AND b.path = m.bundle_path
+LEFT JOIN files f ON f.id = b.file
+WHERE v.asset_mapping_status = 'ready' AND v.channel = $2
+ORDER BY v.id
+            "#,
+            asset_name,
+            channel
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(fold_history(rows))
+    }
+
+    /// Ready versions of `channel` where the bundle at `path` appeared, changed
+    /// content or disappeared.
+    pub async fn query_bundle_history(
+        &self,
+        path: &str,
+        channel: &str,
+    ) -> AppResult<Vec<HistoryEntry>> {
+        let rows = query_as!(
+            HistoryRow,
+            r#"
+SELECT
+    v.id AS version_id,
+    v.channel,
+    v.res AS res_version,
+    v.client AS client_version,
+    b.path AS "bundle_path?",
+    f.hash AS "file_hash?"
+FROM versions v
+LEFT JOIN bundles b ON b.version = v.id AND b.path = $1
+LEFT JOIN files f ON f.id = b.file
+WHERE v.is_ready AND v.channel = $2
+ORDER BY v.id
+            "#,
+            path,
+            channel
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(fold_history(rows))
+    }
+}
+
+fn fold_history(rows: Vec<HistoryRow>) -> Vec<HistoryEntry> {
+    let mut entries = Vec::new();
+    let mut previous: Option<(String, Option<String>)> = None;
+    for row in rows {
+        let change = match (&previous, &row.bundle_path) {
+            (None, Some(_)) => Some(HistoryChange::Added),
+            (Some(_), None) => Some(HistoryChange::Removed),
+            (Some((path, _)), Some(current)) if path != current => Some(HistoryChange::Moved),
+            // Bundles that were never downloaded have no hash to compare.
+            (Some((_, Some(hash))), Some(_)) => row
+                .file_hash
+                .as_ref()
+                .filter(|current| *current != hash)
+                .map(|_| HistoryChange::Modified),
+            (None, None) | (Some((_, None)), Some(_)) => None,
+        };
+
+        previous = match row.bundle_path.clone() {
+            Some(path) => {
+                let hash = row
+                    .file_hash
+                    .clone()
+                    .or_else(|| previous.and_then(|(_, hash)| hash));
+                Some((path, hash))
+            }
+            None => None,
+        };
+
+        if let Some(change) = change {
+            entries.push(HistoryEntry {
+                version_id: row.version_id,
+                channel: row.channel,
+                res_version: row.res_version,
+                client_version: row.client_version,
+                change,
+                bundle_path: row.bundle_path,
+                file_hash: row.file_hash,
+            });
+        }
+    }
+    entries
+}
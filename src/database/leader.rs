@@ -0,0 +1,114 @@
+use crate::{
+    AppError, AppResult,
+    database::{Database, model::WorkerLeader},
+};
+use sqlx::{Connection, PgConnection, query, query_as, query_scalar};
+use tracing::error;
+
+// Two-key advisory lock, so it cannot collide with the single-key locks
+// `import_asset_mappings` takes per version id.
+const LEADER_LOCK_SPACE: i32 = 1;
+const LEADER_LOCK_ID: i32 = 1;
+
+/// Dedicated session a worker campaigns on and, once it holds the worker
+/// leader lock, leads on. Dropping it closes the session, which releases the
+/// lock for the next worker.
+pub struct LeaderSession {
+    conn: PgConnection,
+}
+
+impl LeaderSession {
+    /// Tries to take the leader lock, returning whether this session holds it.
+    /// The new leader records when it was elected.
+    pub async fn try_acquire(&mut self) -> AppResult<bool> {
+        let locked = query_scalar!(
+            "SELECT pg_try_advisory_lock($1, $2)",
+            LEADER_LOCK_SPACE,
+            LEADER_LOCK_ID
+        )
+        .fetch_one(&mut self.conn)
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?
+        .unwrap_or(false);
+
+        if locked {
+            query!(
+                r#"
+INSERT INTO worker_leader (worker)
+VALUES (current_setting('application_name'))
+ON CONFLICT (id) DO UPDATE SET worker = EXCLUDED.worker, elected_at = NOW()
+                "#
+            )
+            .execute(&mut self.conn)
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+        }
+        Ok(locked)
+    }
+
+    /// Whether the session is alive and still holds the lock. A failed check
+    /// counts as lost, since the session may be gone.
+    pub async fn is_held(&mut self) -> bool {
+        query_scalar!(
+            r#"
+SELECT EXISTS (
+    SELECT 1 FROM pg_locks
+    WHERE pid = pg_backend_pid()
+      AND locktype = 'advisory'
+      AND classid = $1::int4::oid
+      AND objid = $2::int4::oid
+      AND objsubid = 2
+      AND granted
+) AS "held!"
+            "#,
+            LEADER_LOCK_SPACE,
+            LEADER_LOCK_ID
+        )
+        .fetch_one(&mut self.conn)
+        .await
+        .unwrap_or_else(|err| {
+            error!("failed to check the leader lock: {err:?}");
+            false
+        })
+    }
+}
+
+impl Database {
+    /// Opens a session outside the pool named after `worker`, so the API can
+    /// tell who leads and since when.
+    pub async fn open_leader_session(&self, worker: &str) -> AppResult<LeaderSession> {
+        let mut conn = PgConnection::connect_with(&self.pool().connect_options())
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        query_scalar!("SELECT set_config('application_name', $1, false)", worker)
+            .fetch_one(&mut conn)
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(LeaderSession { conn })
+    }
+
+    /// The worker whose session holds the leader lock, if any.
+    pub async fn query_worker_leader(&self) -> AppResult<Option<WorkerLeader>> {
+        query_as!(
+            WorkerLeader,
+            r#"
+SELECT a.application_name AS "worker!", COALESCE(w.elected_at, a.backend_start) AS "elected_at!"
+FROM pg_locks l
+JOIN pg_stat_activity a ON a.pid = l.pid
+LEFT JOIN worker_leader w ON w.worker = a.application_name
+WHERE l.locktype = 'advisory'
+  AND l.database = (SELECT oid FROM pg_database WHERE datname = current_database())
+  AND l.classid = $1::int4::oid
+  AND l.objid = $2::int4::oid
+  AND l.objsubid = 2
+  AND l.granted
+            "#,
+            LEADER_LOCK_SPACE,
+            LEADER_LOCK_ID
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+}
@@ -1,10 +1,23 @@
+pub mod admin_command;
+pub mod api_key;
 pub mod asset_mapping;
 pub mod bundle;
+pub mod download_attempt;
+pub mod download_job;
+pub mod download_progress;
 pub mod file;
+pub mod history;
 pub mod item_demand;
+pub mod leader;
 pub mod model;
+pub mod pack;
+pub mod pagination;
+pub mod replication;
 pub mod row;
+pub mod search;
+pub mod stats;
 pub mod version;
+pub mod worker_status;
 
 use crate::{AppError, AppResult, config::DatabaseConfig};
 use sqlx::{PgPool, postgres::PgPoolOptions};
@@ -1,13 +1,16 @@
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, ToSchema, sqlx::FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct VersionSummary {
     pub id: i32,
+    pub channel: String,
     pub client_version: String,
     pub res_version: String,
     pub is_ready: bool,
+    pub download_status: String,
     pub asset_mapping_status: String,
 }
 
@@ -15,24 +18,36 @@ pub struct VersionSummary {
 #[serde(rename_all = "camelCase")]
 pub struct VersionDetails {
     pub id: i32,
+    pub channel: String,
     pub client_version: String,
     pub res_version: String,
     pub is_ready: bool,
+    pub download_status: String,
     pub hot_update_list: String,
 }
 
-#[derive(Debug, Serialize, ToSchema)]
+#[derive(Debug, Serialize, ToSchema, sqlx::FromRow)]
 #[serde(rename_all = "camelCase")]
 pub struct BundleDetails {
     pub id: i32,
     pub path: String,
     pub file_id: i32,
     pub file_hash: String,
-    pub file_size: i32,
+    pub file_size: i64,
     pub version_id: i32,
+    pub version_channel: String,
     pub version_res: String,
     pub version_client: String,
     pub version_is_ready: bool,
+    /// Upstream metadata from the version's hot update list.
+    pub ab_size: Option<i64>,
+    pub total_size: Option<i64>,
+    pub md5: Option<String>,
+    pub upstream_hash: Option<String>,
+    pub pid: Option<String>,
+    #[serde(rename = "type")]
+    pub bundle_type: Option<String>,
+    pub cid: Option<i32>,
 }
 
 #[derive(Debug, Serialize, ToSchema)]
@@ -43,6 +58,59 @@ pub struct ManifestNode {
     pub node_type: String,
 }
 
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestSearchHit {
+    pub name: String,
+    pub path: String,
+    pub node_type: String,
+    pub short_name: Option<String>,
+    pub asset_path: Option<String>,
+    /// Latest searched version containing the asset.
+    pub version_id: i32,
+    pub res_version: String,
+    /// Trigram similarity to the query, higher is better.
+    pub score: f32,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleSearchHit {
+    pub id: i32,
+    pub path: String,
+    pub file_id: i32,
+    pub file_hash: String,
+    pub file_size: i64,
+    /// Latest searched version containing the bundle.
+    pub version_id: i32,
+    pub version_channel: String,
+    pub version_res: String,
+    pub version_client: String,
+    /// Trigram similarity to the query, higher is better.
+    pub score: f32,
+}
+
+/// A bundle required to load an asset, either its own bundle or one of the
+/// bundles it transitively depends on.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ClosureBundle {
+    pub path: String,
+    /// Hash of the stored file, missing until the bundle is downloaded.
+    pub hash: Option<String>,
+    pub size: Option<i64>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleClosure {
+    pub asset_name: String,
+    pub bundle_path: String,
+    pub bundles: Vec<ClosureBundle>,
+    /// Sum of the sizes of all downloaded bundles in the closure.
+    pub total_size: i64,
+}
+
 #[derive(Debug, Serialize, ToSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct AssetMappingDetails {
@@ -50,6 +118,290 @@ pub struct AssetMappingDetails {
     pub bundle_path: String,
     pub asset_path: Option<String>,
     pub short_name: Option<String>,
-    pub bundle_size: Option<i32>,
+    pub bundle_size: Option<i64>,
     pub bundle_hash: Option<String>,
 }
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleDiffEntry {
+    pub path: String,
+    /// Pack of the bundle in the newer version, or in the older one if removed.
+    pub pid: Option<String>,
+    pub old_file_id: Option<i32>,
+    pub new_file_id: Option<i32>,
+    pub old_size: Option<i64>,
+    pub new_size: Option<i64>,
+    pub size_delta: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionDiff {
+    pub from_version_id: i32,
+    pub to_version_id: i32,
+    pub added: Vec<BundleDiffEntry>,
+    pub removed: Vec<BundleDiffEntry>,
+    pub changed: Vec<BundleDiffEntry>,
+    pub packs: Vec<PackDiffSummary>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleFailure {
+    pub path: String,
+    pub attempts: i32,
+    pub last_error: String,
+    pub first_failed_at: DateTime<Utc>,
+    pub last_failed_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionProgress {
+    pub version_id: i32,
+    pub total_bundles: i32,
+    pub done_bundles: i32,
+    pub failed_bundles: i32,
+    pub bytes_fetched: i64,
+    pub bytes_stored: i64,
+    pub bytes_deduplicated: i64,
+    pub started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DeletedVersion {
+    pub version_id: i32,
+    pub channel: String,
+    pub res_version: String,
+    pub bundles: u64,
+    pub asset_mappings: u64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicaStatus {
+    pub replica: String,
+    pub pending: i64,
+    pub failing: i64,
+    pub oldest_pending_at: Option<DateTime<Utc>>,
+    /// Age of the oldest queued object, in seconds.
+    pub lag_seconds: i64,
+    pub last_error: Option<String>,
+}
+
+/// The worker currently holding the leader lock.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerLeader {
+    /// Worker id, as `host:pid`.
+    pub worker: String,
+    /// When the worker took the lock.
+    pub elected_at: DateTime<Utc>,
+}
+
+/// Heartbeat and last run of one worker component.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkerStatus {
+    /// `version_check`, `manifest_watcher` or `item_demand_watcher`.
+    pub component: String,
+    /// Worker id, as `host:pid`.
+    pub worker: String,
+    pub heartbeat_at: DateTime<Utc>,
+    /// How long the component may go without a heartbeat before it counts as
+    /// dead.
+    pub stale_after_seconds: i32,
+    pub stale: bool,
+    /// Set when the worker shut down cleanly, cleared once a worker runs the
+    /// component again.
+    pub stopped_at: Option<DateTime<Utc>>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    /// Summary of the last run, successful or not.
+    pub last_outcome: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+/// A worker operation queued through the admin API.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AdminCommand {
+    pub id: i64,
+    /// One of `check_version`, `download_version`, `import_asset_mapping`,
+    /// `import_item_demand` or `reset_version`.
+    pub kind: String,
+    pub version_id: Option<i32>,
+    pub res_version: Option<String>,
+    /// One of `pending`, `running`, `succeeded` or `failed`.
+    pub status: String,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// An API key as listed by `list-api-keys`, without its secret.
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiKeySummary {
+    pub id: i32,
+    pub name: String,
+    /// Leading characters of the key, enough to recognize it.
+    pub prefix: String,
+    pub scopes: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BundleSize {
+    pub path: String,
+    pub file_hash: String,
+    pub size: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionStats {
+    pub version_id: i32,
+    pub bundle_count: i64,
+    /// Sum of the sizes of all bundles in the version.
+    pub total_size: i64,
+    /// Bundles whose file was not stored by any earlier version.
+    pub new_bundle_count: i64,
+    /// Size of the distinct files first stored by this version.
+    pub new_bytes: i64,
+    pub reused_bytes: i64,
+    pub largest_new_bundles: Vec<BundleSize>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct VersionGrowth {
+    pub version_id: i32,
+    pub channel: String,
+    pub res_version: String,
+    pub total_size: i64,
+    pub new_bytes: i64,
+    /// Unique bytes stored once this version and all earlier ones are included.
+    pub cumulative_unique_bytes: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageStats {
+    pub version_count: i64,
+    pub bundle_count: i64,
+    pub file_count: i64,
+    /// Bytes actually stored, each file counted once.
+    pub unique_bytes: i64,
+    /// Bytes referenced by all bundles of all versions.
+    pub logical_bytes: i64,
+    /// `logical_bytes / unique_bytes`, 1.0 when nothing is stored yet.
+    pub dedupe_ratio: f64,
+    pub growth: Vec<VersionGrowth>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PackSummary {
+    pub pid: String,
+    /// Download size advertised by the hot update list.
+    pub total_size: i64,
+    pub ab_size: Option<i64>,
+    pub md5: Option<String>,
+    pub hash: Option<String>,
+    pub cid: Option<i32>,
+    pub bundle_count: i64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PackBundle {
+    pub path: String,
+    pub ab_size: i64,
+    pub total_size: i64,
+    pub md5: String,
+    pub hash: String,
+    /// Stored file, missing until the bundle has been downloaded.
+    pub file_id: Option<i32>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PackDetails {
+    #[serde(flatten)]
+    pub pack: PackSummary,
+    pub bundles: Vec<PackBundle>,
+}
+
+/// Changes of one pack between two versions. Bundles outside any pack are
+/// grouped under a `null` pid.
+#[derive(Debug, Default, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct PackDiffSummary {
+    pub pid: Option<String>,
+    pub added: usize,
+    pub removed: usize,
+    pub changed: usize,
+    pub size_delta: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum HistoryChange {
+    Added,
+    /// The asset is now packed into a different bundle.
+    Moved,
+    /// The bundle content hash changed.
+    Modified,
+    Removed,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEntry {
+    pub version_id: i32,
+    pub channel: String,
+    pub res_version: String,
+    pub client_version: String,
+    pub change: HistoryChange,
+    /// Bundle holding the asset from this version on, absent once removed.
+    pub bundle_path: Option<String>,
+    pub file_hash: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestDiffEntry {
+    pub asset_name: String,
+    pub asset_path: Option<String>,
+    pub change: HistoryChange,
+    pub from_bundle_path: Option<String>,
+    pub to_bundle_path: Option<String>,
+    pub from_hash: Option<String>,
+    pub to_hash: Option<String>,
+}
+
+/// Asset-level diff between two versions. The counts cover every matching
+/// asset while `entries` holds the requested page.
+#[derive(Debug, Default, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestDiff {
+    pub from_version_id: i32,
+    pub to_version_id: i32,
+    pub total: usize,
+    pub added: usize,
+    pub removed: usize,
+    pub moved: usize,
+    pub modified: usize,
+    pub entries: Vec<ManifestDiffEntry>,
+}
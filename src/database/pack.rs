@@ -0,0 +1,87 @@
+use crate::{
+    AppError, AppResult,
+    database::{
+        Database,
+        model::{PackBundle, PackDetails, PackSummary},
+    },
+};
+use sqlx::query_as;
+
+impl Database {
+    pub async fn query_version_packs(&self, version_id: i32) -> AppResult<Vec<PackSummary>> {
+        query_as!(
+            PackSummary,
+            r#"
+SELECT
+    p.pid,
+    p.total_size,
+    p.ab_size,
+    p.md5,
+    p.hash,
+    p.cid,
+    (SELECT COUNT(*) FROM bundle_infos bi WHERE bi.version_id = p.version_id AND bi.pid = p.pid) AS "bundle_count!"
+FROM version_packs p
+WHERE p.version_id = $1
+ORDER BY p.pid
+            "#,
+            version_id
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    pub async fn query_version_pack(
+        &self,
+        version_id: i32,
+        pid: &str,
+    ) -> AppResult<Option<PackDetails>> {
+        let Some(pack) = query_as!(
+            PackSummary,
+            r#"
+SELECT
+    p.pid,
+    p.total_size,
+    p.ab_size,
+    p.md5,
+    p.hash,
+    p.cid,
+    (SELECT COUNT(*) FROM bundle_infos bi WHERE bi.version_id = p.version_id AND bi.pid = p.pid) AS "bundle_count!"
+FROM version_packs p
+WHERE p.version_id = $1 AND p.pid = $2
+            "#,
+            version_id,
+            pid
+        )
+        .fetch_optional(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?
+        else {
+            return Ok(None);
+        };
+
+        let bundles = query_as!(
+            PackBundle,
+            r#"
+SELECT
+    bi.path,
+    bi.ab_size,
+    bi.total_size,
+    bi.md5,
+    bi.hash,
+    b.file AS "file_id?"
+FROM bundle_infos bi
+LEFT JOIN bundles b ON b.version = bi.version_id AND b.path = bi.path
+WHERE bi.version_id = $1 AND bi.pid = $2
+ORDER BY bi.path
+            "#,
+            version_id,
+            pid
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        Ok(Some(PackDetails { pack, bundles }))
+    }
+}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+/// Keyset position after the last row of a sorted page: the row's sort key,
+/// numeric or textual depending on the sort column and absent for rows
+/// without a value, followed by the row id as tie breaker.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SortCursor {
+    pub num: Option<i64>,
+    pub text: Option<String>,
+    pub id: i32,
+}
+
+impl SortCursor {
+    #[must_use]
+    pub const fn is_missing(&self) -> bool {
+        self.num.is_none() && self.text.is_none()
+    }
+
+    /// Whether the cursor holds a key of the kind the sort column has, so a
+    /// stale or crafted cursor is never compared with the wrong type.
+    #[must_use]
+    pub const fn fits(&self, numeric: bool) -> bool {
+        if numeric {
+            self.text.is_none()
+        } else {
+            self.num.is_none()
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use crate::{
+    AppError, AppResult,
+    database::{
+        Database,
+        model::ReplicaStatus,
+        row::{FileRow, ReplicationTask},
+    },
+};
+use sqlx::{query, query_as, query_scalar};
+
+impl Database {
+    /// Inserts the file and queues it for every replica in one transaction, so
+    /// a stored object is never left without its replication entries. A file
+    /// another worker stored concurrently is reused.
+    pub async fn create_file_with_replicas(
+        &self,
+        file: FileRow,
+        replicas: &[String],
+    ) -> AppResult<i32> {
+        let mut tx = self
+            .pool()
+            .begin()
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        let file_id = query_scalar!(
+            "INSERT INTO files (hash, size) VALUES ($1, $2) ON CONFLICT (hash) DO UPDATE SET size = EXCLUDED.size RETURNING id",
+            file.hash.as_str(),
+            file.size
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        query!(
+            "INSERT INTO replication_queue (replica, file_id) SELECT UNNEST($1::varchar[]), $2 ON CONFLICT DO NOTHING",
+            replicas,
+            file_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        tx.commit()
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(file_id)
+    }
+
+    /// Claims up to `limit` due tasks and pushes their next attempt `lease_seconds`
+    /// into the future, so a crashed worker's tasks are picked up again later.
+    pub async fn claim_replication_tasks(
+        &self,
+        replicas: &[String],
+        limit: i64,
+        lease_seconds: f64,
+    ) -> AppResult<Vec<ReplicationTask>> {
+        query_as!(
+            ReplicationTask,
+            r#"
+UPDATE replication_queue q
+SET next_attempt_at = NOW() + make_interval(secs => $3)
+FROM files f
+WHERE q.file_id = f.id
+  AND q.id IN (
+    SELECT id FROM replication_queue
+    WHERE replica = ANY($1) AND next_attempt_at <= NOW()
+    ORDER BY id
+    LIMIT $2
+    FOR UPDATE SKIP LOCKED
+  )
+RETURNING q.id, q.replica, f.hash, q.attempts
+            "#,
+            replicas,
+            limit,
+            lease_seconds
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    pub async fn complete_replication_task(&self, id: i64) -> AppResult<()> {
+        query!("DELETE FROM replication_queue WHERE id = $1", id)
+            .execute(self.pool())
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(())
+    }
+
+    /// Drops the queued copy of `file_id` once the replica is known to hold it.
+    pub async fn clear_replication_task(&self, replica: &str, file_id: i32) -> AppResult<()> {
+        query!(
+            "DELETE FROM replication_queue WHERE replica = $1 AND file_id = $2",
+            replica,
+            file_id
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(())
+    }
+
+    pub async fn fail_replication_task(
+        &self,
+        id: i64,
+        error: &str,
+        retry_seconds: f64,
+    ) -> AppResult<()> {
+        query!(
+            r#"
+UPDATE replication_queue
+SET attempts = attempts + 1,
+    last_error = $2,
+    next_attempt_at = NOW() + make_interval(secs => $3)
+WHERE id = $1
+            "#,
+            id,
+            error,
+            retry_seconds
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(())
+    }
+
+    pub async fn query_replication_status(
+        &self,
+        replicas: &[String],
+    ) -> AppResult<Vec<ReplicaStatus>> {
+        query_as!(
+            ReplicaStatus,
+            r#"
+SELECT
+    r.name AS "replica!",
+    COUNT(q.id) AS "pending!",
+    COUNT(q.id) FILTER (WHERE q.attempts > 0) AS "failing!",
+    MIN(q.created_at) AS oldest_pending_at,
+    COALESCE(EXTRACT(EPOCH FROM NOW() - MIN(q.created_at))::bigint, 0) AS "lag_seconds!",
+    (ARRAY_AGG(q.last_error ORDER BY q.next_attempt_at DESC) FILTER (WHERE q.last_error IS NOT NULL))[1] AS last_error
+FROM UNNEST($1::varchar[]) AS r(name)
+LEFT JOIN replication_queue q ON q.replica = r.name
+GROUP BY r.name
+ORDER BY r.name
+            "#,
+            replicas
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+}
@@ -1,6 +1,9 @@
+use chrono::{DateTime, Utc};
+
 #[derive(Debug, Clone)]
 pub struct VersionRow {
     pub id: Option<i32>,
+    pub channel: String,
     pub res: String,
     pub client: String,
     pub is_ready: bool,
@@ -14,13 +17,15 @@ pub struct BundleRow {
     pub path: String,
     pub version_id: i32,
     pub file_id: i32,
+    pub upstream_hash: Option<String>,
+    pub upstream_md5: Option<String>,
 }
 
 #[derive(Debug, Clone)]
 pub struct FileRow {
     pub id: Option<i32>,
     pub hash: String,
-    pub size: i32,
+    pub size: i64,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -35,6 +40,12 @@ pub struct AssetMappingRow {
     pub node_type: NodeType,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundleDependencyRow {
+    pub bundle_path: String,
+    pub dependency_path: String,
+}
+
 #[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
 #[sqlx(type_name = "node_type", rename_all = "lowercase")]
 pub enum NodeType {
@@ -50,3 +61,54 @@ pub enum AssetMappingStatus {
     Importing,
     Ready,
 }
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "download_status", rename_all = "lowercase")]
+pub enum DownloadStatus {
+    Pending,
+    Partial,
+    Ready,
+}
+
+#[derive(Debug, Clone)]
+pub struct DownloadJob {
+    pub id: i64,
+    pub version_id: i32,
+    pub path: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReplicationTask {
+    pub id: i64,
+    pub replica: String,
+    pub hash: String,
+    pub attempts: i32,
+}
+
+#[derive(sqlx::Type, Debug, Clone, Copy, PartialEq, Eq)]
+#[sqlx(type_name = "admin_command_kind", rename_all = "snake_case")]
+pub enum AdminCommandKind {
+    CheckVersion,
+    DownloadVersion,
+    ImportAssetMapping,
+    ImportItemDemand,
+    ResetVersion,
+}
+
+#[derive(Debug, Clone)]
+pub struct AdminCommandRow {
+    pub id: i64,
+    pub kind: AdminCommandKind,
+    pub version_id: Option<i32>,
+    pub res_version: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ApiKeyRow {
+    pub id: i32,
+    pub name: String,
+    pub key_hash: String,
+    pub scopes: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,190 @@
+use crate::{
+    AppError, AppResult,
+    database::{
+        Database,
+        model::{BundleSearchHit, ManifestSearchHit},
+    },
+};
+use serde::{Deserialize, Serialize};
+use sqlx::query_as;
+
+/// Position after the last hit of a page. Hits are ordered by descending
+/// score, then ascending key (asset name or bundle path).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchCursor {
+    pub score: f32,
+    pub key: String,
+}
+
+impl From<&ManifestSearchHit> for SearchCursor {
+    fn from(hit: &ManifestSearchHit) -> Self {
+        Self {
+            score: hit.score,
+            key: hit.path.clone(),
+        }
+    }
+}
+
+impl From<&BundleSearchHit> for SearchCursor {
+    fn from(hit: &BundleSearchHit) -> Self {
+        Self {
+            score: hit.score,
+            key: hit.path.clone(),
+        }
+    }
+}
+
+fn contains_pattern(query: &str) -> String {
+    let escaped = query
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{escaped}%")
+}
+
+impl Database {
+    /// Ranked search over asset names, short names and asset paths. Without
+    /// `version_id` every version is searched and each asset is reported
+    /// once, from the latest version containing it. Returns up to `limit`
+    /// hits after `after` together with the total number of hits.
+    pub async fn search_manifest(
+        &self,
+        version_id: Option<i32>,
+        query: &str,
+        after: Option<&SearchCursor>,
+        limit: i64,
+    ) -> AppResult<(Vec<ManifestSearchHit>, i64)> {
+        let pattern = contains_pattern(query);
+        let total = sqlx::query_scalar!(
+            r#"
+SELECT COUNT(DISTINCT asset_name) AS "total!"
+FROM asset_to_bundle_mappings
+WHERE ($1::int IS NULL OR version_id = $1)
+    AND node_type IN ('file', 'both')
+    AND (asset_name ILIKE $2 OR short_name ILIKE $2 OR asset_path ILIKE $2)
+            "#,
+            version_id,
+            pattern
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        let hits = query_as!(
+            ManifestSearchHit,
+            r#"
+WITH hits AS (
+    SELECT DISTINCT ON (m.asset_name)
+        m.asset_name,
+        m.node_type,
+        m.short_name,
+        m.asset_path,
+        m.version_id,
+        GREATEST(
+            similarity(m.asset_name, $2),
+            similarity(m.short_name, $2),
+            similarity(m.asset_path, $2)
+        ) AS score
+    FROM asset_to_bundle_mappings m
+    WHERE ($1::int IS NULL OR m.version_id = $1)
+        AND m.node_type IN ('file', 'both')
+        AND (m.asset_name ILIKE $3 OR m.short_name ILIKE $3 OR m.asset_path ILIKE $3)
+    ORDER BY m.asset_name, m.version_id DESC
+)
+SELECT
+    split_part(h.asset_name, '/', array_length(string_to_array(h.asset_name, '/'), 1)) AS "name!",
+    h.asset_name AS "path!",
+    h.node_type::text AS "node_type!",
+    h.short_name,
+    h.asset_path,
+    h.version_id,
+    v.res AS res_version,
+    h.score AS "score!"
+FROM hits h
+INNER JOIN versions v ON v.id = h.version_id
+WHERE $4::real IS NULL OR h.score < $4 OR (h.score = $4 AND h.asset_name > $5)
+ORDER BY h.score DESC, h.asset_name
+LIMIT $6
+            "#,
+            version_id,
+            query,
+            pattern,
+            after.map(|cursor| cursor.score),
+            after.map(|cursor| cursor.key.as_str()),
+            limit
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        Ok((hits, total))
+    }
+
+    /// Ranked search over bundle paths, see [`Database::search_manifest`].
+    pub async fn search_bundles(
+        &self,
+        version_id: Option<i32>,
+        query: &str,
+        after: Option<&SearchCursor>,
+        limit: i64,
+    ) -> AppResult<(Vec<BundleSearchHit>, i64)> {
+        let pattern = contains_pattern(query);
+        let total = sqlx::query_scalar!(
+            r#"
+SELECT COUNT(DISTINCT path) AS "total!"
+FROM bundles
+WHERE ($1::int IS NULL OR version = $1) AND path ILIKE $2
+            "#,
+            version_id,
+            pattern
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        let hits = query_as!(
+            BundleSearchHit,
+            r#"
+WITH hits AS (
+    SELECT DISTINCT ON (b.path)
+        b.id,
+        b.path,
+        b.file,
+        b.version,
+        similarity(b.path, $2) AS score
+    FROM bundles b
+    WHERE ($1::int IS NULL OR b.version = $1) AND b.path ILIKE $3
+    ORDER BY b.path, b.version DESC
+)
+SELECT
+    h.id,
+    h.path,
+    h.file AS file_id,
+    f.hash AS file_hash,
+    f.size AS file_size,
+    h.version AS version_id,
+    v.channel AS version_channel,
+    v.res AS version_res,
+    v.client AS version_client,
+    h.score AS "score!"
+FROM hits h
+INNER JOIN files f ON f.id = h.file
+INNER JOIN versions v ON v.id = h.version
+WHERE $4::real IS NULL OR h.score < $4 OR (h.score = $4 AND h.path > $5)
+ORDER BY h.score DESC, h.path
+LIMIT $6
+            "#,
+            version_id,
+            query,
+            pattern,
+            after.map(|cursor| cursor.score),
+            after.map(|cursor| cursor.key.as_str()),
+            limit
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        Ok((hits, total))
+    }
+}
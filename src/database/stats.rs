@@ -0,0 +1,144 @@
+use crate::{
+    AppError, AppResult,
+    database::{
+        Database,
+        model::{BundleSize, StorageStats, VersionGrowth, VersionStats},
+    },
+};
+use sqlx::{query, query_as};
+
+const LARGEST_NEW_BUNDLES: i64 = 20;
+
+impl Database {
+    /// A bundle counts as new when no earlier version references its file.
+    /// New bytes count every new file once, however many bundles of the
+    /// version share it.
+    pub async fn query_version_stats(&self, version_id: i32) -> AppResult<VersionStats> {
+        let totals = query!(
+            r#"
+WITH version_bundles AS (
+    SELECT
+        b.file,
+        f.size,
+        NOT EXISTS (
+            SELECT 1 FROM bundles p WHERE p.file = b.file AND p.version < b.version
+        ) AS is_new
+    FROM bundles b
+    JOIN files f ON f.id = b.file
+    WHERE b.version = $1
+)
+SELECT
+    COUNT(*) AS "bundle_count!",
+    COALESCE(SUM(size), 0)::bigint AS "total_size!",
+    COUNT(*) FILTER (WHERE is_new) AS "new_bundle_count!",
+    (
+        SELECT COALESCE(SUM(size), 0)::bigint
+        FROM (SELECT DISTINCT file, size FROM version_bundles WHERE is_new) new_files
+    ) AS "new_bytes!"
+FROM version_bundles
+            "#,
+            version_id
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        let largest_new_bundles = query_as!(
+            BundleSize,
+            r#"
+SELECT b.path, f.hash AS file_hash, f.size
+FROM bundles b
+JOIN files f ON f.id = b.file
+WHERE b.version = $1
+  AND NOT EXISTS (SELECT 1 FROM bundles p WHERE p.file = b.file AND p.version < b.version)
+ORDER BY f.size DESC, b.path
+LIMIT $2
+            "#,
+            version_id,
+            LARGEST_NEW_BUNDLES
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        Ok(VersionStats {
+            version_id,
+            bundle_count: totals.bundle_count,
+            total_size: totals.total_size,
+            new_bundle_count: totals.new_bundle_count,
+            new_bytes: totals.new_bytes,
+            reused_bytes: totals.total_size - totals.new_bytes,
+            largest_new_bundles,
+        })
+    }
+
+    pub async fn query_storage_stats(&self) -> AppResult<StorageStats> {
+        let totals = query!(
+            r#"
+SELECT
+    (SELECT COUNT(*) FROM versions) AS "version_count!",
+    (SELECT COUNT(*) FROM bundles) AS "bundle_count!",
+    (SELECT COUNT(*) FROM files) AS "file_count!",
+    (SELECT COALESCE(SUM(size), 0)::bigint FROM files) AS "unique_bytes!",
+    (SELECT COALESCE(SUM(f.size), 0)::bigint FROM bundles b JOIN files f ON f.id = b.file) AS "logical_bytes!"
+            "#
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        let growth = query_as!(
+            VersionGrowth,
+            r#"
+WITH first_seen AS (
+    SELECT b.file, MIN(b.version) AS version
+    FROM bundles b
+    GROUP BY b.file
+),
+introduced AS (
+    SELECT fs.version, SUM(f.size)::bigint AS bytes
+    FROM first_seen fs
+    JOIN files f ON f.id = fs.file
+    GROUP BY fs.version
+),
+logical AS (
+    SELECT b.version, SUM(f.size)::bigint AS bytes
+    FROM bundles b
+    JOIN files f ON f.id = b.file
+    GROUP BY b.version
+)
+SELECT
+    v.id AS version_id,
+    v.channel,
+    v.res AS res_version,
+    COALESCE(l.bytes, 0) AS "total_size!",
+    COALESCE(i.bytes, 0) AS "new_bytes!",
+    (SUM(COALESCE(i.bytes, 0)) OVER (ORDER BY v.id))::bigint AS "cumulative_unique_bytes!"
+FROM versions v
+LEFT JOIN logical l ON l.version = v.id
+LEFT JOIN introduced i ON i.version = v.id
+ORDER BY v.id
+            "#
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        #[allow(clippy::cast_precision_loss)]
+        let dedupe_ratio = if totals.unique_bytes == 0 {
+            1.0
+        } else {
+            totals.logical_bytes as f64 / totals.unique_bytes as f64
+        };
+
+        Ok(StorageStats {
+            version_count: totals.version_count,
+            bundle_count: totals.bundle_count,
+            file_count: totals.file_count,
+            unique_bytes: totals.unique_bytes,
+            logical_bytes: totals.logical_bytes,
+            dedupe_ratio,
+            growth,
+        })
+    }
+}
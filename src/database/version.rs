@@ -2,14 +2,70 @@ use crate::{
     AppError, AppResult,
     database::{
         Database,
-        model::{VersionDetails, VersionSummary},
+        download_job::queue_version_jobs,
+        model::{DeletedVersion, VersionDetails, VersionSummary},
+        pagination::SortCursor,
         row::{AssetMappingStatus, VersionRow},
     },
 };
-use sqlx::query_as;
+use serde::Deserialize;
+use sqlx::{QueryBuilder, query_as};
+use utoipa::ToSchema;
+
+/// Outcome of [`Database::delete_version`].
+#[derive(Debug)]
+pub enum VersionDeletion {
+    Deleted(DeletedVersion),
+    NotFound,
+    /// Workers still hold or wait for download jobs of the version.
+    Downloading,
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum VersionSort {
+    #[default]
+    Id,
+    ResVersion,
+    ClientVersion,
+}
+
+impl VersionSort {
+    /// Column the page query sorts on ahead of the id tie breaker, absent
+    /// when sorting by id alone.
+    const fn column(self) -> Option<&'static str> {
+        match self {
+            Self::Id => None,
+            Self::ResVersion => Some("res"),
+            Self::ClientVersion => Some("client"),
+        }
+    }
+
+    /// Whether the sort key is numeric rather than textual.
+    #[must_use]
+    pub const fn is_numeric(self) -> bool {
+        matches!(self, Self::Id)
+    }
+
+    /// Cursor positioned right after `version` in this sort.
+    #[must_use]
+    pub fn cursor(self, version: &VersionSummary) -> SortCursor {
+        let (num, text) = match self {
+            Self::Id => (Some(i64::from(version.id)), None),
+            Self::ResVersion => (None, Some(version.res_version.clone())),
+            Self::ClientVersion => (None, Some(version.client_version.clone())),
+        };
+        SortCursor {
+            num,
+            text,
+            id: version.id,
+        }
+    }
+}
 
 fn build_version(
     id: i32,
+    channel: String,
     res: String,
     client: String,
     is_ready: bool,
@@ -18,6 +74,7 @@ fn build_version(
 ) -> VersionRow {
     VersionRow {
         id: Some(id),
+        channel,
         res,
         client,
         is_ready,
@@ -27,14 +84,52 @@ fn build_version(
 }
 
 impl Database {
+    /// Inserts the version together with one `bundle_infos` row per bundle and
+    /// one `version_packs` row per pack of its hot update list.
     pub async fn create_version(&self, version: VersionRow) -> AppResult<i32> {
         let row = sqlx::query!(
-            "INSERT INTO versions (res, client, is_ready, hot_update_list, asset_mapping_status) VALUES ($1, $2, $3, $4, $5::asset_mapping_status) RETURNING id",
+            r#"
+WITH version AS (
+    INSERT INTO versions (res, client, is_ready, hot_update_list, asset_mapping_status, download_status, channel)
+    VALUES ($1, $2, $3, $4, $5::asset_mapping_status, CASE WHEN $3 THEN 'ready' ELSE 'pending' END::download_status, $6)
+    RETURNING id, hot_update_list
+),
+infos AS (
+    INSERT INTO bundle_infos (version_id, path, ab_size, total_size, md5, hash, pid, type, cid)
+    SELECT
+        version.id,
+        info->>'name',
+        (info->>'abSize')::bigint,
+        (info->>'totalSize')::bigint,
+        info->>'md5',
+        info->>'hash',
+        info->>'pid',
+        info->>'type',
+        (info->>'cid')::int
+    FROM version, jsonb_array_elements(version.hot_update_list::jsonb->'abInfos') AS info
+    ON CONFLICT (version_id, path) DO NOTHING
+),
+packs AS (
+    INSERT INTO version_packs (version_id, pid, total_size, ab_size, md5, hash, cid)
+    SELECT
+        version.id,
+        pack->>'name',
+        COALESCE((pack->>'totalSize')::bigint, 0),
+        (pack->>'abSize')::bigint,
+        pack->>'md5',
+        pack->>'hash',
+        (pack->>'cid')::int
+    FROM version, jsonb_array_elements(COALESCE(version.hot_update_list::jsonb->'packInfos', '[]'::jsonb)) AS pack
+    ON CONFLICT (version_id, pid) DO NOTHING
+)
+SELECT id AS "id!" FROM version
+            "#,
             version.res,
             version.client,
             version.is_ready,
             version.hot_update_list,
-            version.asset_mapping_status as AssetMappingStatus
+            version.asset_mapping_status as AssetMappingStatus,
+            version.channel
         )
         .fetch_one(self.pool())
         .await
@@ -45,7 +140,7 @@ impl Database {
 
     pub async fn get_version_by_id(&self, id: i32) -> AppResult<Option<VersionRow>> {
         let result = sqlx::query!(
-            "SELECT id, res, client, is_ready, hot_update_list, asset_mapping_status AS \"asset_mapping_status!: AssetMappingStatus\" FROM versions WHERE id = $1",
+            "SELECT id, channel, res, client, is_ready, hot_update_list, asset_mapping_status AS \"asset_mapping_status!: AssetMappingStatus\" FROM versions WHERE id = $1",
             id
         )
         .fetch_optional(self.pool())
@@ -55,6 +150,7 @@ impl Database {
         Ok(result.map(|row| {
             build_version(
                 row.id,
+                row.channel,
                 row.res,
                 row.client,
                 row.is_ready,
@@ -64,9 +160,14 @@ impl Database {
         }))
     }
 
-    pub async fn get_version_by_res(&self, res: &str) -> AppResult<Option<VersionRow>> {
+    pub async fn get_version_by_res(
+        &self,
+        channel: &str,
+        res: &str,
+    ) -> AppResult<Option<VersionRow>> {
         let result = sqlx::query!(
-            "SELECT id, res, client, is_ready, hot_update_list, asset_mapping_status AS \"asset_mapping_status!: AssetMappingStatus\" FROM versions WHERE res = $1",
+            "SELECT id, channel, res, client, is_ready, hot_update_list, asset_mapping_status AS \"asset_mapping_status!: AssetMappingStatus\" FROM versions WHERE channel = $1 AND res = $2",
+            channel,
             res
         )
         .fetch_optional(self.pool())
@@ -76,6 +177,7 @@ impl Database {
         Ok(result.map(|row| {
             build_version(
                 row.id,
+                row.channel,
                 row.res,
                 row.client,
                 row.is_ready,
@@ -85,9 +187,10 @@ impl Database {
         }))
     }
 
-    pub async fn get_latest_version(&self) -> AppResult<Option<VersionRow>> {
+    pub async fn get_latest_version(&self, channel: &str) -> AppResult<Option<VersionRow>> {
         let result = sqlx::query!(
-            "SELECT id, res, client, is_ready, hot_update_list, asset_mapping_status AS \"asset_mapping_status!: AssetMappingStatus\" FROM versions ORDER BY id DESC LIMIT 1"
+            "SELECT id, channel, res, client, is_ready, hot_update_list, asset_mapping_status AS \"asset_mapping_status!: AssetMappingStatus\" FROM versions WHERE channel = $1 ORDER BY id DESC LIMIT 1",
+            channel
         )
         .fetch_optional(self.pool())
         .await
@@ -96,6 +199,7 @@ impl Database {
         Ok(result.map(|row| {
             build_version(
                 row.id,
+                row.channel,
                 row.res,
                 row.client,
                 row.is_ready,
@@ -105,45 +209,48 @@ impl Database {
         }))
     }
 
-    pub async fn is_client_and_res_exist(&self, client: &str, res: &str) -> AppResult<bool> {
-        let result = sqlx::query!(
-            "SELECT id FROM versions WHERE client = $1 AND res = $2",
-            client,
-            res
+    /// Latest ready version of `channel` created before `id`.
+    pub async fn get_previous_ready_version_id(
+        &self,
+        channel: &str,
+        id: i32,
+    ) -> AppResult<Option<i32>> {
+        sqlx::query_scalar!(
+            "SELECT id FROM versions WHERE channel = $1 AND id < $2 AND is_ready ORDER BY id DESC LIMIT 1",
+            channel,
+            id
         )
         .fetch_optional(self.pool())
         .await
-        .map_err(|err| AppError::ExternalService(err.into()))?;
-
-        Ok(result.is_some())
+        .map_err(|err| AppError::ExternalService(err.into()))
     }
 
-    pub async fn get_oldest_unready_version(&self) -> AppResult<Option<VersionRow>> {
+    pub async fn is_client_and_res_exist(
+        &self,
+        channel: &str,
+        client: &str,
+        res: &str,
+    ) -> AppResult<bool> {
         let result = sqlx::query!(
-            "SELECT id, res, client, is_ready, hot_update_list, asset_mapping_status AS \"asset_mapping_status!: AssetMappingStatus\" FROM versions WHERE is_ready = false ORDER BY id ASC LIMIT 1"
+            "SELECT id FROM versions WHERE channel = $1 AND client = $2 AND res = $3",
+            channel,
+            client,
+            res
         )
         .fetch_optional(self.pool())
         .await
         .map_err(|err| AppError::ExternalService(err.into()))?;
 
-        Ok(result.map(|row| {
-            build_version(
-                row.id,
-                row.res,
-                row.client,
-                row.is_ready,
-                &row.hot_update_list,
-                row.asset_mapping_status,
-            )
-        }))
+        Ok(result.is_some())
     }
 
-    pub async fn mark_version_ready(&self, id: i32) -> AppResult<()> {
-        sqlx::query!("UPDATE versions SET is_ready = true WHERE id = $1", id)
-            .execute(self.pool())
+    pub async fn is_version_exist(&self, id: i32) -> AppResult<bool> {
+        let result = sqlx::query!("SELECT id FROM versions WHERE id = $1", id)
+            .fetch_optional(self.pool())
             .await
             .map_err(|err| AppError::ExternalService(err.into()))?;
-        Ok(())
+
+        Ok(result.is_some())
     }
 
     pub async fn set_asset_mapping_status(
@@ -162,24 +269,240 @@ impl Database {
         Ok(())
     }
 
-    pub async fn query_versions(&self) -> AppResult<Vec<VersionSummary>> {
+    /// Puts a version back into the download and asset mapping queues,
+    /// requeueing every bundle with a fresh attempt count.
+    pub async fn reset_version(&self, id: i32) -> AppResult<bool> {
+        let mut tx = self
+            .pool()
+            .begin()
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        let found = sqlx::query!(
+            "UPDATE versions SET is_ready = false, download_status = 'pending', asset_mapping_status = 'pending' WHERE id = $1",
+            id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?
+        .rows_affected()
+            > 0;
+        if found {
+            sqlx::query!(
+                "DELETE FROM bundle_download_attempts WHERE version_id = $1",
+                id
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+            queue_version_jobs(&mut tx, id).await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(found)
+    }
+
+    pub async fn query_versions(&self, channel: Option<&str>) -> AppResult<Vec<VersionSummary>> {
         query_as!(
             VersionSummary,
-            r#"SELECT id, client as "client_version", res as "res_version", is_ready, asset_mapping_status::text AS "asset_mapping_status!" FROM versions ORDER BY id ASC"#
+            r#"SELECT id, channel, client as "client_version", res as "res_version", is_ready, download_status::text AS "download_status!", asset_mapping_status::text AS "asset_mapping_status!" FROM versions WHERE ($1::varchar IS NULL OR channel = $1) ORDER BY id ASC"#,
+            channel
         )
         .fetch_all(self.pool())
         .await
         .map_err(|err| AppError::ExternalService(err.into()))
     }
 
+    pub async fn count_versions(&self, channel: Option<&str>) -> AppResult<i64> {
+        sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM versions WHERE ($1::varchar IS NULL OR channel = $1)"#,
+            channel
+        )
+        .fetch_one(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    /// Up to `limit` versions of `channel` that come after `after` in the
+    /// requested sort.
+    pub async fn query_version_page(
+        &self,
+        channel: Option<&str>,
+        sort: VersionSort,
+        descending: bool,
+        after: Option<&SortCursor>,
+        limit: i64,
+    ) -> AppResult<Vec<VersionSummary>> {
+        let mut builder = QueryBuilder::new(
+            r"
+SELECT
+    id,
+    channel,
+    client AS client_version,
+    res AS res_version,
+    is_ready,
+    download_status::text AS download_status,
+    asset_mapping_status::text AS asset_mapping_status
+FROM versions
+WHERE TRUE",
+        );
+        if let Some(channel) = channel {
+            builder.push(" AND channel = ").push_bind(channel);
+        }
+        let column = sort.column();
+        let (cmp, order) = if descending {
+            ("<", "DESC")
+        } else {
+            (">", "ASC")
+        };
+        if let Some(cursor) = after {
+            match (column, cursor.text.as_deref()) {
+                (Some(column), Some(text)) => {
+                    builder
+                        .push(format_args!(" AND ({column}, id) {cmp} ("))
+                        .push_bind(text)
+                        .push(", ")
+                        .push_bind(cursor.id)
+                        .push(")");
+                }
+                _ => {
+                    builder
+                        .push(format_args!(" AND id {cmp} "))
+                        .push_bind(cursor.id);
+                }
+            }
+        }
+        builder.push(" ORDER BY ");
+        if let Some(column) = column {
+            builder.push(format_args!("{column} {order}, "));
+        }
+        builder
+            .push(format_args!("id {order} LIMIT "))
+            .push_bind(limit);
+        builder
+            .build_query_as()
+            .fetch_all(self.pool())
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
     pub async fn query_version_detail_by_id(&self, id: i32) -> AppResult<Option<VersionDetails>> {
         query_as!(
             VersionDetails,
-            r#"SELECT id, client as "client_version", res as "res_version", is_ready, hot_update_list FROM versions WHERE id = $1"#,
+            r#"SELECT id, channel, client as "client_version", res as "res_version", is_ready, download_status::text AS "download_status!", hot_update_list FROM versions WHERE id = $1"#,
             id
         )
         .fetch_optional(self.pool())
         .await
         .map_err(|err| AppError::ExternalService(err.into()))
     }
+
+    /// Removes a version together with its bundles, bundle infos, packs, bundle
+    /// dependencies, asset mappings and download bookkeeping in one
+    /// transaction. Files are left for the garbage collector.
+    pub async fn delete_version(&self, id: i32) -> AppResult<VersionDeletion> {
+        let mut tx = self
+            .pool()
+            .begin()
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        let Some(version) = sqlx::query!(
+            "SELECT channel, res FROM versions WHERE id = $1 FOR UPDATE",
+            id
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?
+        else {
+            return Ok(VersionDeletion::NotFound);
+        };
+
+        // The row lock keeps the planner from queueing the version meanwhile.
+        let downloading = sqlx::query_scalar!(
+            r#"
+SELECT EXISTS (
+    SELECT 1 FROM download_jobs
+    WHERE version_id = $1 AND status IN ('pending', 'running')
+) AS "downloading!"
+            "#,
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        if downloading {
+            return Ok(VersionDeletion::Downloading);
+        }
+
+        let asset_mappings = sqlx::query!(
+            "DELETE FROM asset_to_bundle_mappings WHERE version_id = $1",
+            id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?
+        .rows_affected();
+
+        let bundles = sqlx::query!("DELETE FROM bundles WHERE version = $1", id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?
+            .rows_affected();
+
+        sqlx::query!(
+            "DELETE FROM bundle_download_attempts WHERE version_id = $1",
+            id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        sqlx::query!("DELETE FROM bundle_dependencies WHERE version_id = $1", id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        sqlx::query!("DELETE FROM version_packs WHERE version_id = $1", id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        sqlx::query!("DELETE FROM bundle_infos WHERE version_id = $1", id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        sqlx::query!("DELETE FROM download_jobs WHERE version_id = $1", id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        sqlx::query!(
+            "DELETE FROM version_download_progress WHERE version_id = $1",
+            id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        sqlx::query!("DELETE FROM versions WHERE id = $1", id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        tx.commit()
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+
+        Ok(VersionDeletion::Deleted(DeletedVersion {
+            version_id: id,
+            channel: version.channel,
+            res_version: version.res,
+            bundles,
+            asset_mappings,
+        }))
+    }
 }
@@ -0,0 +1,107 @@
+use crate::{
+    AppError, AppResult,
+    database::{Database, model::WorkerStatus},
+};
+use sqlx::{query, query_as};
+
+impl Database {
+    /// Records that `component` is alive on `worker`. The component counts as
+    /// stale once no heartbeat arrived for `stale_after_seconds`.
+    pub async fn worker_heartbeat(
+        &self,
+        component: &str,
+        worker: &str,
+        stale_after_seconds: i32,
+    ) -> AppResult<()> {
+        query!(
+            r"
+INSERT INTO worker_status (component, worker, stale_after_seconds)
+VALUES ($1, $2, $3)
+ON CONFLICT (component) DO UPDATE
+SET worker = EXCLUDED.worker,
+    heartbeat_at = NOW(),
+    stale_after_seconds = EXCLUDED.stale_after_seconds,
+    stopped_at = NULL
+            ",
+            component,
+            worker,
+            stale_after_seconds
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(())
+    }
+
+    /// Records the outcome of a run of `component`, which also counts as a
+    /// heartbeat. `error` is set when the run failed.
+    pub async fn record_worker_run(
+        &self,
+        component: &str,
+        worker: &str,
+        stale_after_seconds: i32,
+        outcome: &str,
+        error: Option<&str>,
+    ) -> AppResult<()> {
+        query!(
+            r"
+INSERT INTO worker_status (
+    component, worker, stale_after_seconds, last_run_at, last_success_at, last_outcome,
+    last_error_at, last_error
+)
+VALUES (
+    $1, $2, $3, NOW(), CASE WHEN $5::text IS NULL THEN NOW() END, $4,
+    CASE WHEN $5::text IS NOT NULL THEN NOW() END, $5
+)
+ON CONFLICT (component) DO UPDATE
+SET worker = EXCLUDED.worker,
+    heartbeat_at = NOW(),
+    stale_after_seconds = EXCLUDED.stale_after_seconds,
+    stopped_at = NULL,
+    last_run_at = NOW(),
+    last_success_at = COALESCE(EXCLUDED.last_success_at, worker_status.last_success_at),
+    last_outcome = EXCLUDED.last_outcome,
+    last_error_at = COALESCE(EXCLUDED.last_error_at, worker_status.last_error_at),
+    last_error = COALESCE(EXCLUDED.last_error, worker_status.last_error)
+            ",
+            component,
+            worker,
+            stale_after_seconds,
+            outcome,
+            error
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(())
+    }
+
+    pub async fn query_worker_status(&self) -> AppResult<Vec<WorkerStatus>> {
+        query_as!(
+            WorkerStatus,
+            r#"
+SELECT component, worker, heartbeat_at, stale_after_seconds,
+       heartbeat_at < NOW() - make_interval(secs => stale_after_seconds) AS "stale!",
+       stopped_at, last_run_at, last_success_at, last_outcome, last_error_at, last_error
+FROM worker_status
+ORDER BY component
+            "#
+        )
+        .fetch_all(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    /// Marks the components of a worker that shut down cleanly as stopped,
+    /// until another worker takes them over.
+    pub async fn mark_worker_stopped(&self, worker: &str) -> AppResult<()> {
+        query!(
+            "UPDATE worker_status SET stopped_at = NOW() WHERE worker = $1",
+            worker
+        )
+        .execute(self.pool())
+        .await
+        .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(())
+    }
+}
@@ -1,29 +1,53 @@
-use crate::{AppError, AppResult, config::AkApiConfig, service::types::RemoteVersion};
+use crate::{
+    AppError, AppResult,
+    config::{AkApiConfig, AkChannelConfig},
+    service::types::RemoteVersion,
+};
 use reqwest::Client;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::io::{AsyncWrite, AsyncWriteExt};
 use tracing::{info, instrument};
 
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+// Bundle downloads stream for as long as the upstream keeps sending, so
+// they are bounded by the idle time between reads rather than in total.
+const READ_TIMEOUT: Duration = Duration::from_secs(30);
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
 #[derive(Clone, Debug)]
 pub struct AkApi {
     client: Client,
+    channel: String,
     conf_url: String,
     asset_url: String,
 }
 
 impl AkApi {
-    pub fn new(config: &AkApiConfig) -> AppResult<Self> {
+    pub fn new(config: &AkChannelConfig) -> AppResult<Self> {
         let client = reqwest::ClientBuilder::new()
-            .timeout(Duration::from_secs(30))
+            .connect_timeout(CONNECT_TIMEOUT)
+            .read_timeout(READ_TIMEOUT)
             .build()
             .map_err(|err| AppError::ExternalService(err.into()))?;
 
         Ok(Self {
             client,
+            channel: config.name.clone(),
             conf_url: config.conf_url.clone(),
             asset_url: config.asset_url.clone(),
         })
     }
 
+    /// Builds one client per configured channel, primary channel first.
+    pub fn from_config(config: &AkApiConfig) -> AppResult<Vec<Self>> {
+        config.channels()?.iter().map(Self::new).collect()
+    }
+
+    #[must_use]
+    pub fn channel(&self) -> &str {
+        &self.channel
+    }
+
     #[instrument(name = "ak_api.get_version", skip(self))]
     pub async fn get_version(&self) -> AppResult<RemoteVersion> {
         let timestamp = SystemTime::now()
@@ -37,6 +61,7 @@ impl AkApi {
         let version = self
             .client
             .get(url)
+            .timeout(REQUEST_TIMEOUT)
             .send()
             .await
             .map_err(|err| AppError::ExternalService(err.into()))?
@@ -54,6 +79,7 @@ impl AkApi {
 
         self.client
             .get(url)
+            .timeout(REQUEST_TIMEOUT)
             .send()
             .await
             .map_err(|err| AppError::ExternalService(err.into()))?
@@ -62,21 +88,39 @@ impl AkApi {
             .map_err(|err| AppError::ExternalService(err.into()))
     }
 
-    #[instrument(name = "ak_api.download_file", skip(self))]
-    pub async fn download_file(&self, res_version: &str, path: &str) -> AppResult<Vec<u8>> {
+    #[instrument(name = "ak_api.download_file", skip(self, writer))]
+    pub async fn download_file<W>(
+        &self,
+        res_version: &str,
+        path: &str,
+        writer: &mut W,
+    ) -> AppResult<u64>
+    where
+        W: AsyncWrite + Unpin + Send,
+    {
         let url = format!("{}/{res_version}/{path}", self.asset_url);
         info!("downloading file from {res_version}/{path}");
 
-        let bytes = self
+        let mut response = self
             .client
             .get(url)
             .send()
             .await
             .map_err(|err| AppError::ExternalService(err.into()))?
-            .bytes()
-            .await
+            .error_for_status()
             .map_err(|err| AppError::ExternalService(err.into()))?;
 
-        Ok(bytes.to_vec())
+        let mut size = 0;
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?
+        {
+            writer.write_all(&chunk).await?;
+            size += chunk.len() as u64;
+        }
+        writer.flush().await?;
+
+        Ok(size)
     }
 }
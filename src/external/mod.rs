@@ -2,6 +2,6 @@ pub mod ak_api;
 pub mod docker;
 pub mod github;
 pub mod notification;
-pub mod s3;
+pub mod storage;
 pub mod torappu;
 pub mod types;
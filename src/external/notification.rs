@@ -24,6 +24,7 @@ impl NotificationClient {
 
     pub async fn notify_update(
         &self,
+        channel: &str,
         old_client: &str,
         old_res: &str,
         new_client: &str,
@@ -31,15 +32,21 @@ impl NotificationClient {
     ) {
         if let Self::Smtp(client) = self {
             client
-                .notify_update(old_client, old_res, new_client, new_res)
+                .notify_update(channel, old_client, old_res, new_client, new_res)
                 .await;
         }
     }
 
-    pub async fn notify_download_finished(&self, client_version: &str, res_version: &str) {
+    pub async fn notify_download_finished(
+        &self,
+        channel: &str,
+        client_version: &str,
+        res_version: &str,
+        pack_changes: &[String],
+    ) {
         if let Self::Smtp(client) = self {
             client
-                .notify_download_finished(client_version, res_version)
+                .notify_download_finished(channel, client_version, res_version, pack_changes)
                 .await;
         }
     }
@@ -85,13 +92,14 @@ impl SmtpNotificationClient {
     #[instrument(name = "smtp.notify_update", skip(self))]
     pub async fn notify_update(
         &self,
+        channel: &str,
         old_client: &str,
         old_res: &str,
         new_client: &str,
         new_res: &str,
     ) {
         if let Err(err) = self
-            .inner_notify_update(old_client, old_res, new_client, new_res)
+            .inner_notify_update(channel, old_client, old_res, new_client, new_res)
             .await
         {
             error!("Failed to send update notification: {err:?}");
@@ -100,14 +108,16 @@ impl SmtpNotificationClient {
 
     async fn inner_notify_update(
         &self,
+        channel: &str,
         old_client: &str,
         old_res: &str,
         new_client: &str,
         new_res: &str,
     ) -> AppResult<()> {
-        let subject = format!("AK Asset Update: {old_res} -> {new_res}");
+        let subject = format!("AK Asset Update [{channel}]: {old_res} -> {new_res}");
         let plain_body = format!(
-            "UPDATE: {} {} -> {} {}\n{}{}...{}",
+            "UPDATE [{}]: {} {} -> {} {}\n{}{}...{}",
+            channel,
             old_client,
             old_res,
             new_client,
@@ -117,7 +127,8 @@ impl SmtpNotificationClient {
             new_res
         );
         let html_body = format!(
-            "UPDATE: {} {} -> {} {}<br><a href='{}{}...{}'>View Details</a>",
+            "UPDATE [{}]: {} {} -> {} {}<br><a href='{}{}...{}'>View Details</a>",
+            channel,
             old_client,
             old_res,
             new_client,
@@ -158,10 +169,16 @@ impl SmtpNotificationClient {
         Ok(())
     }
 
-    #[instrument(name = "smtp.notify_download_finished", skip(self))]
-    pub async fn notify_download_finished(&self, client_version: &str, res_version: &str) {
+    #[instrument(name = "smtp.notify_download_finished", skip(self, pack_changes))]
+    pub async fn notify_download_finished(
+        &self,
+        channel: &str,
+        client_version: &str,
+        res_version: &str,
+        pack_changes: &[String],
+    ) {
         if let Err(err) = self
-            .inner_notify_download_finished(client_version, res_version)
+            .inner_notify_download_finished(channel, client_version, res_version, pack_changes)
             .await
         {
             error!("Failed to send download completion notification: {err:?}");
@@ -170,11 +187,19 @@ impl SmtpNotificationClient {
 
     async fn inner_notify_download_finished(
         &self,
+        channel: &str,
         client_version: &str,
         res_version: &str,
+        pack_changes: &[String],
     ) -> AppResult<()> {
-        let subject = format!("AK Asset Download Completed: {client_version} {res_version}");
-        let body = format!("Download completed for version {client_version} {res_version}");
+        let subject =
+            format!("AK Asset Download Completed [{channel}]: {client_version} {res_version}");
+        let mut body =
+            format!("Download completed for {channel} version {client_version} {res_version}");
+        if !pack_changes.is_empty() {
+            body.push_str("\n\nChanges by pack:\n");
+            body.push_str(&pack_changes.join("\n"));
+        }
 
         let email = Message::builder()
             .from(
@@ -0,0 +1,244 @@
+use crate::{
+    AppError, AppResult,
+    config::{AppSettings, S3Config, StorageBackend, StorageConfig},
+};
+use bytes::Bytes;
+use futures::{StreamExt, TryStreamExt, stream::BoxStream};
+use object_store::{
+    GetOptions, GetRange, ObjectMeta, ObjectStore, ObjectStoreExt, WriteMultipart,
+    aws::AmazonS3Builder, local::LocalFileSystem, memory::InMemory,
+};
+use std::{ops::Range, path::Path, sync::Arc};
+use tokio::{
+    fs::File,
+    io::{AsyncReadExt, AsyncWrite, AsyncWriteExt},
+};
+use tracing::{info, instrument, warn};
+
+const MULTIPART_THRESHOLD: u64 = 16 * 1024 * 1024;
+const MULTIPART_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+const MULTIPART_MAX_CONCURRENCY: usize = 4;
+
+/// Content addressed object storage backed by S3, a local directory or memory.
+#[derive(Debug, Clone)]
+pub struct Storage {
+    store: Arc<dyn ObjectStore>,
+}
+
+/// A named secondary copy of the primary storage.
+#[derive(Debug, Clone)]
+pub struct Replica {
+    pub name: String,
+    pub storage: Storage,
+}
+
+#[must_use]
+pub fn object_path(hash: &str) -> String {
+    format!("/{}/{}/{}", &hash[..2], &hash[2..4], &hash[4..])
+}
+
+impl Storage {
+    pub fn from_settings(settings: &AppSettings) -> AppResult<Self> {
+        Self::new(&settings.storage, settings.s3.as_ref())
+    }
+
+    pub fn new(config: &StorageConfig, s3: Option<&S3Config>) -> AppResult<Self> {
+        let storage = Self::build(config.backend, config.path.as_deref(), s3)?;
+        info!("Using {} storage backend", config.backend);
+        Ok(storage)
+    }
+
+    /// Builds the `[[storage.replicas]]` targets, rejecting duplicate names.
+    pub fn replicas_from_settings(settings: &AppSettings) -> AppResult<Vec<Replica>> {
+        let mut replicas: Vec<Replica> = Vec::new();
+        for config in &settings.storage.replicas {
+            if replicas.iter().any(|replica| replica.name == config.name) {
+                return Err(AppError::Application(anyhow::anyhow!(
+                    "duplicate storage replica: {}",
+                    config.name
+                )));
+            }
+            let storage = Self::build(config.backend, config.path.as_deref(), config.s3.as_ref())?;
+            info!(
+                "Using {} backend for replica {}",
+                config.backend, config.name
+            );
+            replicas.push(Replica {
+                name: config.name.clone(),
+                storage,
+            });
+        }
+        Ok(replicas)
+    }
+
+    fn build(
+        backend: StorageBackend,
+        path: Option<&str>,
+        s3: Option<&S3Config>,
+    ) -> AppResult<Self> {
+        let store: Arc<dyn ObjectStore> = match backend {
+            StorageBackend::S3 => {
+                let s3 = s3.ok_or_else(|| {
+                    AppError::Application(anyhow::anyhow!(
+                        "s3 settings must be configured for the s3 storage backend"
+                    ))
+                })?;
+                Arc::new(Self::build_s3(s3)?)
+            }
+            StorageBackend::Local => {
+                let path = path.ok_or_else(|| {
+                    AppError::Application(anyhow::anyhow!(
+                        "path must be set for the local storage backend"
+                    ))
+                })?;
+                std::fs::create_dir_all(path)?;
+                Arc::new(
+                    LocalFileSystem::new_with_prefix(path)
+                        .map_err(|err| AppError::Application(err.into()))?,
+                )
+            }
+            StorageBackend::Memory => Arc::new(InMemory::new()),
+        };
+        Ok(Self { store })
+    }
+
+    fn build_s3(config: &S3Config) -> AppResult<impl ObjectStore> {
+        AmazonS3Builder::new()
+            .with_allow_http(true)
+            .with_endpoint(&config.endpoint)
+            .with_bucket_name(&config.bucket_name)
+            .with_access_key_id(&config.access_key_id)
+            .with_secret_access_key(&config.secret_access_key)
+            .with_virtual_hosted_style_request(config.with_virtual_hosted_style_request)
+            .build()
+            .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    #[instrument(name = "storage.upload_file", skip(self))]
+    pub async fn upload_file(&self, path: &str, file: &Path) -> AppResult<()> {
+        let object_path = object_store::path::Path::from(path);
+        let size = tokio::fs::metadata(file).await?.len();
+
+        if size <= MULTIPART_THRESHOLD {
+            let bytes = Bytes::from(tokio::fs::read(file).await?);
+            self.store
+                .put(&object_path, bytes.into())
+                .await
+                .map_err(|err| AppError::ExternalService(err.into()))?;
+        } else {
+            let upload = self
+                .store
+                .put_multipart(&object_path)
+                .await
+                .map_err(|err| AppError::ExternalService(err.into()))?;
+            let mut writer = WriteMultipart::new_with_chunk_size(upload, MULTIPART_CHUNK_SIZE);
+
+            if let Err(err) = Self::write_parts(&mut writer, file).await {
+                if let Err(abort_err) = writer.abort().await {
+                    warn!("Failed to abort multipart upload {path}: {abort_err}");
+                }
+                return Err(err);
+            }
+            writer
+                .finish()
+                .await
+                .map_err(|err| AppError::ExternalService(err.into()))?;
+        }
+
+        info!("Uploaded object {path} ({size} bytes)");
+        Ok(())
+    }
+
+    async fn write_parts(writer: &mut WriteMultipart, file: &Path) -> AppResult<()> {
+        let mut reader = File::open(file).await?;
+        let mut buffer = vec![0; MULTIPART_CHUNK_SIZE];
+        loop {
+            let read = reader.read(&mut buffer).await?;
+            if read == 0 {
+                return Ok(());
+            }
+            writer
+                .wait_for_capacity(MULTIPART_MAX_CONCURRENCY)
+                .await
+                .map_err(|err| AppError::ExternalService(err.into()))?;
+            writer.write(&buffer[..read]);
+        }
+    }
+
+    #[instrument(name = "storage.head", skip(self))]
+    pub async fn head(&self, path: &str) -> AppResult<Option<ObjectMeta>> {
+        let object_path = object_store::path::Path::from(path);
+
+        match self.store.head(&object_path).await {
+            Ok(meta) => Ok(Some(meta)),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(err) => Err(AppError::ExternalService(err.into())),
+        }
+    }
+
+    #[instrument(name = "storage.get_stream", skip(self))]
+    pub async fn get_stream(
+        &self,
+        path: &str,
+        range: Option<Range<u64>>,
+    ) -> AppResult<BoxStream<'static, object_store::Result<Bytes>>> {
+        let object_path = object_store::path::Path::from(path);
+        let options = GetOptions {
+            range: range.map(GetRange::Bounded),
+            ..Default::default()
+        };
+
+        let result = self
+            .store
+            .get_opts(&object_path, options)
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))?;
+        Ok(result.into_stream())
+    }
+
+    #[instrument(name = "storage.delete", skip(self))]
+    pub async fn delete(&self, path: &str) -> AppResult<()> {
+        let object_path = object_store::path::Path::from(path);
+
+        match self.store.delete(&object_path).await {
+            Ok(()) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(err) => Err(AppError::ExternalService(err.into())),
+        }
+    }
+
+    #[instrument(name = "storage.list", skip(self))]
+    pub async fn list(&self) -> AppResult<Vec<ObjectMeta>> {
+        self.store
+            .list(None)
+            .try_collect()
+            .await
+            .map_err(|err| AppError::ExternalService(err.into()))
+    }
+
+    #[instrument(name = "storage.download", skip(self, writer))]
+    pub async fn download<W>(&self, path: &str, writer: &mut W) -> AppResult<u64>
+    where
+        W: AsyncWrite + Unpin + Send,
+    {
+        let mut stream = self.get_stream(path, None).await?;
+        let mut size = 0;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|err| AppError::ExternalService(err.into()))?;
+            writer.write_all(&chunk).await?;
+            size += chunk.len() as u64;
+        }
+        writer.flush().await?;
+        Ok(size)
+    }
+
+    /// Copies the object stored under `hash` into `target` through a temp file.
+    #[instrument(name = "storage.copy_to", skip(self, target))]
+    pub async fn copy_to(&self, hash: &str, target: &Self) -> AppResult<()> {
+        let path = object_path(hash);
+        let temp = tempfile::NamedTempFile::new()?;
+        let mut writer = File::from_std(temp.reopen()?);
+        self.download(&path, &mut writer).await?;
+        drop(writer);
+        target.upload_file(&path, temp.path()).await
+    }
+}
@@ -118,3 +118,16 @@ pub async fn shutdown_signal() {
         () = terminate => {},
     }
 }
+
+/// Identifies this process among the workers sharing the database, as
+/// `host:pid`.
+#[must_use]
+pub fn worker_id() -> String {
+    let host = std::env::var("HOSTNAME")
+        .ok()
+        .or_else(|| std::fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    format!("{host}:{}", std::process::id())
+}
@@ -0,0 +1,60 @@
+use crate::{AppResult, database::api_key::ApiScope};
+use anyhow::anyhow;
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+use subtle::ConstantTimeEq;
+
+const KEY_TAG: &str = "aks_";
+const KEY_BYTES: usize = 32;
+/// Characters of the key stored in clear to look it up.
+const PREFIX_LEN: usize = KEY_TAG.len() + 8;
+
+/// A freshly generated key. Only `prefix` and `hash` are stored, the key
+/// itself is shown once to whoever created it.
+pub struct GeneratedApiKey {
+    pub key: String,
+    pub prefix: String,
+    pub hash: String,
+}
+
+pub fn generate_api_key() -> AppResult<GeneratedApiKey> {
+    let mut bytes = [0_u8; KEY_BYTES];
+    getrandom::fill(&mut bytes).map_err(|err| anyhow!("Failed to generate API key: {err}"))?;
+    let key = bytes.iter().fold(String::from(KEY_TAG), |mut out, byte| {
+        let _ = write!(out, "{byte:02x}");
+        out
+    });
+    Ok(GeneratedApiKey {
+        prefix: key[..PREFIX_LEN].to_string(),
+        hash: hash_api_key(&key),
+        key,
+    })
+}
+
+#[must_use]
+pub fn api_key_prefix(key: &str) -> Option<&str> {
+    key.starts_with(KEY_TAG)
+        .then(|| key.get(..PREFIX_LEN))
+        .flatten()
+}
+
+#[must_use]
+pub fn hash_api_key(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.as_bytes()))
+}
+
+/// Compares `key` against a stored hash in constant time.
+#[must_use]
+pub fn verify_api_key(key: &str, hash: &str) -> bool {
+    hash_api_key(key).as_bytes().ct_eq(hash.as_bytes()).into()
+}
+
+/// Whether any of the granted scope names allows `required`.
+#[must_use]
+pub fn scopes_grant(scopes: &[String], required: ApiScope) -> bool {
+    scopes.iter().any(|scope| {
+        scope
+            .parse::<ApiScope>()
+            .is_ok_and(|scope| scope.grants(required))
+    })
+}
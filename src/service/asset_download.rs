@@ -1,33 +1,104 @@
 use crate::{
-    AppResult,
+    AppError, AppResult,
     database::{
         Database,
-        row::{BundleRow, FileRow, VersionRow},
+        file::FileLock,
+        row::{BundleRow, DownloadJob, FileRow, VersionRow},
+    },
+    external::{
+        ak_api::AkApi,
+        notification::NotificationClient,
+        storage::{Storage, object_path},
     },
-    external::{ak_api::AkApi, notification::NotificationClient, s3::S3Storage},
     service::types::{ABInfo, HotUpdateList},
 };
 use anyhow::Context;
 use futures::{StreamExt, TryStreamExt, stream};
 use itertools::Itertools;
-use sha256::digest;
-use std::io::{Cursor, Read};
-use tracing::{debug, error, info, instrument};
+use md5::Md5;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    io::{self, BufReader, Read, Seek, Write},
+    time::Duration,
+};
+use tempfile::NamedTempFile;
+use tokio::{task::JoinHandle, time::sleep};
+use tracing::{debug, error, info, instrument, warn};
 use zip::ZipArchive;
 
+const MAX_BUNDLE_ATTEMPTS: i32 = 5;
+const BUNDLE_RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+const BUNDLE_RETRY_MAX_DELAY: Duration = Duration::from_mins(2);
+/// A claimed job is handed to another worker when its lease runs out, so
+/// heartbeats renew it well before that.
+const JOB_LEASE: Duration = Duration::from_mins(2);
+const JOB_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+const QUEUE_WAIT_INTERVAL: Duration = Duration::from_secs(1);
+
+fn retry_delay(attempts: i32) -> Duration {
+    let exponent = u32::try_from(attempts - 1).unwrap_or(0).min(16);
+    BUNDLE_RETRY_BASE_DELAY
+        .saturating_mul(2_u32.pow(exponent))
+        .min(BUNDLE_RETRY_MAX_DELAY)
+}
+
 #[derive(Clone)]
 pub struct AssetDownloadService {
     pub database: Database,
-    pub ak_api: AkApi,
+    /// One client per tracked channel, used to fetch bundles of versions
+    /// belonging to that channel.
+    pub ak_apis: Vec<AkApi>,
     pub notification: NotificationClient,
-    pub storage: S3Storage,
+    pub storage: Storage,
+    /// Names of the replicas every newly stored file is queued for.
+    pub replicas: Vec<String>,
     pub concurrent: usize,
+    /// Holder name written on claimed jobs, unique among workers sharing the
+    /// database.
+    pub worker_id: String,
+}
+
+struct QueuedVersion {
+    version: VersionRow,
+    ak_api: AkApi,
+    bundles: HashMap<String, ABInfo>,
+}
+
+/// Renews the leases of a batch of jobs until dropped.
+struct Heartbeat {
+    handle: JoinHandle<()>,
+}
+
+impl Heartbeat {
+    fn start(database: Database, worker_id: String, ids: Vec<i64>) -> Self {
+        let handle = tokio::spawn(async move {
+            loop {
+                sleep(JOB_HEARTBEAT_INTERVAL).await;
+                if let Err(err) = database
+                    .heartbeat_download_jobs(&ids, &worker_id, JOB_LEASE.as_secs_f64())
+                    .await
+                {
+                    warn!("failed to renew download job leases: {err:?}");
+                }
+            }
+        });
+        Self { handle }
+    }
+}
+
+impl Drop for Heartbeat {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
 }
 
 impl AssetDownloadService {
+    /// Queues the next pending version once the job queue has drained, then
+    /// runs one batch of jobs. Returns whether download work is left.
     #[instrument(name = "service.asset_download", skip(self))]
     pub async fn perform_download(&self) -> AppResult<bool> {
-        match self.sync_oldest_version().await {
+        match self.process_queue().await {
             Ok(has_more) => Ok(has_more),
             Err(err) => {
                 error!("download failed: {err:?}");
@@ -36,68 +107,303 @@ impl AssetDownloadService {
         }
     }
 
+    /// Queues every bundle of `version_id` again, retrying its failed bundles,
+    /// and works the queue until the version is settled. Without a version,
+    /// runs a single round of the queue.
     pub async fn manual_download(&self, version_id: Option<i32>) -> AppResult<()> {
         if let Some(id) = version_id {
             self.sync_specific_version(id).await
         } else {
-            self.sync_oldest_version().await?;
+            self.process_queue().await?;
             Ok(())
         }
     }
 
-    async fn sync_oldest_version(&self) -> AppResult<bool> {
-        let version = self.database.get_oldest_unready_version().await?;
-        if let Some(version) = version {
-            info!("start sync {}-{}", version.res, version.client);
-            self.sync_version(&version).await?;
-            return Ok(true);
+    /// Channels this worker has an AK API client for, and so can download.
+    fn channels(&self) -> Vec<String> {
+        self.ak_apis
+            .iter()
+            .map(|ak_api| ak_api.channel().to_string())
+            .collect_vec()
+    }
+
+    async fn process_queue(&self) -> AppResult<bool> {
+        let planned = self.database.plan_download_jobs(&self.channels()).await?;
+        if let Some(version_id) = planned {
+            self.finish_version(version_id).await?;
+        }
+
+        let processed = self.process_batch().await?;
+        let active = self.database.has_active_download_jobs().await?;
+        if processed == 0 && active {
+            // Remaining jobs are backing off or leased by other workers.
+            sleep(QUEUE_WAIT_INTERVAL).await;
         }
-        info!("no pending version to sync");
-        Ok(false)
+        // Go round again after any progress: the next round settles the
+        // version whose last jobs just finished and plans the one behind it.
+        Ok(active || processed > 0 || planned.is_some())
     }
 
     async fn sync_specific_version(&self, version_id: i32) -> AppResult<()> {
+        self.database.clear_bundle_failures(version_id).await?;
+        if !self.database.requeue_version_download(version_id).await? {
+            return Err(anyhow::anyhow!("Version not found: {version_id}").into());
+        }
+        info!("queued download of version {version_id}");
+
+        while self
+            .database
+            .is_version_download_pending(version_id)
+            .await?
+        {
+            if self.process_batch().await? == 0 {
+                sleep(QUEUE_WAIT_INTERVAL).await;
+            }
+            self.finish_version(version_id).await?;
+        }
+        Ok(())
+    }
+
+    /// Claims up to `concurrent` jobs and runs them while a background task
+    /// keeps their leases alive. Returns the number of jobs claimed.
+    async fn process_batch(&self) -> AppResult<usize> {
+        let limit = i64::try_from(self.concurrent).unwrap_or(i64::MAX);
+        let jobs = self
+            .database
+            .claim_download_jobs(
+                &self.worker_id,
+                &self.channels(),
+                limit,
+                JOB_LEASE.as_secs_f64(),
+            )
+            .await?;
+        if jobs.is_empty() {
+            return Ok(0);
+        }
+
+        let heartbeat = Heartbeat::start(
+            self.database.clone(),
+            self.worker_id.clone(),
+            jobs.iter().map(|job| job.id).collect(),
+        );
+        let mut versions = HashMap::new();
+        for job in &jobs {
+            if let Entry::Vacant(entry) = versions.entry(job.version_id) {
+                entry.insert(self.load_version(job.version_id).await?);
+            }
+        }
+
+        let claimed = jobs.len();
+        stream::iter(jobs)
+            .map(|job| {
+                let queued = &versions[&job.version_id];
+                self.run_job(job, queued)
+            })
+            .buffer_unordered(self.concurrent)
+            .try_collect::<Vec<_>>()
+            .await?;
+        drop(heartbeat);
+
+        for version_id in versions.keys() {
+            self.finish_version(*version_id).await?;
+        }
+        Ok(claimed)
+    }
+
+    async fn load_version(&self, version_id: i32) -> AppResult<QueuedVersion> {
         let version = self
             .database
             .get_version_by_id(version_id)
             .await?
             .ok_or_else(|| anyhow::anyhow!("Version not found: {version_id}"))?;
-
-        info!(
-            "start sync specific version {}-{}",
-            version.res, version.client
-        );
-        self.sync_version(&version).await
+        let ak_api = self
+            .ak_apis
+            .iter()
+            .find(|ak_api| ak_api.channel() == version.channel)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Channel not configured: {}", version.channel))?;
+        let bundles = HotUpdateList::new(&version.hot_update_list)?
+            .ab_infos()
+            .iter()
+            .map(|info| (info.name.clone(), info.clone()))
+            .collect();
+        Ok(QueuedVersion {
+            version,
+            ak_api,
+            bundles,
+        })
     }
 
-    async fn sync_version(&self, version: &VersionRow) -> AppResult<()> {
-        let version_id = version
-            .id
-            .ok_or_else(|| anyhow::anyhow!("Version ID is missing"))?;
-        let hot_update_list = HotUpdateList::new(&version.hot_update_list)?;
+    /// Downloads the bundle of one job. A failed attempt puts the job back
+    /// with exponential backoff until it exhausts its attempts, so the rest
+    /// of the version can still complete.
+    async fn run_job(&self, job: DownloadJob, queued: &QueuedVersion) -> AppResult<()> {
+        let Some(info) = queued.bundles.get(&job.path) else {
+            error!("{} is not in the hot update list, skip", job.path);
+            return self.settle_job(&job, None).await;
+        };
 
-        stream::iter(hot_update_list.ab_infos())
-            .map(Ok)
-            .try_for_each_concurrent(self.concurrent, |info| {
-                self.skip_or_download(info.clone(), version_id, version.res.as_str())
-            })
+        let attempts = self
+            .database
+            .get_bundle_download_attempts(job.version_id, &job.path)
             .await?;
+        if attempts >= MAX_BUNDLE_ATTEMPTS {
+            warn!("{} has failed {attempts} times, skip", job.path);
+            return self.settle_job(&job, None).await;
+        }
+
+        match self
+            .skip_or_download(
+                info.clone(),
+                job.version_id,
+                &queued.ak_api,
+                &queued.version.res,
+            )
+            .await
+        {
+            Ok(transfer) => {
+                if attempts > 0 {
+                    self.database
+                        .clear_bundle_failure(job.version_id, &job.path)
+                        .await?;
+                }
+                self.settle_job(&job, Some(transfer)).await
+            }
+            Err(err) => {
+                let attempts = self
+                    .database
+                    .record_bundle_failure(job.version_id, &job.path, &err.to_string())
+                    .await?;
+                if attempts >= MAX_BUNDLE_ATTEMPTS {
+                    error!("Giving up on {} after {attempts} attempts: {err}", job.path);
+                    return self.settle_job(&job, None).await;
+                }
+
+                let delay = retry_delay(attempts);
+                warn!(
+                    "Download of {} failed (attempt {attempts}/{MAX_BUNDLE_ATTEMPTS}), retrying after {delay:?}: {err}",
+                    job.path
+                );
+                self.database
+                    .retry_download_job(job.id, &self.worker_id, delay.as_secs_f64())
+                    .await
+            }
+        }
+    }
+
+    /// Finishes a job and counts it towards the version progress, unless its
+    /// lease was lost to another worker; `None` marks a failed bundle.
+    async fn settle_job(
+        &self,
+        job: &DownloadJob,
+        transfer: Option<BundleTransfer>,
+    ) -> AppResult<()> {
+        if self
+            .database
+            .finish_download_job(job.id, &self.worker_id, transfer.is_none())
+            .await?
+        {
+            self.record_progress(job.version_id, transfer).await?;
+        } else {
+            warn!("lost the lease of {} to another worker", job.path);
+        }
+        Ok(())
+    }
+
+    /// Settles a version whose jobs are all finished. Only the worker that
+    /// settles it sends the notification.
+    async fn finish_version(&self, version_id: i32) -> AppResult<()> {
+        let Some(ready) = self.database.finish_version_download(version_id).await? else {
+            return Ok(());
+        };
+        self.database.finish_download_progress(version_id).await?;
+        let version = self
+            .database
+            .get_version_by_id(version_id)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Version not found: {version_id}"))?;
 
-        self.database.mark_version_ready(version_id).await?;
+        if !ready {
+            warn!("sync version {} finished with failed bundles", version.res);
+            return Ok(());
+        }
         info!("sync version {} finished", version.res);
 
+        let pack_changes = match self.pack_changes(&version, version_id).await {
+            Ok(changes) => changes,
+            Err(err) => {
+                warn!("failed to summarize pack changes of {}: {err}", version.res);
+                Vec::new()
+            }
+        };
         self.notification
-            .notify_download_finished(version.client.as_str(), version.res.as_str())
+            .notify_download_finished(
+                version.channel.as_str(),
+                version.client.as_str(),
+                version.res.as_str(),
+                &pack_changes,
+            )
             .await;
         Ok(())
     }
 
+    /// Summarizes the diff against the previous ready version of the channel,
+    /// one line per pack.
+    async fn pack_changes(&self, version: &VersionRow, version_id: i32) -> AppResult<Vec<String>> {
+        let Some(previous_id) = self
+            .database
+            .get_previous_ready_version_id(&version.channel, version_id)
+            .await?
+        else {
+            return Ok(Vec::new());
+        };
+        let diff = self
+            .database
+            .query_bundle_diff(previous_id, version_id)
+            .await?;
+        Ok(diff
+            .packs
+            .iter()
+            .map(|pack| {
+                format!(
+                    "{}: {} added, {} removed, {} changed ({:+} bytes)",
+                    pack.pid.as_deref().unwrap_or("(no pack)"),
+                    pack.added,
+                    pack.removed,
+                    pack.changed,
+                    pack.size_delta
+                )
+            })
+            .collect())
+    }
+
+    /// Counts a finished bundle towards the version progress; `None` marks a
+    /// bundle that exhausted its attempts.
+    async fn record_progress(
+        &self,
+        version_id: i32,
+        transfer: Option<BundleTransfer>,
+    ) -> AppResult<()> {
+        let failed = transfer.is_none();
+        let transfer = transfer.unwrap_or_default();
+        self.database
+            .record_bundle_progress(
+                version_id,
+                failed,
+                transfer.fetched,
+                transfer.stored,
+                transfer.deduplicated,
+            )
+            .await
+    }
+
     async fn skip_or_download(
         &self,
         info: ABInfo,
         version_id: i32,
+        ak_api: &AkApi,
         res_version: &str,
-    ) -> AppResult<()> {
+    ) -> AppResult<BundleTransfer> {
         if self
             .database
             .get_bundle_by_version_and_path(version_id, &info.name)
@@ -105,27 +411,69 @@ impl AssetDownloadService {
             .is_some()
         {
             info!("{} is already downloaded, skip", info.name);
-            return Ok(());
+            return Ok(BundleTransfer::default());
         }
 
-        let file_id = self.sync_file(res_version, &info.url()).await?;
+        // The file stays locked until the bundle references it, so garbage
+        // collection cannot remove it in between.
+        let (file_id, transfer, lock) =
+            if let Some((file_id, lock)) = self.lock_reusable_file(version_id, &info).await? {
+                info!("{} is unchanged upstream, reuse file {file_id}", info.name);
+                let size =
+                    i64::try_from(info.total_size).context("Failed to convert file size to i64")?;
+                let transfer = BundleTransfer {
+                    deduplicated: size,
+                    ..Default::default()
+                };
+                (file_id, transfer, lock)
+            } else {
+                self.sync_file(ak_api, res_version, &info).await?
+            };
         let bundle_path = info.name.clone();
         let bundle = BundleRow {
             id: None,
             path: bundle_path.clone(),
             version_id,
             file_id,
+            upstream_hash: Some(info.hash),
+            upstream_md5: Some(info.md5),
         };
 
         self.database.create_bundle(bundle).await?;
+        lock.release().await?;
         info!("{} sync finished", bundle_path);
-        Ok(())
+        Ok(transfer)
+    }
+
+    /// Locks the file an unchanged bundle of an earlier version points at,
+    /// unless garbage collection removed it before the lock was taken.
+    async fn lock_reusable_file(
+        &self,
+        version_id: i32,
+        info: &ABInfo,
+    ) -> AppResult<Option<(i32, FileLock)>> {
+        let Some(file) = self
+            .database
+            .get_reusable_file(version_id, &info.name, &info.hash, &info.md5)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let lock = self.database.lock_file(&file.hash).await?;
+        let Some(FileRow { id: Some(id), .. }) = self.database.get_file_by_hash(&file.hash).await?
+        else {
+            lock.release().await?;
+            return Ok(None);
+        };
+        Ok(Some((id, lock)))
     }
 
-    fn calc_sha256(bytes: &[u8]) -> AppResult<String> {
-        let mut zip =
-            ZipArchive::new(Cursor::new(bytes)).context("Failed to create zip archive")?;
-        let mut buffer = Vec::new();
+    /// Hashes the concatenated zip entries in name order, returning the sha256
+    /// used as storage key together with the md5 and size the hot update list
+    /// advertises for the unpacked bundle.
+    pub fn calc_digest<R: Read + Seek>(reader: R) -> AppResult<BundleDigest> {
+        let mut zip = ZipArchive::new(reader).context("Failed to create zip archive")?;
+        let mut hasher = BundleHasher::default();
         let name_list = zip
             .file_names()
             .sorted()
@@ -134,36 +482,140 @@ impl AssetDownloadService {
 
         for name in name_list {
             let mut file = zip.by_name(&name).context("Failed to read zip file")?;
-            file.read_to_end(&mut buffer)
-                .context("Failed to push zip file content to buffer")?;
+            io::copy(&mut file, &mut hasher).context("Failed to hash zip file content")?;
         }
 
-        Ok(digest(&buffer))
+        Ok(BundleDigest {
+            sha256: format!("{:x}", hasher.sha256.finalize()),
+            md5: format!("{:x}", hasher.md5.finalize()),
+            size: hasher.size,
+        })
     }
 
-    async fn sync_file(&self, res_version: &str, path: &str) -> AppResult<i32> {
-        let bytes = self.ak_api.download_file(res_version, path).await?;
-        let sha = Self::calc_sha256(&bytes)?;
+    async fn download_verified(
+        &self,
+        ak_api: &AkApi,
+        res_version: &str,
+        info: &ABInfo,
+    ) -> AppResult<(NamedTempFile, BundleDigest)> {
+        let temp = NamedTempFile::new()?;
+        let mut writer = tokio::fs::File::from_std(temp.reopen()?);
+        let size = ak_api
+            .download_file(res_version, &info.url(), &mut writer)
+            .await?;
+        drop(writer);
+
+        if size != info.total_size {
+            return Err(AppError::ExternalService(anyhow::anyhow!(
+                "Size mismatch for {}: expected {} bytes, got {size}",
+                info.name,
+                info.total_size
+            )));
+        }
+
+        let reader = temp.reopen()?;
+        let digest = tokio::task::spawn_blocking(move || Self::calc_digest(BufReader::new(reader)))
+            .await
+            .context("Hash task panicked")??;
+
+        if !digest.md5.eq_ignore_ascii_case(&info.md5) {
+            return Err(AppError::ExternalService(anyhow::anyhow!(
+                "MD5 mismatch for {}: expected {}, got {}",
+                info.name,
+                info.md5,
+                digest.md5
+            )));
+        }
+        if digest.size != info.ab_size {
+            return Err(AppError::ExternalService(anyhow::anyhow!(
+                "Unpacked size mismatch for {}: expected {} bytes, got {}",
+                info.name,
+                info.ab_size,
+                digest.size
+            )));
+        }
+
+        Ok((temp, digest))
+    }
+
+    async fn sync_file(
+        &self,
+        ak_api: &AkApi,
+        res_version: &str,
+        info: &ABInfo,
+    ) -> AppResult<(i32, BundleTransfer, FileLock)> {
+        let (temp, digest) = self.download_verified(ak_api, res_version, info).await?;
+        let size = i64::try_from(info.total_size).context("Failed to convert file size to i64")?;
 
-        if let Some(file) = self.database.get_file_by_hash(&sha).await? {
-            debug!("file {} already exists, skip", path);
-            return file
+        // Garbage collection may be removing an unreferenced file of the same
+        // hash; wait until its object is gone before uploading ours.
+        let lock = self.database.lock_file(&digest.sha256).await?;
+        if let Some(file) = self.database.get_file_by_hash(&digest.sha256).await? {
+            debug!("file {} already exists, skip", info.name);
+            let file_id = file
                 .id
-                .ok_or_else(|| anyhow::anyhow!("File ID is missing"))
-                .map_err(Into::into);
+                .ok_or_else(|| anyhow::anyhow!("File ID is missing"))?;
+            let transfer = BundleTransfer {
+                fetched: size,
+                stored: 0,
+                deduplicated: size,
+            };
+            return Ok((file_id, transfer, lock));
         }
 
-        let storage_path = format!("/{}/{}/{}", &sha[..2], &sha[2..4], &sha[4..]);
-        self.storage.upload(&storage_path, &bytes).await?;
+        self.storage
+            .upload_file(&object_path(&digest.sha256), temp.path())
+            .await?;
 
         let file = FileRow {
             id: None,
-            hash: sha,
-            size: i32::try_from(bytes.len()).context("Failed to convert file size to i32")?,
+            hash: digest.sha256,
+            size,
         };
 
-        let file_id = self.database.create_file(file).await?;
-        debug!("sync file {} finished", path);
-        Ok(file_id)
+        let file_id = self
+            .database
+            .create_file_with_replicas(file, &self.replicas)
+            .await?;
+        debug!("sync file {} finished", info.name);
+        let transfer = BundleTransfer {
+            fetched: size,
+            stored: size,
+            deduplicated: 0,
+        };
+        Ok((file_id, transfer, lock))
     }
 }
+
+#[derive(Debug, Default, Clone, Copy)]
+struct BundleTransfer {
+    fetched: i64,
+    stored: i64,
+    deduplicated: i64,
+}
+
+#[derive(Default)]
+struct BundleHasher {
+    sha256: Sha256,
+    md5: Md5,
+    size: u64,
+}
+
+impl Write for BundleHasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.sha256.update(buf);
+        self.md5.update(buf);
+        self.size += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+pub struct BundleDigest {
+    pub sha256: String,
+    pub md5: String,
+    pub size: u64,
+}
@@ -2,7 +2,7 @@ use crate::{
     AppResult,
     database::{
         Database,
-        row::{AssetMappingRow, AssetMappingStatus, NodeType},
+        row::{AssetMappingRow, AssetMappingStatus, BundleDependencyRow, NodeType},
     },
 };
 use anyhow::{Context, anyhow};
@@ -19,6 +19,8 @@ const MANIFEST_NAME: &str = "resource_manifest_idx.json";
 #[derive(Debug, Clone)]
 pub struct AssetMappingImportService {
     pub database: Database,
+    /// Manifests are exported for the primary channel only.
+    pub channel: String,
     pub gamedata_root: PathBuf,
 }
 
@@ -36,7 +38,7 @@ impl AssetMappingImportService {
 
         let version = self
             .database
-            .get_version_by_res(res_version)
+            .get_version_by_res(&self.channel, res_version)
             .await?
             .ok_or_else(|| anyhow!("Version not found for res {res_version}"))?;
 
@@ -49,7 +51,7 @@ impl AssetMappingImportService {
         }
 
         let manifest_path = version_dir.join(MANIFEST_NAME);
-        let mappings = parse_manifest(&manifest_path, res_version)?;
+        let (mappings, dependencies) = parse_manifest(&manifest_path, res_version)?;
 
         let version_id = version
             .id
@@ -65,7 +67,7 @@ impl AssetMappingImportService {
 
         if !self
             .database
-            .import_asset_mappings(version_id, &mappings)
+            .import_asset_mappings(version_id, &mappings, &dependencies)
             .await?
         {
             return Err(anyhow!("Asset mapping import already running for {res_version}").into());
@@ -84,8 +86,12 @@ struct ManifestFile {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct ManifestBundle {
     name: String,
+    /// Indices into `bundles` of the bundles this one loads directly.
+    #[serde(default)]
+    direct_dependencies: Vec<usize>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -123,7 +129,10 @@ fn new_asset_mapping(
     }
 }
 
-fn parse_manifest(path: &Path, res_version: &str) -> AppResult<Vec<AssetMappingRow>> {
+fn parse_manifest(
+    path: &Path,
+    res_version: &str,
+) -> AppResult<(Vec<AssetMappingRow>, Vec<BundleDependencyRow>)> {
     let content = fs::read_to_string(path)
         .with_context(|| format!("Failed to read manifest: {}", path.display()))?;
     let manifest: ManifestFile = serde_json::from_str(&content)
@@ -184,5 +193,21 @@ fn parse_manifest(path: &Path, res_version: &str) -> AppResult<Vec<AssetMappingR
         ));
     }
 
-    Ok(mappings)
+    let mut dependencies = Vec::new();
+    for bundle in &bundles {
+        for &index in &bundle.direct_dependencies {
+            let dependency = bundles.get(index).ok_or_else(|| {
+                anyhow!(
+                    "Invalid dependency index {index} of {} for {res_version}",
+                    bundle.name
+                )
+            })?;
+            dependencies.push(BundleDependencyRow {
+                bundle_path: bundle.name.clone(),
+                dependency_path: dependency.name.clone(),
+            });
+        }
+    }
+
+    Ok((mappings, dependencies))
 }
@@ -0,0 +1,85 @@
+use crate::{
+    AppResult,
+    database::{Database, row::FileRow},
+    external::storage::{Replica, Storage, object_path},
+};
+use futures::{StreamExt, TryStreamExt, stream};
+use serde::Serialize;
+use tracing::{info, instrument, warn};
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GcReport {
+    pub dry_run: bool,
+    pub files: usize,
+    pub bytes: i64,
+    /// Hashes that were picked up by a bundle again before they could be removed.
+    pub skipped: Vec<String>,
+}
+
+#[derive(Clone)]
+pub struct GarbageCollectionService {
+    pub database: Database,
+    pub storage: Storage,
+    pub replicas: Vec<Replica>,
+    pub concurrent: usize,
+}
+
+impl GarbageCollectionService {
+    /// Deletes `files` rows and their objects once no bundle references them.
+    /// With `dry_run` only the reclaimable files and bytes are reported.
+    #[instrument(name = "service.gc", skip(self))]
+    pub async fn collect(&self, dry_run: bool) -> AppResult<GcReport> {
+        let files = self.database.query_unreferenced_files().await?;
+        info!("found {} unreferenced files", files.len());
+
+        let mut report = GcReport {
+            dry_run,
+            ..Default::default()
+        };
+        if dry_run {
+            report.files = files.len();
+            report.bytes = files.iter().map(|file| file.size).sum();
+            return Ok(report);
+        }
+
+        let results = stream::iter(files)
+            .map(|file| self.remove_file(file))
+            .buffer_unordered(self.concurrent)
+            .try_collect::<Vec<_>>()
+            .await?;
+        for (file, removed) in results {
+            if removed {
+                report.files += 1;
+                report.bytes += file.size;
+            } else {
+                report.skipped.push(file.hash);
+            }
+        }
+        Ok(report)
+    }
+
+    /// Drops the row first so a failed object deletion only leaves an orphan
+    /// object behind, which the storage audit reports. The file lock keeps a
+    /// concurrent sync from storing the same hash again until the object is
+    /// gone.
+    async fn remove_file(&self, file: FileRow) -> AppResult<(FileRow, bool)> {
+        let Some(id) = file.id else {
+            return Ok((file, false));
+        };
+        let lock = self.database.lock_file(&file.hash).await?;
+        if !self.database.delete_unreferenced_file(id).await? {
+            warn!("file {} is referenced again, skipping", file.hash);
+            lock.release().await?;
+            return Ok((file, false));
+        }
+        let path = object_path(&file.hash);
+        self.storage.delete(&path).await?;
+        for replica in &self.replicas {
+            replica.storage.delete(&path).await?;
+        }
+        lock.release().await?;
+        info!("removed file {} ({} bytes)", file.hash, file.size);
+        Ok((file, true))
+    }
+}
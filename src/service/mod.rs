@@ -1,5 +1,9 @@
+pub mod api_key;
 pub mod asset_download;
 pub mod asset_mapping_import;
+pub mod garbage_collection;
 pub mod item_demand_import;
+pub mod replication;
+pub mod storage_audit;
 pub mod types;
 pub mod version_check;
@@ -0,0 +1,174 @@
+use crate::{
+    AppResult,
+    database::{Database, row::ReplicationTask},
+    external::storage::{Replica, Storage, object_path},
+};
+use futures::{StreamExt, stream};
+use serde::Serialize;
+use std::time::Duration;
+use tracing::{info, instrument, warn};
+
+const CLAIM_BATCH_SIZE: i64 = 64;
+const CLAIM_LEASE: Duration = Duration::from_mins(10);
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(30);
+const RETRY_MAX_DELAY: Duration = Duration::from_hours(1);
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackfillReport {
+    pub replica: String,
+    pub checked: usize,
+    pub copied: usize,
+    pub failed: Vec<String>,
+}
+
+#[derive(Clone)]
+pub struct ReplicationService {
+    pub database: Database,
+    pub primary: Storage,
+    pub replicas: Vec<Replica>,
+    pub concurrent: usize,
+}
+
+fn retry_delay(attempts: i32) -> Duration {
+    let exponent = u32::try_from(attempts).unwrap_or(0).min(16);
+    RETRY_BASE_DELAY
+        .saturating_mul(2_u32.saturating_pow(exponent))
+        .min(RETRY_MAX_DELAY)
+}
+
+impl ReplicationService {
+    fn replica_names(&self) -> Vec<String> {
+        self.replicas
+            .iter()
+            .map(|replica| replica.name.clone())
+            .collect()
+    }
+
+    /// Copies one batch of queued objects to their replicas. Returns how many
+    /// tasks were claimed, so callers can keep draining until it reaches zero.
+    #[instrument(name = "service.replication.process_queue", skip(self))]
+    pub async fn process_queue(&self) -> AppResult<usize> {
+        let tasks = self
+            .database
+            .claim_replication_tasks(
+                &self.replica_names(),
+                CLAIM_BATCH_SIZE,
+                CLAIM_LEASE.as_secs_f64(),
+            )
+            .await?;
+        let claimed = tasks.len();
+
+        let results = stream::iter(tasks)
+            .map(|task| self.process_task(task))
+            .buffer_unordered(self.concurrent)
+            .collect::<Vec<_>>()
+            .await;
+        for result in results {
+            result?;
+        }
+        Ok(claimed)
+    }
+
+    async fn process_task(&self, task: ReplicationTask) -> AppResult<()> {
+        let Some(replica) = self.replicas.iter().find(|r| r.name == task.replica) else {
+            return Ok(());
+        };
+        match self.primary.copy_to(&task.hash, &replica.storage).await {
+            Ok(()) => {
+                self.database.complete_replication_task(task.id).await?;
+                info!("replicated {} to {}", task.hash, replica.name);
+            }
+            Err(err) => {
+                let delay = retry_delay(task.attempts);
+                warn!(
+                    "failed to replicate {} to {} (attempt {}), retrying in {}s: {err}",
+                    task.hash,
+                    replica.name,
+                    task.attempts + 1,
+                    delay.as_secs()
+                );
+                self.database
+                    .fail_replication_task(task.id, &err.to_string(), delay.as_secs_f64())
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Logs the queue depth and lag of every replica.
+    pub async fn log_lag(&self) -> AppResult<()> {
+        for status in self
+            .database
+            .query_replication_status(&self.replica_names())
+            .await?
+        {
+            if status.failing > 0 {
+                warn!(
+                    "replica {}: {} pending ({} failing), lag {}s, last error: {}",
+                    status.replica,
+                    status.pending,
+                    status.failing,
+                    status.lag_seconds,
+                    status.last_error.unwrap_or_default()
+                );
+            } else if status.pending > 0 {
+                info!(
+                    "replica {}: {} pending, lag {}s",
+                    status.replica, status.pending, status.lag_seconds
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Walks `files` and copies every object the replica is missing or holds
+    /// with a different size. Queued copies of files found on the replica are
+    /// dropped.
+    #[instrument(name = "service.replication.backfill", skip(self, replica), fields(replica = %replica.name))]
+    pub async fn backfill(&self, replica: &Replica) -> AppResult<BackfillReport> {
+        let files = self.database.list_files().await?;
+        info!("checking {} files on replica {}", files.len(), replica.name);
+
+        let results = stream::iter(files)
+            .map(|file| async move {
+                let path = object_path(&file.hash);
+                let present = replica
+                    .storage
+                    .head(&path)
+                    .await?
+                    .is_some_and(|meta| i64::try_from(meta.size).ok() == Some(file.size));
+                let copied = if present {
+                    None
+                } else {
+                    Some(self.primary.copy_to(&file.hash, &replica.storage).await)
+                };
+                if let (Some(id), None | Some(Ok(()))) = (file.id, &copied) {
+                    self.database
+                        .clear_replication_task(&replica.name, id)
+                        .await?;
+                }
+                AppResult::Ok((file.hash, copied))
+            })
+            .buffer_unordered(self.concurrent)
+            .collect::<Vec<_>>()
+            .await;
+
+        let mut report = BackfillReport {
+            replica: replica.name.clone(),
+            checked: results.len(),
+            ..Default::default()
+        };
+        for result in results {
+            match result? {
+                (_, None) => {}
+                (_, Some(Ok(()))) => report.copied += 1,
+                (hash, Some(Err(err))) => {
+                    warn!("failed to copy {hash} to {}: {err}", replica.name);
+                    report.failed.push(hash);
+                }
+            }
+        }
+        Ok(report)
+    }
+}
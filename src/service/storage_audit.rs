@@ -0,0 +1,234 @@
+use crate::{
+    AppResult,
+    database::Database,
+    external::storage::{Storage, object_path},
+    service::asset_download::AssetDownloadService,
+};
+use anyhow::Context;
+use futures::{StreamExt, stream};
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    io::BufReader,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tempfile::NamedTempFile;
+use tracing::{info, instrument};
+
+#[derive(Debug, Clone, Copy)]
+pub enum RehashMode {
+    None,
+    Sample(usize),
+    All,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SizeMismatch {
+    pub hash: String,
+    pub expected: i64,
+    pub actual: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashMismatch {
+    pub hash: String,
+    pub actual: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditReport {
+    pub checked_files: usize,
+    pub checked_objects: usize,
+    pub rehashed_objects: usize,
+    /// File hashes whose object is missing from the bucket.
+    pub missing_objects: Vec<String>,
+    /// Object paths without a matching `files` row.
+    pub orphan_objects: Vec<String>,
+    pub size_mismatches: Vec<SizeMismatch>,
+    pub hash_mismatches: Vec<HashMismatch>,
+}
+
+impl AuditReport {
+    #[must_use]
+    pub const fn is_consistent(&self) -> bool {
+        self.missing_objects.is_empty()
+            && self.orphan_objects.is_empty()
+            && self.size_mismatches.is_empty()
+            && self.hash_mismatches.is_empty()
+    }
+}
+
+#[derive(Clone)]
+pub struct StorageAuditService {
+    pub database: Database,
+    pub storage: Storage,
+    pub concurrent: usize,
+}
+
+/// Maps an `ab/cd/rest` object location back to the file hash it stores.
+fn hash_from_location(location: &str) -> Option<String> {
+    let mut parts = location.split('/');
+    let hash = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(a), Some(b), Some(rest), None) if a.len() == 2 && b.len() == 2 => {
+            format!("{a}{b}{rest}")
+        }
+        _ => return None,
+    };
+    (hash.len() == 64 && hash.bytes().all(|byte| byte.is_ascii_hexdigit())).then_some(hash)
+}
+
+/// Picks `count` evenly spaced hashes starting from a time based offset, so
+/// repeated scheduled runs cover different objects.
+fn sample(hashes: Vec<String>, count: usize) -> Vec<String> {
+    if count == 0 {
+        return Vec::new();
+    }
+    if count >= hashes.len() {
+        return hashes;
+    }
+    let step = hashes.len() / count;
+    let offset = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+        % step as u64;
+    hashes
+        .into_iter()
+        .skip(usize::try_from(offset).unwrap_or(0))
+        .step_by(step)
+        .take(count)
+        .collect()
+}
+
+impl StorageAuditService {
+    #[instrument(name = "service.storage_audit", skip(self))]
+    pub async fn audit(&self, rehash: RehashMode) -> AppResult<AuditReport> {
+        let files = self.database.list_files().await?;
+        let objects = self.storage.list().await?;
+        info!(
+            "auditing {} files against {} objects",
+            files.len(),
+            objects.len()
+        );
+
+        let mut report = AuditReport {
+            checked_files: files.len(),
+            checked_objects: objects.len(),
+            ..Default::default()
+        };
+
+        let mut object_sizes = HashMap::new();
+        for object in objects {
+            let location = object.location.to_string();
+            match hash_from_location(&location) {
+                Some(hash) => {
+                    object_sizes.insert(hash, object.size);
+                }
+                None => report.orphan_objects.push(location),
+            }
+        }
+
+        let mut present = Vec::new();
+        let mut missing = Vec::new();
+        for file in files {
+            match object_sizes.remove(&file.hash) {
+                None => missing.push(file.hash),
+                Some(size) if u64::try_from(file.size).ok() != Some(size) => {
+                    report.size_mismatches.push(SizeMismatch {
+                        hash: file.hash,
+                        expected: file.size,
+                        actual: size,
+                    });
+                }
+                Some(_) => present.push(file.hash),
+            }
+        }
+        // The two listings are not one snapshot: a concurrent sync uploads an
+        // object before committing its row, and garbage collection deletes a
+        // row before its object. Both hold the file lock while doing so.
+        for hash in missing {
+            if self.is_missing(&hash).await? {
+                report.missing_objects.push(hash);
+            }
+        }
+        for hash in object_sizes.into_keys() {
+            if self.is_orphan(&hash).await? {
+                report
+                    .orphan_objects
+                    .push(object_path(&hash).trim_start_matches('/').to_string());
+            }
+        }
+        report.orphan_objects.sort();
+
+        let targets = match rehash {
+            RehashMode::None => Vec::new(),
+            RehashMode::Sample(count) => sample(present, count),
+            RehashMode::All => present,
+        };
+        report.rehashed_objects = targets.len();
+        report.hash_mismatches = stream::iter(targets)
+            .map(|hash| self.rehash(hash))
+            .buffer_unordered(self.concurrent)
+            .filter_map(|mismatch| async move { mismatch })
+            .collect()
+            .await;
+
+        Ok(report)
+    }
+
+    /// Whether the `files` row of `hash` still lacks its object once no sync
+    /// or garbage collection of the hash is in flight.
+    async fn is_missing(&self, hash: &str) -> AppResult<bool> {
+        let lock = self.database.lock_file(hash).await?;
+        let missing = self.database.get_file_by_hash(hash).await?.is_some()
+            && self.storage.head(&object_path(hash)).await?.is_none();
+        lock.release().await?;
+        Ok(missing)
+    }
+
+    /// Whether the object of `hash` still lacks its `files` row once no sync
+    /// or garbage collection of the hash is in flight.
+    async fn is_orphan(&self, hash: &str) -> AppResult<bool> {
+        let lock = self.database.lock_file(hash).await?;
+        let orphan = self.database.get_file_by_hash(hash).await?.is_none()
+            && self.storage.head(&object_path(hash)).await?.is_some();
+        lock.release().await?;
+        Ok(orphan)
+    }
+
+    async fn rehash(&self, hash: String) -> Option<HashMismatch> {
+        match self.calc_object_sha256(&hash).await {
+            Ok(actual) if actual == hash => None,
+            Ok(actual) => Some(HashMismatch {
+                hash,
+                actual: Some(actual),
+                error: None,
+            }),
+            Err(err) => Some(HashMismatch {
+                hash,
+                actual: None,
+                error: Some(err.to_string()),
+            }),
+        }
+    }
+
+    async fn calc_object_sha256(&self, hash: &str) -> AppResult<String> {
+        let temp = NamedTempFile::new()?;
+        let mut writer = tokio::fs::File::from_std(temp.reopen()?);
+        self.storage
+            .download(&object_path(hash), &mut writer)
+            .await?;
+        drop(writer);
+
+        let reader = temp.reopen()?;
+        let digest = tokio::task::spawn_blocking(move || {
+            AssetDownloadService::calc_digest(BufReader::new(reader))
+        })
+        .await
+        .context("Hash task panicked")??;
+        Ok(digest.sha256)
+    }
+}
@@ -21,15 +21,20 @@ pub struct ABInfo {
     pub total_size: u64,
 }
 
+#[must_use]
+pub fn dat_file_name(bundle_path: &str) -> String {
+    let path = bundle_path.replace('/', "_").replace('#', "__");
+    if let Some((left, _)) = path.rsplit_once('.') {
+        format!("{left}.dat")
+    } else {
+        path
+    }
+}
+
 impl ABInfo {
     #[must_use]
     pub fn url(&self) -> String {
-        let path = self.name.replace('/', "_").replace('#', "__");
-        if let Some((left, _)) = path.rsplit_once('.') {
-            format!("{left}.dat")
-        } else {
-            path
-        }
+        dat_file_name(&self.name)
     }
 }
 
@@ -14,7 +14,9 @@ use tracing::{error, info, instrument};
 #[derive(Clone)]
 pub struct VersionCheckService {
     pub database: Database,
-    pub ak_api: AkApi,
+    /// One client per tracked channel; the first one is the primary channel
+    /// that drives the Docker and GitHub integrations.
+    pub ak_apis: Vec<AkApi>,
     pub notification: NotificationClient,
     pub docker: Option<DockerClient>,
     pub github: Option<GithubClient>,
@@ -33,19 +35,54 @@ impl VersionCheckService {
     }
 
     async fn inner_perform(&self) -> AppResult<bool> {
-        let remote = self.ak_api.get_version().await?;
+        let mut has_update = false;
+        let mut last_error = None;
+        for ak_api in &self.ak_apis {
+            match self.check_channel(ak_api).await {
+                Ok(updated) => has_update |= updated,
+                Err(err) => {
+                    error!("check channel {} failed: {err:?}", ak_api.channel());
+                    last_error = Some(err);
+                }
+            }
+        }
+
+        match last_error {
+            Some(err) if !has_update => Err(err),
+            _ => Ok(has_update),
+        }
+    }
+
+    async fn check_channel(&self, ak_api: &AkApi) -> AppResult<bool> {
+        let remote = ak_api.get_version().await?;
         info!(
-            "remote version {} {}",
-            &remote.client_version, &remote.res_version
+            "remote version [{}] {} {}",
+            ak_api.channel(),
+            &remote.client_version,
+            &remote.res_version
         );
-        self.check_and_save(remote).await
+        self.check_and_save(ak_api, remote).await
+    }
+
+    pub fn find_channel(&self, channel: &str) -> AppResult<&AkApi> {
+        self.ak_apis
+            .iter()
+            .find(|ak_api| ak_api.channel() == channel)
+            .ok_or_else(|| anyhow::anyhow!("Unknown channel: {channel}").into())
+    }
+
+    fn is_primary(&self, ak_api: &AkApi) -> bool {
+        self.ak_apis
+            .first()
+            .is_some_and(|primary| primary.channel() == ak_api.channel())
     }
 
     #[allow(clippy::cognitive_complexity)]
-    pub async fn check_and_save(&self, remote: RemoteVersion) -> AppResult<bool> {
+    pub async fn check_and_save(&self, ak_api: &AkApi, remote: RemoteVersion) -> AppResult<bool> {
+        let channel = ak_api.channel();
         let exists = self
             .database
-            .is_client_and_res_exist(&remote.client_version, &remote.res_version)
+            .is_client_and_res_exist(channel, &remote.client_version, &remote.res_version)
             .await?;
 
         if exists {
@@ -53,10 +90,11 @@ impl VersionCheckService {
             return Ok(false);
         }
 
-        let prev = self.database.get_latest_version().await?;
+        let prev = self.database.get_latest_version(channel).await?;
         if let Some(prev) = &prev {
             self.notification
                 .notify_update(
+                    channel,
                     &prev.client,
                     &prev.res,
                     &remote.client_version,
@@ -65,11 +103,11 @@ impl VersionCheckService {
                 .await;
         } else {
             self.notification
-                .notify_update("", "", &remote.client_version, &remote.res_version)
+                .notify_update(channel, "", "", &remote.client_version, &remote.res_version)
                 .await;
         }
 
-        let hot_update_list = self.ak_api.get_hot_update_list(&remote.res_version).await?;
+        let hot_update_list = ak_api.get_hot_update_list(&remote.res_version).await?;
         HotUpdateList::new(&hot_update_list)?;
         let RemoteVersion {
             res_version,
@@ -77,6 +115,7 @@ impl VersionCheckService {
         } = &remote;
         let version = VersionRow {
             id: None,
+            channel: channel.to_string(),
             res: res_version.clone(),
             client: client_version.clone(),
             hot_update_list,
@@ -87,6 +126,10 @@ impl VersionCheckService {
         self.database.create_version(version).await?;
         info!("new version created and ready for download");
 
+        if !self.is_primary(ak_api) {
+            return Ok(true);
+        }
+
         if let Some(github) = &self.github {
             info!("Attempting to dispatch GitHub workflow for new version");
             match github.dispatch_workflow().await {
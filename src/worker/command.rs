@@ -0,0 +1,193 @@
+use crate::{
+    AppResult,
+    database::{
+        Database,
+        row::{AdminCommandKind, AdminCommandRow},
+    },
+    service::{
+        asset_mapping_import::AssetMappingImportService,
+        item_demand_import::ItemDemandImportService,
+    },
+    worker::sync::SyncWorker,
+};
+use anyhow::anyhow;
+use std::{sync::Arc, time::Duration};
+use tokio::{task::JoinHandle, time::sleep};
+use tracing::{error, info, warn};
+
+const IDLE_INTERVAL: Duration = Duration::from_secs(5);
+const ERROR_INTERVAL: Duration = Duration::from_mins(1);
+/// A running command whose lease ran out counts as abandoned by its worker,
+/// so heartbeats renew it well before that.
+const COMMAND_LEASE: Duration = Duration::from_mins(2);
+const COMMAND_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Runs the commands queued through the admin API, one at a time.
+pub struct CommandWorker {
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for CommandWorker {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+        info!("command worker stopped");
+    }
+}
+
+impl CommandWorker {
+    #[must_use]
+    pub fn new(
+        database: Database,
+        worker_id: String,
+        sync: Arc<SyncWorker>,
+        asset_mapping: AssetMappingImportService,
+        item_demand: ItemDemandImportService,
+    ) -> Self {
+        let runner = CommandRunner {
+            database,
+            worker_id,
+            sync,
+            asset_mapping,
+            item_demand,
+        };
+        Self {
+            handle: Some(tokio::spawn(run_loop(runner))),
+        }
+    }
+}
+
+struct CommandRunner {
+    database: Database,
+    worker_id: String,
+    sync: Arc<SyncWorker>,
+    asset_mapping: AssetMappingImportService,
+    item_demand: ItemDemandImportService,
+}
+
+impl CommandRunner {
+    async fn process_next(&self) -> AppResult<bool> {
+        match self
+            .database
+            .fail_orphaned_admin_commands(&self.worker_id)
+            .await?
+        {
+            0 => {}
+            count => warn!("marked {count} orphaned admin commands as failed"),
+        }
+
+        let Some(command) = self
+            .database
+            .claim_admin_command(&self.worker_id, COMMAND_LEASE.as_secs_f64())
+            .await?
+        else {
+            return Ok(false);
+        };
+
+        info!("running admin command {} ({:?})", command.id, command.kind);
+        let heartbeat =
+            LeaseHeartbeat::start(self.database.clone(), self.worker_id.clone(), command.id);
+        let result = self.execute(&command).await;
+        drop(heartbeat);
+        let error = match result {
+            Ok(()) => {
+                info!("admin command {} succeeded", command.id);
+                None
+            }
+            Err(err) => {
+                error!("admin command {} failed: {err:?}", command.id);
+                Some(err.to_string())
+            }
+        };
+        if !self
+            .database
+            .finish_admin_command(command.id, &self.worker_id, error.as_deref())
+            .await?
+        {
+            warn!(
+                "admin command {} was failed after its lease ran out",
+                command.id
+            );
+        }
+        Ok(true)
+    }
+
+    async fn execute(&self, command: &AdminCommandRow) -> AppResult<()> {
+        match command.kind {
+            AdminCommandKind::CheckVersion => self.sync.perform_poll().await,
+            AdminCommandKind::DownloadVersion => {
+                self.sync.download_version(version_id(command)?).await
+            }
+            AdminCommandKind::ImportAssetMapping => {
+                let res_version = command
+                    .res_version
+                    .as_deref()
+                    .ok_or_else(|| anyhow!("Command {} has no res version", command.id))?;
+                self.asset_mapping
+                    .import_by_res_version(res_version, false)
+                    .await
+            }
+            AdminCommandKind::ImportItemDemand => self.item_demand.import().await,
+            AdminCommandKind::ResetVersion => {
+                let id = version_id(command)?;
+                if self.database.reset_version(id).await? {
+                    Ok(())
+                } else {
+                    Err(anyhow!("Version not found: {id}").into())
+                }
+            }
+        }
+    }
+}
+
+/// Renews the lease on a running command until dropped.
+struct LeaseHeartbeat {
+    handle: JoinHandle<()>,
+}
+
+impl LeaseHeartbeat {
+    fn start(database: Database, worker_id: String, id: i64) -> Self {
+        let handle = tokio::spawn(async move {
+            loop {
+                sleep(COMMAND_HEARTBEAT_INTERVAL).await;
+                if let Err(err) = database
+                    .heartbeat_admin_command(id, &worker_id, COMMAND_LEASE.as_secs_f64())
+                    .await
+                {
+                    warn!("failed to renew admin command lease: {err:?}");
+                }
+            }
+        });
+        Self { handle }
+    }
+}
+
+impl Drop for LeaseHeartbeat {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+fn version_id(command: &AdminCommandRow) -> AppResult<i32> {
+    command
+        .version_id
+        .ok_or_else(|| anyhow!("Command {} has no version id", command.id).into())
+}
+
+async fn run_loop(runner: CommandRunner) {
+    loop {
+        let delay = match runner.process_next().await {
+            Ok(true) => Duration::ZERO,
+            Ok(false) => IDLE_INTERVAL,
+            Err(err) => {
+                error!("admin command processing failed: {err:?}");
+                ERROR_INTERVAL
+            }
+        };
+
+        if !delay.is_zero() {
+            sleep(delay).await;
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::service::item_demand_import::ItemDemandImportService;
+use crate::{
+    service::item_demand_import::ItemDemandImportService,
+    worker::status::{HeartbeatTask, StatusReporter},
+};
 use std::{
     path::{Path, PathBuf},
     time::{Duration, Instant, SystemTime},
@@ -12,6 +15,7 @@ use tracing::{debug, error, info, warn};
 const SCAN_INTERVAL: Duration = Duration::from_secs(10);
 const IMPORT_TICK_INTERVAL: Duration = Duration::from_secs(10);
 const IMPORT_DEBOUNCE: Duration = Duration::from_secs(30);
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 struct FileFingerprint {
@@ -29,6 +33,7 @@ pub struct ItemDemandWatcher {
     event_tx: Option<UnboundedSender<FileFingerprint>>,
     scan_handle: Option<JoinHandle<()>>,
     import_handle: Option<JoinHandle<()>>,
+    _heartbeat: HeartbeatTask,
     file_path: PathBuf,
 }
 
@@ -46,7 +51,11 @@ impl Drop for ItemDemandWatcher {
 }
 
 impl ItemDemandWatcher {
-    pub fn new(service: ItemDemandImportService, file_path: &Path) -> anyhow::Result<Self> {
+    pub fn new(
+        service: ItemDemandImportService,
+        file_path: &Path,
+        status: StatusReporter,
+    ) -> anyhow::Result<Self> {
         let file_path = std::fs::canonicalize(file_path).or_else(|err| {
             if err.kind() == std::io::ErrorKind::NotFound {
                 Ok(file_path.to_path_buf())
@@ -56,7 +65,8 @@ impl ItemDemandWatcher {
         })?;
         let (event_tx, event_rx) = unbounded_channel();
         let scan_handle = Some(spawn_scan_loop(event_tx.clone(), file_path.clone()));
-        let import_handle = Some(spawn_import_loop(event_rx, service));
+        let heartbeat = status.start_heartbeat();
+        let import_handle = Some(spawn_import_loop(event_rx, service, status));
 
         info!("polling item demand file: {}", file_path.display());
 
@@ -64,6 +74,7 @@ impl ItemDemandWatcher {
             event_tx: Some(event_tx),
             scan_handle,
             import_handle,
+            _heartbeat: heartbeat,
             file_path,
         })
     }
@@ -131,6 +142,7 @@ fn spawn_scan_loop(
 fn spawn_import_loop(
     mut event_rx: UnboundedReceiver<FileFingerprint>,
     service: ItemDemandImportService,
+    status: StatusReporter,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
         let mut pending: Option<PendingImport> = None;
@@ -162,8 +174,16 @@ fn spawn_import_loop(
                         pending_import.fingerprint
                     );
                     match service.import().await {
-                        Ok(()) => debug!("imported item demand"),
-                        Err(err) => error!("failed to import item demand: {err:?}"),
+                        Ok(()) => {
+                            debug!("imported item demand");
+                            status.succeeded("imported item demand").await;
+                        }
+                        Err(err) => {
+                            error!("failed to import item demand: {err:?}");
+                            status
+                                .failed("item demand import failed", &err.to_string())
+                                .await;
+                        }
                     }
                 }
             }
@@ -0,0 +1,77 @@
+use crate::{
+    AppResult,
+    database::{Database, leader::LeaderSession},
+};
+use std::time::Duration;
+use tokio::time::sleep;
+use tracing::{error, info, warn};
+
+const ELECTION_INTERVAL: Duration = Duration::from_secs(5);
+const LEASE_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Campaigns for the Postgres advisory lock that makes one worker the leader.
+///
+/// The lock lives as long as the leader's session, so a crashed leader is
+/// replaced as soon as Postgres notices its connection is gone.
+pub struct LeaderElection {
+    database: Database,
+    worker_id: String,
+}
+
+impl LeaderElection {
+    #[must_use]
+    pub const fn new(database: Database, worker_id: String) -> Self {
+        Self {
+            database,
+            worker_id,
+        }
+    }
+
+    /// Waits until this worker is the leader, retrying on the same session
+    /// until it fails.
+    pub async fn acquire(&self) -> LeaderSession {
+        let mut waiting = false;
+        let mut session = None;
+        loop {
+            match self.campaign(&mut session).await {
+                Ok(true) => {
+                    if let Some(session) = session.take() {
+                        info!("{} is now the leader", self.worker_id);
+                        return session;
+                    }
+                }
+                Ok(false) => {
+                    if !waiting {
+                        info!("another worker is the leader, standing by");
+                        waiting = true;
+                    }
+                }
+                Err(err) => {
+                    error!("leader election failed: {err:?}");
+                    session = None;
+                }
+            }
+            sleep(ELECTION_INTERVAL).await;
+        }
+    }
+
+    async fn campaign(&self, session: &mut Option<LeaderSession>) -> AppResult<bool> {
+        let session = match session {
+            Some(session) => session,
+            None => session.insert(self.database.open_leader_session(&self.worker_id).await?),
+        };
+        session.try_acquire().await
+    }
+
+    /// Returns once the session no longer holds the lock, e.g. because the
+    /// connection to Postgres dropped.
+    pub async fn wait_until_lost(&self, session: &mut LeaderSession) {
+        loop {
+            sleep(LEASE_CHECK_INTERVAL).await;
+            if !session.is_held().await {
+                warn!("{} lost the leader lock", self.worker_id);
+                return;
+            }
+        }
+    }
+}
@@ -1,4 +1,7 @@
-use crate::service::asset_mapping_import::AssetMappingImportService;
+use crate::{
+    service::asset_mapping_import::AssetMappingImportService,
+    worker::status::{HeartbeatTask, StatusReporter},
+};
 use std::{
     collections::{HashMap, HashSet},
     fs,
@@ -15,6 +18,7 @@ const MANIFEST_NAME: &str = "resource_manifest_idx.json";
 const SCAN_INTERVAL: Duration = Duration::from_secs(10);
 const IMPORT_TICK_INTERVAL: Duration = Duration::from_secs(10);
 const IMPORT_DEBOUNCE: Duration = Duration::from_secs(30);
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 struct ManifestFingerprint {
@@ -40,6 +44,7 @@ pub struct ManifestWatcher {
     event_tx: Option<UnboundedSender<ManifestSignal>>,
     scan_handle: Option<JoinHandle<()>>,
     import_handle: Option<JoinHandle<()>>,
+    _heartbeat: HeartbeatTask,
     gamedata_root: PathBuf,
 }
 
@@ -57,11 +62,16 @@ impl Drop for ManifestWatcher {
 }
 
 impl ManifestWatcher {
-    pub fn new(service: AssetMappingImportService, gamedata_root: &Path) -> anyhow::Result<Self> {
+    pub fn new(
+        service: AssetMappingImportService,
+        gamedata_root: &Path,
+        status: StatusReporter,
+    ) -> anyhow::Result<Self> {
         let gamedata_root = fs::canonicalize(gamedata_root)?;
         let (event_tx, event_rx) = unbounded_channel();
         let scan_handle = Some(spawn_scan_loop(event_tx.clone(), gamedata_root.clone()));
-        let import_handle = Some(spawn_import_loop(event_rx, service));
+        let heartbeat = status.start_heartbeat();
+        let import_handle = Some(spawn_import_loop(event_rx, service, status));
 
         info!("polling gamedata root: {}", gamedata_root.display());
 
@@ -69,6 +79,7 @@ impl ManifestWatcher {
             event_tx: Some(event_tx),
             scan_handle,
             import_handle,
+            _heartbeat: heartbeat,
             gamedata_root,
         })
     }
@@ -132,6 +143,7 @@ fn spawn_scan_loop(
 fn spawn_import_loop(
     mut event_rx: UnboundedReceiver<ManifestSignal>,
     service: AssetMappingImportService,
+    status: StatusReporter,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
         let mut pending = HashMap::<String, PendingImport>::new();
@@ -171,8 +183,14 @@ fn spawn_import_loop(
                             pending_import.fingerprint
                         );
                         match service.import_by_res_version(&res_version, pending_import.is_new).await {
-                            Ok(()) => debug!("imported asset mapping for {res_version}"),
-                            Err(err) => error!("failed to import asset mapping for {res_version}: {err:?}"),
+                            Ok(()) => {
+                                debug!("imported asset mapping for {res_version}");
+                                status.succeeded(&format!("imported asset mapping for {res_version}")).await;
+                            }
+                            Err(err) => {
+                                error!("failed to import asset mapping for {res_version}: {err:?}");
+                                status.failed(&format!("asset mapping import failed for {res_version}"), &err.to_string()).await;
+                            }
                         }
                     }
                 }
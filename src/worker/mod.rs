@@ -1,3 +1,7 @@
+pub mod command;
 pub mod item_demand_watcher;
+pub mod leader;
 pub mod manifest_watcher;
+pub mod replication;
+pub mod status;
 pub mod sync;
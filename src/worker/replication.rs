@@ -0,0 +1,64 @@
+use crate::service::replication::ReplicationService;
+use std::time::{Duration, Instant};
+use tokio::{task::JoinHandle, time::sleep};
+use tracing::{error, info};
+
+const IDLE_INTERVAL: Duration = Duration::from_secs(30);
+const ERROR_INTERVAL: Duration = Duration::from_mins(1);
+const LAG_LOG_INTERVAL: Duration = Duration::from_mins(5);
+
+pub struct ReplicationWorker {
+    handle: Option<JoinHandle<()>>,
+}
+
+impl Drop for ReplicationWorker {
+    fn drop(&mut self) {
+        if let Some(handle) = self.handle.take() {
+            handle.abort();
+        }
+        info!("replication worker stopped");
+    }
+}
+
+impl ReplicationWorker {
+    #[must_use]
+    pub fn new(service: ReplicationService) -> Self {
+        info!(
+            "replicating to: {}",
+            service
+                .replicas
+                .iter()
+                .map(|replica| replica.name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        Self {
+            handle: Some(tokio::spawn(run_loop(service))),
+        }
+    }
+}
+
+async fn run_loop(service: ReplicationService) {
+    let mut last_lag_log: Option<Instant> = None;
+    loop {
+        let delay = match service.process_queue().await {
+            Ok(0) => IDLE_INTERVAL,
+            Ok(_) => Duration::ZERO,
+            Err(err) => {
+                error!("replication batch failed: {err:?}");
+                ERROR_INTERVAL
+            }
+        };
+
+        if last_lag_log.is_none_or(|at| at.elapsed() >= LAG_LOG_INTERVAL) {
+            if let Err(err) = service.log_lag().await {
+                error!("failed to query replication lag: {err:?}");
+            }
+            last_lag_log = Some(Instant::now());
+        }
+
+        if !delay.is_zero() {
+            sleep(delay).await;
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::database::Database;
+use std::time::Duration;
+use tokio::{task::JoinHandle, time::MissedTickBehavior};
+use tracing::warn;
+
+/// Published by every worker for as long as it works the download queue,
+/// leader or not.
+pub const DOWNLOAD_QUEUE: &str = "download_queue";
+pub const VERSION_CHECK: &str = "version_check";
+pub const MANIFEST_WATCHER: &str = "manifest_watcher";
+pub const ITEM_DEMAND_WATCHER: &str = "item_demand_watcher";
+
+/// A component counts as dead after missing this many heartbeats.
+const MISSED_HEARTBEATS: u32 = 4;
+
+/// Publishes the heartbeats and run outcomes of one worker component to the
+/// `worker_status` table, where the server reads them. Failing to publish is
+/// only logged, it never stops the component itself.
+#[derive(Clone)]
+pub struct StatusReporter {
+    database: Database,
+    worker_id: String,
+    component: &'static str,
+    heartbeat_interval: Duration,
+    stale_after_seconds: i32,
+}
+
+/// Publishes the heartbeats of one component on a timer of its own until
+/// dropped, so a long run of the component does not hold them back.
+pub struct HeartbeatTask {
+    handle: JoinHandle<()>,
+}
+
+impl Drop for HeartbeatTask {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+impl StatusReporter {
+    #[must_use]
+    pub fn new(
+        database: Database,
+        worker_id: String,
+        component: &'static str,
+        heartbeat_interval: Duration,
+    ) -> Self {
+        let stale_after = heartbeat_interval * MISSED_HEARTBEATS;
+        Self {
+            database,
+            worker_id,
+            component,
+            heartbeat_interval,
+            stale_after_seconds: i32::try_from(stale_after.as_secs()).unwrap_or(i32::MAX),
+        }
+    }
+
+    /// Reporter for another component of the same worker.
+    #[must_use]
+    pub fn for_component(&self, component: &'static str) -> Self {
+        Self {
+            component,
+            ..self.clone()
+        }
+    }
+
+    #[must_use]
+    pub fn start_heartbeat(&self) -> HeartbeatTask {
+        let reporter = self.clone();
+        let handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(reporter.heartbeat_interval);
+            ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                reporter.heartbeat().await;
+            }
+        });
+        HeartbeatTask { handle }
+    }
+
+    pub async fn heartbeat(&self) {
+        if let Err(err) = self
+            .database
+            .worker_heartbeat(self.component, &self.worker_id, self.stale_after_seconds)
+            .await
+        {
+            warn!("failed to publish {} heartbeat: {err:?}", self.component);
+        }
+    }
+
+    pub async fn succeeded(&self, outcome: &str) {
+        self.record(outcome, None).await;
+    }
+
+    pub async fn failed(&self, outcome: &str, error: &str) {
+        self.record(outcome, Some(error)).await;
+    }
+
+    async fn record(&self, outcome: &str, error: Option<&str>) {
+        if let Err(err) = self
+            .database
+            .record_worker_run(
+                self.component,
+                &self.worker_id,
+                self.stale_after_seconds,
+                outcome,
+                error,
+            )
+            .await
+        {
+            warn!("failed to publish {} status: {err:?}", self.component);
+        }
+    }
+}
@@ -1,19 +1,27 @@
 use crate::{
     AppResult,
     service::{asset_download::AssetDownloadService, version_check::VersionCheckService},
+    worker::status::{self, StatusReporter},
 };
 use std::{
     sync::{Arc, Mutex},
     time::Duration,
 };
-use tokio::{spawn, task::JoinHandle, time::sleep};
+use tokio::{spawn, sync::Mutex as AsyncMutex, task::JoinHandle, time::sleep};
 use tracing::{error, info, instrument};
 
+const DOWNLOAD_IDLE_INTERVAL: Duration = Duration::from_secs(5);
+pub const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
 pub struct SyncWorker {
     version_check: Arc<VersionCheckService>,
     download: Arc<AssetDownloadService>,
     poll_interval: Duration,
+    status: StatusReporter,
     download_task: Arc<Mutex<Option<JoinHandle<()>>>>,
+    /// Keeps the scheduled poll and one requested through the admin API from
+    /// checking the same versions at once.
+    poll_lock: AsyncMutex<()>,
 }
 
 impl SyncWorker {
@@ -22,13 +30,16 @@ impl SyncWorker {
         version_check: VersionCheckService,
         download: AssetDownloadService,
         poll_interval: Duration,
+        status: StatusReporter,
     ) -> Self {
         let download = Arc::new(download);
         let worker = Self {
             version_check: Arc::new(version_check),
             download,
             poll_interval,
+            status,
             download_task: Arc::new(Mutex::new(None)),
+            poll_lock: AsyncMutex::new(()),
         };
         worker
             .download_task
@@ -38,55 +49,60 @@ impl SyncWorker {
         worker
     }
 
+    /// Works the shared download queue for as long as the worker runs, so
+    /// versions queued by other workers or the admin API are picked up too.
+    /// Every worker runs it, so its heartbeat shows followers in the status
+    /// as well.
     fn start_download_task(&self) -> JoinHandle<()> {
         let download = self.download.clone();
-        let download_task = self.download_task.clone();
+        let status = self.status.for_component(status::DOWNLOAD_QUEUE);
         spawn(async move {
+            let _heartbeat = status.start_heartbeat();
             loop {
                 match download.perform_download().await {
-                    Ok(has_more) => {
-                        if !has_more {
-                            info!("No more versions to download, exiting loop");
-                            break;
-                        }
-                        info!("Continuing download for more versions");
-                    }
+                    Ok(true) => {}
+                    Ok(false) => sleep(DOWNLOAD_IDLE_INTERVAL).await,
                     Err(err) => {
                         error!("Download failed: {err:?}");
                         sleep(Duration::from_mins(1)).await;
                     }
                 }
             }
-            download_task.lock().unwrap().take();
         })
     }
 
     #[instrument(name = "services.version_poll", skip_all)]
     pub async fn perform_poll(&self) -> AppResult<()> {
+        let _poll = self.poll_lock.lock().await;
         match self.version_check.perform_check().await {
             Ok(has_update) => {
                 if has_update {
-                    info!("New version detected, starting download...");
-                    let mut task = self.download_task.lock().unwrap();
-                    if task.as_ref().is_none_or(JoinHandle::is_finished) {
-                        *task = Some(self.start_download_task());
-                    } else {
-                        info!("Download task is already running");
-                    }
-                    drop(task);
+                    info!("New version detected, queued for download");
+                    self.status.succeeded("new version queued").await;
+                } else {
+                    self.status.succeeded("no new version").await;
                 }
                 Ok(())
             }
             Err(err) => {
                 error!("Version check failed: {err:?}");
+                self.status
+                    .failed("version check failed", &err.to_string())
+                    .await;
                 Err(err)
             }
         }
     }
 
+    /// Downloads one version right away, retrying its failed bundles.
+    pub async fn download_version(&self, version_id: i32) -> AppResult<()> {
+        self.download.manual_download(Some(version_id)).await
+    }
+
     pub async fn run(&self) {
         let mut interval = tokio::time::interval(self.poll_interval);
         interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let _heartbeat = self.status.start_heartbeat();
 
         loop {
             interval.tick().await;
@@ -96,11 +112,14 @@ impl SyncWorker {
         }
     }
 
-    pub fn stop(&self) {
+    /// Stops the download task and waits until it is gone, so its heartbeat
+    /// does not outlive the worker's stopped mark.
+    pub async fn stop(&self) {
         let value = self.download_task.lock().unwrap().take();
         if let Some(handle) = value {
             info!("Stopping download task");
             handle.abort();
+            let _ = handle.await;
         }
     }
 }
@@ -0,0 +1,171 @@
+use crate::support::{self, TestEnv};
+use ak_asset_storage::database::Database;
+use axum::http::StatusCode;
+use serde_json::Value;
+use std::{path::PathBuf, time::Duration};
+use tokio::time::{Instant, sleep};
+
+async fn wait_for_command(database: &Database, command: &Value) -> String {
+    let id = command["id"].as_i64().unwrap();
+    let deadline = Instant::now() + Duration::from_mins(1);
+    loop {
+        let command = database.query_admin_command(id).await.unwrap().unwrap();
+        if command.status != "pending" && command.status != "running" {
+            return command.status;
+        }
+        assert!(Instant::now() < deadline, "command {id} did not finish");
+        sleep(Duration::from_millis(200)).await;
+    }
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn admin_commands_are_run_by_the_worker() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+    let token = env.run_create_api_key("e2e-admin", &["admin:write"]).await;
+    env.copy_item_demand_fixture(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("e2e/fixtures/item_demand.json"),
+    );
+
+    let database = support::connect_database().await;
+    let version = database
+        .query_versions(None)
+        .await
+        .unwrap()
+        .into_iter()
+        .max_by_key(|version| version.id)
+        .unwrap();
+
+    let reset_path = format!("/api/v1/admin/version/{}/reset", version.id);
+    let (status, _): (_, Value) = env.post_json(&reset_path, None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+    let (status, _): (_, Value) = env
+        .post_json("/api/v1/admin/version/999999/reset", Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _): (_, Value) = env
+        .post_json("/api/v1/admin/manifest/unknown/import", Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, reset): (_, Value) = env.post_json(&reset_path, Some(&token)).await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(reset["kind"], "reset_version");
+    assert_eq!(reset["status"], "pending");
+
+    let mut worker = support::spawn_worker(env.config_path(), 3600).await;
+
+    assert_eq!(wait_for_command(&database, &reset).await, "succeeded");
+    let deadline = Instant::now() + Duration::from_mins(1);
+    while !database
+        .query_version_detail_by_id(version.id)
+        .await
+        .unwrap()
+        .unwrap()
+        .is_ready
+    {
+        assert!(
+            Instant::now() < deadline,
+            "reset version was not downloaded"
+        );
+        sleep(Duration::from_millis(200)).await;
+    }
+    let stale_jobs: i64 = sqlx::query_scalar(
+        r"
+SELECT COUNT(*) FROM download_jobs
+WHERE version_id = $1
+  AND (finished_at IS NULL OR finished_at < (SELECT started_at FROM admin_commands WHERE id = $2))
+        ",
+    )
+    .bind(version.id)
+    .bind(reset["id"].as_i64().unwrap())
+    .fetch_one(database.pool())
+    .await
+    .unwrap();
+    assert_eq!(stale_jobs, 0, "a reset version runs all of its jobs again");
+
+    let (status, download): (_, Value) = env
+        .post_json(
+            &format!("/api/v1/admin/version/{}/download", version.id),
+            Some(&token),
+        )
+        .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(wait_for_command(&database, &download).await, "succeeded");
+    let details = database
+        .query_version_detail_by_id(version.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(details.is_ready);
+
+    let (status, import): (_, Value) = env
+        .post_json("/api/v1/admin/item-demand/import", Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+    assert_eq!(wait_for_command(&database, &import).await, "succeeded");
+
+    let _ = worker.start_kill();
+
+    let (status, _) = env.get_text("/api/v1/item/源岩/demand").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let commands = database.query_admin_commands(10).await.unwrap();
+    assert_eq!(
+        commands
+            .iter()
+            .map(|command| command.kind.as_str())
+            .collect::<Vec<_>>(),
+        ["import_item_demand", "download_version", "reset_version"]
+    );
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn only_orphaned_admin_commands_are_failed() {
+    let env = TestEnv::bootstrap().await;
+    let token = env.run_create_api_key("e2e-admin", &["admin:write"]).await;
+    env.copy_item_demand_fixture(
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("e2e/fixtures/item_demand.json"),
+    );
+    let database = support::connect_database().await;
+
+    let (expired, held): (i64, i64) = sqlx::query_as(
+        r"
+WITH inserted AS (
+    INSERT INTO admin_commands (kind, status, started_at, worker, lease_expires_at)
+    VALUES
+        ('import_item_demand', 'running', NOW() - INTERVAL '10 minutes', 'gone:1', NOW() - INTERVAL '1 minute'),
+        ('import_item_demand', 'running', NOW(), 'busy:1', NOW() + INTERVAL '10 minutes')
+    RETURNING id, worker
+)
+SELECT
+    (SELECT id FROM inserted WHERE worker = 'gone:1'),
+    (SELECT id FROM inserted WHERE worker = 'busy:1')
+        ",
+    )
+    .fetch_one(database.pool())
+    .await
+    .unwrap();
+
+    let (status, import): (_, Value) = env
+        .post_json("/api/v1/admin/item-demand/import", Some(&token))
+        .await;
+    assert_eq!(status, StatusCode::ACCEPTED);
+
+    let mut worker = support::spawn_worker(env.config_path(), 3600).await;
+    assert_eq!(wait_for_command(&database, &import).await, "succeeded");
+    let _ = worker.start_kill();
+
+    let expired = database
+        .query_admin_command(expired)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(expired.status, "failed");
+    assert!(expired.error.unwrap().contains("worker stopped"));
+    let held = database.query_admin_command(held).await.unwrap().unwrap();
+    assert_eq!(held.status, "running", "a live lease is left alone");
+}
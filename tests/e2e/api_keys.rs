@@ -0,0 +1,67 @@
+use crate::support::{TestEnv, VersionSummary};
+use axum::http::StatusCode;
+use serde_json::Value;
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn api_keys_are_scoped_and_revocable() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+
+    let admin_key = env.run_create_api_key("e2e-admin", &["admin:write"]).await;
+    let docker_key = env
+        .run_create_api_key("e2e-docker", &["docker:launch"])
+        .await;
+    assert!(admin_key.starts_with("aks_"));
+
+    let (status, _): (_, Value) = env
+        .post_json("/api/v1/docker/launch", Some(&admin_key))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _): (_, Value) = env
+        .post_json("/api/v1/admin/item-demand/import", Some(&docker_key))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _): (_, Value) = env
+        .post_json(
+            "/api/v1/admin/item-demand/import",
+            Some("aks_00000000invalid"),
+        )
+        .await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED);
+
+    let versions: Vec<VersionSummary> = env.get_all_pages("/api/v1/version").await;
+    let path = format!("/api/v1/version/{}", versions[0].id);
+    assert_eq!(
+        env.delete(&path, Some(&docker_key)).await,
+        StatusCode::FORBIDDEN
+    );
+
+    // The legacy shared token only stands in for a `docker:launch` key.
+    let legacy_token = "e2e-token";
+    assert_eq!(
+        env.get_status("/api/v1/admin/command", Some(legacy_token))
+            .await,
+        StatusCode::FORBIDDEN
+    );
+    let (status, _): (_, Value) = env
+        .post_json("/api/v1/admin/item-demand/import", Some(legacy_token))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(
+        env.delete(&path, Some(legacy_token)).await,
+        StatusCode::FORBIDDEN
+    );
+
+    env.run_revoke_api_key("e2e-admin").await;
+    assert_eq!(
+        env.delete(&path, Some(&admin_key)).await,
+        StatusCode::UNAUTHORIZED
+    );
+
+    let full_key = env
+        .run_create_api_key("e2e-full", &["admin:write", "docker:launch"])
+        .await;
+    assert_eq!(env.delete(&path, Some(&full_key)).await, StatusCode::OK);
+}
@@ -0,0 +1,74 @@
+use crate::support::{BundleDetails, TestEnv};
+use ak_asset_storage::service::{asset_download::AssetDownloadService, types::dat_file_name};
+use axum::http::StatusCode;
+use reqwest::header;
+use std::io::Cursor;
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn download_stored_bundle_content() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+
+    let bundles: Vec<BundleDetails> = env.get_all_pages("/api/v1/bundle").await;
+
+    for bundle in &bundles {
+        // Files are keyed by their unpacked content, so a bundle serves the
+        // copy of the first seeded version that carried that content.
+        let expected = env
+            .fixture
+            .versions
+            .iter()
+            .filter_map(|version| {
+                std::fs::read(version.root.join(dat_file_name(&bundle.path))).ok()
+            })
+            .find(|bytes| {
+                AssetDownloadService::calc_digest(Cursor::new(bytes))
+                    .unwrap()
+                    .sha256
+                    == bundle.file_hash
+            })
+            .unwrap();
+
+        let response = env
+            .get_response(&format!("/api/v1/bundle/{}/content", bundle.id), None)
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_LENGTH],
+            expected.len().to_string()
+        );
+        assert_eq!(
+            response.headers()[header::CONTENT_DISPOSITION],
+            format!("attachment; filename=\"{}\"", dat_file_name(&bundle.path))
+        );
+        assert_eq!(response.bytes().await.unwrap(), expected);
+
+        let response = env
+            .get_response(
+                &format!("/api/v1/file/{}/content", bundle.file_hash),
+                Some("bytes=10-19"),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+        assert_eq!(
+            response.headers()[header::CONTENT_RANGE],
+            format!("bytes 10-19/{}", expected.len())
+        );
+        assert_eq!(response.bytes().await.unwrap(), expected[10..20]);
+
+        let response = env
+            .get_response(
+                &format!("/api/v1/file/{}/content", bundle.file_hash),
+                Some(&format!("bytes={}-", expected.len())),
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    }
+
+    let response = env
+        .get_response(&format!("/api/v1/file/{}/content", "0".repeat(64)), None)
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
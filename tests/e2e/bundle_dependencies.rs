@@ -0,0 +1,70 @@
+use crate::support::{TestEnv, VersionSummary};
+use axum::http::StatusCode;
+use serde_json::Value;
+
+const RES_VERSION: &str = "26-05-27-13-32-37_d44f28";
+const SCENE_ASSET: &str = "scenes/activities/a001/level_a001_01/level_a001_01";
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn dependency_closure_of_imported_manifest() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+    env.copy_manifest_fixture(RES_VERSION);
+    env.run_import_manifest(RES_VERSION).await;
+
+    let versions: Vec<VersionSummary> = env.get_all_pages("/api/v1/version").await;
+    let version = versions
+        .iter()
+        .find(|version| version.res_version == RES_VERSION)
+        .unwrap();
+
+    let (status, closure): (_, Value) = env
+        .get_json(&format!(
+            "/api/v1/manifest/{}/dependencies?asset_name={SCENE_ASSET}",
+            version.id
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        closure["bundlePath"],
+        "scenes/activities/a001/level_a001_01/level_a001_01.ab"
+    );
+    let bundles = closure["bundles"].as_array().unwrap();
+    let paths: Vec<&str> = bundles
+        .iter()
+        .map(|bundle| bundle["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        paths,
+        [
+            "scenes/activities/a001/level_a001_01/level_a001_01.ab",
+            "arts/avg_shader_profile.ab",
+            "scenes/activities/a001/level_a001_01/level_a001_01/lightingdata.ab",
+        ]
+    );
+    assert!(bundles.iter().all(|bundle| bundle["hash"].is_string()));
+    let total: i64 = bundles
+        .iter()
+        .map(|bundle| bundle["size"].as_i64().unwrap())
+        .sum();
+    assert_eq!(closure["totalSize"], total);
+
+    let (status, closure): (_, Value) = env
+        .get_json(&format!(
+            "/api/v1/manifest/{}/dependencies?asset_name=arts/avg_shader_profile",
+            version.id
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(closure["bundles"].as_array().unwrap().len(), 1);
+
+    let (status, _) = env
+        .get_text(&format!(
+            "/api/v1/manifest/{}/dependencies?asset_name=missing",
+            version.id
+        ))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
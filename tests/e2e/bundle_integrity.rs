@@ -0,0 +1,56 @@
+use crate::support::{self, AssetFault, BundleDetails, TestEnv};
+use axum::http::StatusCode;
+use serde_json::Value;
+
+const CORRUPT_BUNDLE: &str = "arts/charportraits/portraits_hub.ab";
+const TRUNCATED_BUNDLE: &str = "scenes/activities/a001/level_a001_01/level_a001_01/lightingdata.ab";
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn corrupted_and_truncated_bundles_are_not_stored() {
+    let env = TestEnv::bootstrap().await;
+    env.set_asset_fault(CORRUPT_BUNDLE, AssetFault::Corrupt);
+    env.set_asset_fault(TRUNCATED_BUNDLE, AssetFault::Truncated);
+
+    env.run_seed().await;
+
+    let latest = env.fixture.versions.last().unwrap();
+    let versions: Vec<Value> = env.get_all_pages("/api/v1/version").await;
+    let version = versions
+        .iter()
+        .find(|version| version["resVersion"] == latest.res_version.as_str())
+        .unwrap();
+    assert_eq!(version["downloadStatus"], "partial");
+
+    let (status, failures): (_, Vec<Value>) = env
+        .get_json(&format!("/api/v1/version/{}/failures", version["id"]))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let mut failed: Vec<&str> = failures
+        .iter()
+        .map(|failure| failure["path"].as_str().unwrap())
+        .collect();
+    failed.sort_unstable();
+    assert_eq!(failed, [CORRUPT_BUNDLE, TRUNCATED_BUNDLE]);
+    let truncated = failures
+        .iter()
+        .find(|failure| failure["path"] == TRUNCATED_BUNDLE)
+        .unwrap();
+    assert!(
+        truncated["lastError"]
+            .as_str()
+            .unwrap()
+            .contains("Size mismatch")
+    );
+
+    let bundles: Vec<BundleDetails> = env.get_all_pages("/api/v1/bundle").await;
+    assert!(
+        bundles
+            .iter()
+            .all(|bundle| bundle.path != CORRUPT_BUNDLE && bundle.path != TRUNCATED_BUNDLE)
+    );
+    let database = support::connect_database().await;
+    let stored = support::count_files(&env.runtime_dir().join("objects"));
+    let files = database.list_files().await.unwrap();
+    assert_eq!(stored, files.len());
+}
@@ -0,0 +1,48 @@
+use crate::support::{TestEnv, VersionSummary};
+use serde_json::Value;
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn bundles_expose_hot_update_list_metadata() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+
+    let mut versions: Vec<VersionSummary> = env.get_all_pages("/api/v1/version").await;
+    versions.sort_by_key(|version| version.id);
+    let version = versions.last().unwrap();
+    let fixture = env
+        .fixture
+        .versions
+        .iter()
+        .find(|fixture| fixture.res_version == version.res_version)
+        .unwrap();
+    let hot_update_list: Value = serde_json::from_str(&fixture.hot_update_list).unwrap();
+    let info = &hot_update_list["abInfos"][0];
+
+    let bundles: Vec<Value> = env
+        .get_all_pages(&format!(
+            "/api/v1/bundle?version={}&sort=totalSize&order=desc&limit=1",
+            version.id
+        ))
+        .await;
+    assert_eq!(bundles.len(), fixture.bundle_names.len());
+    let sizes: Vec<i64> = bundles
+        .iter()
+        .map(|bundle| bundle["totalSize"].as_i64().unwrap())
+        .collect();
+    assert!(sizes.windows(2).all(|pair| pair[0] >= pair[1]));
+
+    let bundles: Vec<Value> = env
+        .get_all_pages(&format!(
+            "/api/v1/bundle?version={}&md5={}",
+            version.id,
+            info["md5"].as_str().unwrap()
+        ))
+        .await;
+    assert_eq!(bundles.len(), 1);
+    assert_eq!(bundles[0]["path"], info["name"]);
+    assert_eq!(bundles[0]["abSize"], info["abSize"]);
+    assert_eq!(bundles[0]["totalSize"], info["totalSize"]);
+    assert_eq!(bundles[0]["upstreamHash"], info["hash"]);
+}
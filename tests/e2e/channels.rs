@@ -0,0 +1,79 @@
+use crate::support::{self, BundleDetails, TestEnv};
+use serde_json::Value;
+use std::collections::HashSet;
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn channels_track_versions_separately_and_share_files() {
+    let env = TestEnv::bootstrap_with_channels(&[
+        ("official-android", "official"),
+        ("bilibili-android", "bilibili"),
+    ])
+    .await;
+    let [first, latest] = [&env.fixture.versions[0], &env.fixture.versions[1]];
+
+    env.run_seed_csv(&format!(
+        "{},{}\n{},{}\n{},{},bilibili-android\n",
+        first.res_version,
+        first.client_version,
+        latest.res_version,
+        latest.client_version,
+        latest.res_version,
+        latest.client_version,
+    ))
+    .await;
+
+    let (_, channels): (_, Vec<Value>) = env.get_json("/api/v1/channel").await;
+    assert_eq!(channels.len(), 2);
+    assert_eq!(channels[0]["name"], "official-android");
+    assert_eq!(channels[1]["server"], "bilibili");
+    assert_eq!(channels[1]["platform"], "Android");
+
+    let all: Vec<Value> = env.get_all_pages("/api/v1/version").await;
+    assert_eq!(all.len(), 3);
+    assert!(
+        all.iter()
+            .all(|version| version["downloadStatus"] == "ready")
+    );
+    let official: Vec<Value> = env
+        .get_all_pages("/api/v1/version?channel=official-android")
+        .await;
+    assert_eq!(official.len(), 2);
+    let bilibili: Vec<Value> = env
+        .get_all_pages("/api/v1/version?channel=bilibili-android")
+        .await;
+    assert_eq!(bilibili.len(), 1);
+    assert_eq!(bilibili[0]["resVersion"], latest.res_version.as_str());
+
+    // The same res version on the second channel reuses the files stored for
+    // the first one instead of fetching them again.
+    let official_bundles: Vec<BundleDetails> = env
+        .get_all_pages(&format!(
+            "/api/v1/bundle?channel=official-android&version={}",
+            official
+                .iter()
+                .find(|version| version["resVersion"] == latest.res_version.as_str())
+                .unwrap()["id"]
+        ))
+        .await;
+    let bilibili_bundles: Vec<BundleDetails> = env
+        .get_all_pages("/api/v1/bundle?channel=bilibili-android")
+        .await;
+    assert_eq!(bilibili_bundles.len(), latest.bundle_names.len());
+    let official_files: HashSet<i32> = official_bundles
+        .iter()
+        .map(|bundle| bundle.file_id)
+        .collect();
+    let bilibili_files: HashSet<i32> = bilibili_bundles
+        .iter()
+        .map(|bundle| bundle.file_id)
+        .collect();
+    assert_eq!(official_files, bilibili_files);
+    assert_eq!(env.asset_downloads("bilibili"), 0);
+
+    let database = support::connect_database().await;
+    assert_eq!(
+        support::count_files(&env.runtime_dir().join("objects")),
+        database.list_files().await.unwrap().len()
+    );
+}
@@ -0,0 +1,121 @@
+use crate::support::{self, AssetFault, TestEnv};
+use std::{collections::HashSet, time::Duration};
+use tokio::time::{Instant, sleep};
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn workers_share_the_download_queue() {
+    let env = TestEnv::bootstrap_worker().await;
+    let mut first = support::spawn_worker(env.config_path(), 1).await;
+    let mut second = support::spawn_worker(env.config_path(), 1).await;
+
+    let database = support::connect_database().await;
+    support::wait_for_ready_version(&database, Duration::from_mins(1))
+        .await
+        .unwrap();
+
+    let _ = first.start_kill();
+    let _ = second.start_kill();
+
+    assert!(!database.has_active_download_jobs().await.unwrap());
+
+    let versions = database.query_versions(None).await.unwrap();
+    assert_eq!(versions.len(), 1);
+
+    let bundles = database
+        .query_bundles_by_version_id(versions.first().unwrap().id)
+        .await
+        .unwrap();
+    assert_eq!(bundles.len(), 5);
+    assert_eq!(
+        bundles
+            .iter()
+            .map(|bundle| bundle.file_hash.as_str())
+            .collect::<HashSet<_>>()
+            .len(),
+        5
+    );
+
+    env.assert_storage_state().await;
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn leases_of_a_killed_worker_are_reclaimed() {
+    let env = TestEnv::bootstrap_worker().await;
+    let latest = env.fixture.versions.last().unwrap();
+    for bundle_name in &latest.bundle_names {
+        env.set_asset_fault(
+            bundle_name,
+            AssetFault::Slow {
+                chunk_delay: Duration::from_millis(500),
+            },
+        );
+    }
+    let mut crashed = support::spawn_worker(env.config_path(), 3600).await;
+
+    let database = support::connect_database().await;
+    let deadline = Instant::now() + Duration::from_mins(1);
+    let (holder, leased): (String, i64) = loop {
+        let held: Option<(String, i64)> = sqlx::query_as(
+            "SELECT worker, COUNT(*) FROM download_jobs WHERE status = 'running' GROUP BY worker",
+        )
+        .fetch_optional(database.pool())
+        .await
+        .unwrap();
+        if let Some(held) = held.filter(|_| env.asset_downloads("official") > 0) {
+            break held;
+        }
+        assert!(Instant::now() < deadline, "worker did not claim a job");
+        sleep(Duration::from_millis(100)).await;
+    };
+    crashed.kill().await.unwrap();
+
+    // Stand in for the lease running out, which takes minutes in real time.
+    sqlx::query(
+        "UPDATE download_jobs SET lease_expires_at = NOW() WHERE status = 'running' AND worker = $1",
+    )
+    .bind(&holder)
+    .execute(database.pool())
+    .await
+    .unwrap();
+
+    let mut survivor = support::spawn_worker(env.config_path(), 3600).await;
+    support::wait_for_ready_version(&database, Duration::from_mins(1))
+        .await
+        .unwrap();
+    let _ = survivor.start_kill();
+
+    let (done, left_with_holder): (i64, i64) = sqlx::query_as(
+        "SELECT COUNT(*) FILTER (WHERE status = 'done'), COUNT(*) FILTER (WHERE worker = $1) FROM download_jobs",
+    )
+    .bind(&holder)
+    .fetch_one(database.pool())
+    .await
+    .unwrap();
+    assert_eq!(done, i64::try_from(latest.bundle_names.len()).unwrap());
+    assert_eq!(
+        left_with_holder, 0,
+        "every lease of the killed worker is reclaimed"
+    );
+
+    // The interrupted bundles are fetched again, every other one only once,
+    // and each file is stored once.
+    assert_eq!(
+        env.asset_downloads("official"),
+        latest.bundle_names.len() + usize::try_from(leased).unwrap()
+    );
+    let files = database.list_files().await.unwrap();
+    assert_eq!(
+        files
+            .iter()
+            .map(|file| file.hash.as_str())
+            .collect::<HashSet<_>>()
+            .len(),
+        files.len()
+    );
+    assert_eq!(
+        support::count_files(&env.runtime_dir().join("objects")),
+        files.len()
+    );
+}
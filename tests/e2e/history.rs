@@ -0,0 +1,116 @@
+use crate::support::{self, TestEnv, VersionSummary};
+use ak_asset_storage::service::asset_mapping_import::AssetMappingImportService;
+use axum::http::StatusCode;
+use serde_json::Value;
+
+const RES_VERSION: &str = "26-05-27-13-32-37_d44f28";
+
+fn changes(history: &[Value]) -> Vec<(i64, &str)> {
+    history
+        .iter()
+        .map(|entry| {
+            (
+                entry["versionId"].as_i64().unwrap(),
+                entry["change"].as_str().unwrap(),
+            )
+        })
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn bundle_and_asset_history_across_versions() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+
+    let mut versions: Vec<VersionSummary> = env.get_all_pages("/api/v1/version").await;
+    versions.sort_by_key(|version| version.id);
+    let (first, last) = (
+        i64::from(versions.first().unwrap().id),
+        i64::from(versions.last().unwrap().id),
+    );
+
+    let (status, history): (_, Vec<Value>) = env
+        .get_json("/api/v1/bundle/history?path=arts/avg_shader_profile.ab")
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(changes(&history), [(first, "added")]);
+    assert!(history[0]["fileHash"].is_string());
+
+    let (status, history): (_, Vec<Value>) = env
+        .get_json(
+            "/api/v1/bundle/history?path=refs/fx/texture/overseas/live2d/dyn_char_1037_amiya3_sale%2313.ab",
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(changes(&history), [(first, "added"), (last, "removed")]);
+    assert!(history[1]["bundlePath"].is_null());
+
+    let (status, _) = env.get_text("/api/v1/bundle/history?path=missing.ab").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    env.copy_manifest_fixture(RES_VERSION);
+    env.run_import_manifest(RES_VERSION).await;
+
+    let (status, history): (_, Vec<Value>) = env
+        .get_json("/api/v1/asset/history?asset_name=arts/avg_shader_profile")
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(changes(&history), [(last, "added")]);
+    assert_eq!(history[0]["bundlePath"], "arts/avg_shader_profile.ab");
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn asset_history_stays_within_a_channel() {
+    let env = TestEnv::bootstrap_with_channels(&[
+        ("official-android", "official"),
+        ("bilibili-android", "bilibili"),
+    ])
+    .await;
+    let latest = env.fixture.versions.last().unwrap();
+    env.run_seed_csv(&format!(
+        "{res},{client}\n{res},{client},bilibili-android\n",
+        res = latest.res_version,
+        client = latest.client_version,
+    ))
+    .await;
+
+    env.copy_manifest_fixture(RES_VERSION);
+    AssetMappingImportService {
+        database: support::connect_database().await,
+        channel: "bilibili-android".to_string(),
+        gamedata_root: env.runtime_dir().join("asset/gamedata"),
+    }
+    .import_by_res_version(RES_VERSION, false)
+    .await
+    .unwrap();
+
+    // Only the second channel has mappings so far.
+    let (status, _) = env
+        .get_text("/api/v1/asset/history?asset_name=arts/avg_shader_profile")
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, history): (_, Vec<Value>) = env
+        .get_json(
+            "/api/v1/asset/history?asset_name=arts/avg_shader_profile&channel=bilibili-android",
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0]["channel"], "bilibili-android");
+
+    env.run_import_manifest(RES_VERSION).await;
+    for channel in ["official-android", "bilibili-android"] {
+        let (status, history): (_, Vec<Value>) = env
+            .get_json(&format!(
+                "/api/v1/asset/history?asset_name=arts/avg_shader_profile&channel={channel}"
+            ))
+            .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0]["channel"], channel);
+        assert_eq!(history[0]["change"], "added");
+    }
+}
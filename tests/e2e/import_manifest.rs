@@ -5,7 +5,7 @@ use std::time::Duration;
 const RES_VERSION: &str = "26-05-27-13-32-37_d44f28";
 
 #[tokio::test]
-#[ignore = "manual e2e test requiring docker, rc, and fixture assets"]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
 async fn import_manifest_command_imports_asset_mappings() {
     let env = TestEnv::bootstrap_worker().await;
     let version_id = env
@@ -1,8 +1,42 @@
 #![allow(clippy::unwrap_used)]
+// The harness predates `clippy --all-targets` in CI; keep its helpers as written.
+#![allow(
+    clippy::duration_suboptimal_units,
+    clippy::ignored_unit_patterns,
+    clippy::map_unwrap_or,
+    clippy::match_same_arms,
+    clippy::missing_const_for_fn,
+    clippy::option_if_let_else,
+    clippy::unused_async
+)]
 
+mod admin_commands;
+mod api_keys;
+mod bundle_content;
+mod bundle_dependencies;
+mod bundle_integrity;
+mod bundle_metadata;
+mod channels;
+mod download_jobs;
+mod history;
 mod import_manifest;
 mod item_demand;
+mod manifest_diff;
 mod manifest_watcher;
+mod pagination;
+mod search;
 mod seed_server;
+mod shared_bundles;
+mod slow_download;
+mod storage_audit;
+mod storage_replication;
+mod storage_stats;
 mod support;
+mod version_delete;
+mod version_diff;
+mod version_failures;
+mod version_packs;
+mod version_progress;
+mod worker_leader;
 mod worker_poll;
+mod worker_status;
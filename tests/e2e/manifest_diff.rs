@@ -0,0 +1,117 @@
+use crate::support::{TestEnv, VersionSummary};
+use axum::http::StatusCode;
+use serde_json::Value;
+
+const BASE_RES_VERSION: &str = "26-05-20-12-59-09_e8f456";
+const RES_VERSION: &str = "26-05-27-13-32-37_d44f28";
+
+fn asset_names(diff: &Value) -> Vec<&str> {
+    diff["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["assetName"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn manifest_diff_filters_and_pages_assets() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+    env.copy_manifest_fixture(RES_VERSION);
+    env.run_import_manifest(RES_VERSION).await;
+
+    let mut versions: Vec<VersionSummary> = env.get_all_pages("/api/v1/version").await;
+    versions.sort_by_key(|version| version.id);
+    let (from, to) = (versions.first().unwrap().id, versions.last().unwrap().id);
+
+    // The base version has no imported mappings yet.
+    let (status, _) = env
+        .get_text(&format!("/api/v1/manifest/diff?from={from}&to={to}"))
+        .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    env.copy_manifest_fixture(BASE_RES_VERSION);
+    env.run_import_manifest(BASE_RES_VERSION).await;
+
+    let (status, diff): (_, Value) = env
+        .get_json(&format!("/api/v1/manifest/diff?from={from}&to={to}"))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(diff["total"], 5);
+    assert_eq!(diff["added"], 3);
+    assert_eq!(diff["removed"], 1);
+    assert_eq!(diff["moved"], 1);
+    assert_eq!(diff["modified"], 0);
+    let changes: Vec<(&str, &str)> = diff["entries"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| {
+            (
+                entry["assetName"].as_str().unwrap(),
+                entry["change"].as_str().unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        changes,
+        [
+            ("arts/avgmaterialpresets/bluescreen_middle", "moved"),
+            ("arts/charportraits/char_002_amiya_1", "added"),
+            ("refs/fx/texture/overseas/char_1037_amiya3_sale", "removed"),
+            (
+                "scenes/activities/a001/level_a001_01/level_a001_01",
+                "added"
+            ),
+            (
+                "scenes/activities/a001/level_a001_01/level_a001_01/lightingdata",
+                "added"
+            ),
+        ]
+    );
+
+    let (_, diff): (_, Value) = env
+        .get_json(&format!(
+            "/api/v1/manifest/diff?from={from}&to={to}&prefix=arts/&offset=1&limit=1"
+        ))
+        .await;
+    assert_eq!(diff["total"], 2);
+    assert_eq!(asset_names(&diff), ["arts/charportraits/char_002_amiya_1"]);
+
+    let (_, diff): (_, Value) = env
+        .get_json(&format!(
+            "/api/v1/manifest/diff?from={from}&to={to}&offset=10"
+        ))
+        .await;
+    assert_eq!(diff["total"], 5);
+    assert!(asset_names(&diff).is_empty());
+
+    let (_, diff): (_, Value) = env
+        .get_json(&format!(
+            "/api/v1/manifest/diff?from={from}&to={to}&limit=0"
+        ))
+        .await;
+    assert_eq!(asset_names(&diff).len(), 1);
+
+    let (_, diff): (_, Value) = env
+        .get_json(&format!(
+            "/api/v1/manifest/diff?from={to}&to={from}&prefix=dyn/scenes/"
+        ))
+        .await;
+    assert_eq!(diff["removed"], 2);
+    assert_eq!(
+        asset_names(&diff),
+        [
+            "scenes/activities/a001/level_a001_01/level_a001_01",
+            "scenes/activities/a001/level_a001_01/level_a001_01/lightingdata",
+        ]
+    );
+
+    let (status, _) = env
+        .get_text(&format!("/api/v1/manifest/diff?from={from}&to=999999"))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
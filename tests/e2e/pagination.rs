@@ -0,0 +1,98 @@
+use crate::support::{BundleDetails, TestEnv, VersionSummary};
+use axum::http::StatusCode;
+use serde_json::Value;
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn list_endpoints_page_with_cursors_and_legacy_arrays() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+
+    let (status, page): (_, Value) = env.get_json("/api/v1/version?limit=1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["total"], env.fixture.versions.len());
+    assert_eq!(page["items"].as_array().unwrap().len(), 1);
+    assert!(page["nextCursor"].is_string());
+
+    let ascending: Vec<VersionSummary> = env
+        .get_all_pages("/api/v1/version?sort=resVersion&order=asc&limit=1")
+        .await;
+    let mut expected: Vec<&str> = ascending
+        .iter()
+        .map(|version| version.res_version.as_str())
+        .collect();
+    expected.sort_unstable();
+    assert_eq!(
+        ascending
+            .iter()
+            .map(|version| version.res_version.as_str())
+            .collect::<Vec<_>>(),
+        expected
+    );
+
+    let (status, legacy): (_, Vec<VersionSummary>) =
+        env.get_json("/api/v1/version?legacy=true").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(legacy.len(), ascending.len());
+
+    let (status, invalid): (_, Value) = env.get_json("/api/v1/version?cursor=zz").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{invalid}");
+
+    let version = legacy.iter().max_by_key(|version| version.id).unwrap();
+    let paged: Vec<BundleDetails> = env
+        .get_all_pages(&format!(
+            "/api/v1/version/{}/files?sort=fileSize&order=desc&limit=2",
+            version.id
+        ))
+        .await;
+    assert!(
+        paged
+            .windows(2)
+            .all(|pair| pair[0].file_size >= pair[1].file_size)
+    );
+
+    let (status, mut files): (_, Vec<BundleDetails>) = env
+        .get_json(&format!("/api/v1/version/{}/files?legacy=true", version.id))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let mut paged_ids: Vec<i32> = paged.iter().map(|file| file.id).collect();
+    paged_ids.sort_unstable();
+    files.sort_by_key(|file| file.id);
+    assert_eq!(
+        files.iter().map(|file| file.id).collect::<Vec<_>>(),
+        paged_ids
+    );
+
+    let (status, bundles): (_, Vec<BundleDetails>) = env
+        .get_json(&format!(
+            "/api/v1/bundle?version={}&legacy=true",
+            version.id
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(bundles.len(), paged.len());
+
+    let (status, all): (_, Vec<BundleDetails>) = env.get_json("/api/v1/bundle?legacy=true").await;
+    assert_eq!(status, StatusCode::OK);
+    let by_pid: Vec<BundleDetails> = env
+        .get_all_pages("/api/v1/bundle?sort=pid&order=desc&limit=7")
+        .await;
+    assert_eq!(by_pid.len(), all.len());
+    let pids: Vec<Option<&str>> = by_pid.iter().map(|bundle| bundle.pid.as_deref()).collect();
+    let missing = pids.iter().position(Option::is_none).unwrap_or(pids.len());
+    assert!(
+        pids[missing..].iter().all(Option::is_none),
+        "bundles without pid come last"
+    );
+    assert!(pids[..missing].windows(2).all(|pair| pair[0] >= pair[1]));
+
+    let (_, page): (_, Value) = env.get_json("/api/v1/bundle?sort=path&limit=1").await;
+    let text_cursor = page["nextCursor"].as_str().unwrap();
+    let (status, mismatched): (_, Value) = env
+        .get_json(&format!(
+            "/api/v1/bundle?sort=fileSize&cursor={text_cursor}"
+        ))
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST, "{mismatched}");
+}
@@ -0,0 +1,105 @@
+use crate::support::{TestEnv, VersionSummary};
+use axum::http::StatusCode;
+use serde_json::Value;
+
+const RES_VERSION: &str = "26-05-27-13-32-37_d44f28";
+const SALE_BUNDLE: &str = "refs/fx/texture/overseas/char_1037_amiya3_sale#13.ab";
+const LIVE2D_BUNDLE: &str = "refs/fx/texture/overseas/live2d/dyn_char_1037_amiya3_sale#13.ab";
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn bundle_search_pages_ranked_hits() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+
+    let mut versions: Vec<VersionSummary> = env.get_all_pages("/api/v1/version").await;
+    versions.sort_by_key(|version| version.id);
+    let (first, last) = (versions.first().unwrap().id, versions.last().unwrap().id);
+
+    let (status, page): (_, Value) = env.get_json("/api/v1/bundle/search?q=amiya3&limit=1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["total"], 2);
+    assert_eq!(page["items"].as_array().unwrap().len(), 1);
+    let cursor = page["nextCursor"].as_str().unwrap();
+
+    let (status, next): (_, Value) = env
+        .get_json(&format!(
+            "/api/v1/bundle/search?q=amiya3&limit=1&cursor={cursor}"
+        ))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(next["nextCursor"].is_null());
+
+    let mut hits: Vec<(&str, i64)> = [&page, &next]
+        .iter()
+        .map(|page| {
+            let hit = &page["items"][0];
+            (
+                hit["path"].as_str().unwrap(),
+                hit["versionId"].as_i64().unwrap(),
+            )
+        })
+        .collect();
+    hits.sort_unstable();
+    assert_eq!(
+        hits,
+        [
+            (SALE_BUNDLE, i64::from(last)),
+            (LIVE2D_BUNDLE, i64::from(first))
+        ]
+    );
+    assert!(
+        page["items"][0]["score"].as_f64().unwrap() >= next["items"][0]["score"].as_f64().unwrap()
+    );
+
+    let (_, page): (_, Value) = env
+        .get_json(&format!("/api/v1/bundle/search?q=amiya3&version={last}"))
+        .await;
+    assert_eq!(page["total"], 1);
+    assert_eq!(page["items"][0]["path"], SALE_BUNDLE);
+
+    let (status, _) = env
+        .get_text("/api/v1/bundle/search?q=amiya3&cursor=invalid")
+        .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn manifest_search_matches_short_name_and_asset_path() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+    env.copy_manifest_fixture(RES_VERSION);
+    env.run_import_manifest(RES_VERSION).await;
+
+    let mut versions: Vec<VersionSummary> = env.get_all_pages("/api/v1/version").await;
+    versions.sort_by_key(|version| version.id);
+    let (first, last) = (versions.first().unwrap().id, versions.last().unwrap().id);
+
+    let (status, page): (_, Value) = env
+        .get_json(&format!("/api/v1/manifest/{last}/search?q=.unity"))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["total"], 1);
+    assert_eq!(
+        page["items"][0]["path"],
+        "scenes/activities/a001/level_a001_01/level_a001_01"
+    );
+
+    let (_, page): (_, Value) = env
+        .get_json(&format!("/api/v1/manifest/{first}/search?q=amiya"))
+        .await;
+    assert_eq!(page["total"], 0);
+
+    let (_, page): (_, Value) = env
+        .get_json(&format!(
+            "/api/v1/manifest/{first}/search?q=amiya&all_versions=true"
+        ))
+        .await;
+    assert_eq!(page["total"], 1);
+    assert_eq!(page["items"][0]["shortName"], "char_002_amiya_1");
+    assert_eq!(page["items"][0]["versionId"], last);
+    assert_eq!(page["items"][0]["resVersion"], RES_VERSION);
+}
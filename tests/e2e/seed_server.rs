@@ -2,14 +2,13 @@ use crate::support::{BundleDetails, TestEnv, VersionDetails, VersionSummary};
 use std::collections::{HashMap, HashSet};
 
 #[tokio::test]
-#[ignore = "manual e2e test requiring docker, rc, and fixture assets"]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
 async fn seed_two_versions_then_query_real_server() {
     let env = TestEnv::bootstrap().await;
 
     env.run_seed().await;
 
-    let (status, version_list): (_, Vec<VersionSummary>) = env.get_json("/api/v1/version").await;
-    assert_eq!(status, axum::http::StatusCode::OK);
+    let version_list: Vec<VersionSummary> = env.get_all_pages("/api/v1/version?limit=1").await;
     assert_eq!(version_list.len(), env.fixture.versions.len());
 
     let expected_by_res = env
@@ -30,10 +29,9 @@ async fn seed_two_versions_then_query_real_server() {
         assert_eq!(status, axum::http::StatusCode::OK);
         assert_eq!(version_detail.hot_update_list, expected.hot_update_list);
 
-        let (status, bundles): (_, Vec<BundleDetails>) = env
-            .get_json(&format!("/api/v1/version/{}/files", version.id))
+        let bundles: Vec<BundleDetails> = env
+            .get_all_pages(&format!("/api/v1/version/{}/files?limit=2", version.id))
             .await;
-        assert_eq!(status, axum::http::StatusCode::OK);
         assert_eq!(bundles.len(), expected.bundle_names.len());
 
         let mut bundle_paths = bundles
@@ -45,10 +43,9 @@ async fn seed_two_versions_then_query_real_server() {
         assert!(bundle_paths.iter().any(|path| path.contains('#')));
         assert!(bundle_paths.iter().any(|path| path.contains('/')));
 
-        let (status, query_bundles): (_, Vec<BundleDetails>) = env
-            .get_json(&format!("/api/v1/bundle?version={}", version.id))
+        let query_bundles: Vec<BundleDetails> = env
+            .get_all_pages(&format!("/api/v1/bundle?version={}", version.id))
             .await;
-        assert_eq!(status, axum::http::StatusCode::OK);
         assert_eq!(query_bundles.len(), bundles.len());
 
         for bundle in &query_bundles {
@@ -67,8 +64,7 @@ async fn seed_two_versions_then_query_real_server() {
         }
     }
 
-    let (status, all_bundles): (_, Vec<BundleDetails>) = env.get_json("/api/v1/bundle").await;
-    assert_eq!(status, axum::http::StatusCode::OK);
+    let all_bundles: Vec<BundleDetails> = env.get_all_pages("/api/v1/bundle").await;
     assert_eq!(all_bundles.len(), env.fixture.all_bundle_names.len());
 
     let unique_hashes = all_bundles
@@ -89,5 +85,5 @@ async fn seed_two_versions_then_query_real_server() {
     assert!(file_id_by_hash.values().all(|ids| ids.len() == 1));
 
     env.assert_database_state().await;
-    env.assert_s3_state().await;
+    env.assert_storage_state().await;
 }
@@ -0,0 +1,42 @@
+use crate::support::{self, BundleDetails, TestEnv};
+use std::collections::{HashMap, HashSet};
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn versions_sharing_a_bundle_store_one_file() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+
+    let bundles: Vec<BundleDetails> = env.get_all_pages("/api/v1/bundle").await;
+    let mut versions_by_path: HashMap<&str, Vec<&BundleDetails>> = HashMap::new();
+    for bundle in &bundles {
+        versions_by_path
+            .entry(bundle.path.as_str())
+            .or_default()
+            .push(bundle);
+    }
+    // Both fixture versions carry two of the bundles, repacked with new
+    // timestamps but with the same contents.
+    let shared: Vec<&Vec<&BundleDetails>> = versions_by_path
+        .values()
+        .filter(|bundles| bundles.len() == 2)
+        .collect();
+    assert_eq!(shared.len(), 2);
+    for bundles in shared {
+        assert_ne!(bundles[0].version_id, bundles[1].version_id);
+        assert_eq!(bundles[0].file_id, bundles[1].file_id);
+    }
+
+    let database = support::connect_database().await;
+    let files = database.list_files().await.unwrap();
+    let hashes: HashSet<&str> = bundles
+        .iter()
+        .map(|bundle| bundle.file_hash.as_str())
+        .collect();
+    assert_eq!(files.len(), hashes.len());
+    assert_eq!(
+        support::count_files(&env.runtime_dir().join("objects")),
+        files.len()
+    );
+}
@@ -0,0 +1,29 @@
+use crate::support::{AssetFault, TestEnv};
+use std::time::Duration;
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn slow_bundle_streams_past_the_request_timeout() {
+    let env = TestEnv::bootstrap().await;
+    let slow_bundle = env.fixture.all_bundle_names.first().unwrap().clone();
+    // Eight chunks five seconds apart: about 40s in total, but never idle
+    // long enough to hit the read timeout.
+    env.set_asset_fault(
+        &slow_bundle,
+        AssetFault::Slow {
+            chunk_delay: Duration::from_secs(5),
+        },
+    );
+
+    env.run_seed().await;
+
+    let versions: Vec<serde_json::Value> = env.get_all_pages("/api/v1/version").await;
+    assert_eq!(versions.len(), env.fixture.versions.len());
+    assert!(
+        versions
+            .iter()
+            .all(|version| version["downloadStatus"] == "ready")
+    );
+    env.assert_database_state().await;
+    env.assert_storage_state().await;
+}
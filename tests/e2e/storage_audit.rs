@@ -0,0 +1,41 @@
+use crate::support::{TestEnv, connect_database};
+use ak_asset_storage::database::row::FileRow;
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn audit_reports_missing_objects() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+
+    let (success, report) = env.run_audit(&["--rehash-all"]).await;
+    assert!(success, "audit failed on a consistent store: {report}");
+    assert!(report["rehashedObjects"].as_u64().unwrap() > 0);
+    assert_eq!(report["checkedFiles"], report["checkedObjects"]);
+
+    let missing_hash = "0".repeat(64);
+    connect_database()
+        .await
+        .create_file(FileRow {
+            id: None,
+            hash: missing_hash.clone(),
+            size: 1,
+        })
+        .await
+        .unwrap();
+
+    let (success, report) = env.run_audit(&[]).await;
+    assert!(!success);
+    assert_eq!(report["missingObjects"], serde_json::json!([missing_hash]));
+    assert!(report["orphanObjects"].as_array().unwrap().is_empty());
+
+    let orphan_dir = env.runtime_dir().join("objects/ff/ff");
+    std::fs::create_dir_all(&orphan_dir).unwrap();
+    std::fs::write(orphan_dir.join("f".repeat(60)), b"orphan").unwrap();
+    let (success, report) = env.run_audit(&[]).await;
+    assert!(!success);
+    assert_eq!(
+        report["orphanObjects"],
+        serde_json::json!([format!("ff/ff/{}", "f".repeat(60))])
+    );
+}
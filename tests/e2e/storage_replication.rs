@@ -0,0 +1,26 @@
+use crate::support::{TestEnv, connect_database};
+use axum::http::StatusCode;
+use serde_json::Value;
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn replicate_backfills_queued_objects() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+
+    let files = connect_database().await.list_files().await.unwrap();
+    let (status, replicas): (_, Vec<Value>) = env.get_json("/api/v1/storage/replication").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(replicas.len(), 1);
+    assert_eq!(replicas[0]["replica"], "mirror");
+    assert_eq!(replicas[0]["pending"], files.len());
+    assert_eq!(env.count_replica_objects(), 0);
+
+    env.run_replicate().await;
+
+    assert_eq!(env.count_replica_objects(), files.len());
+    let (_, replicas): (_, Vec<Value>) = env.get_json("/api/v1/storage/replication").await;
+    assert_eq!(replicas[0]["pending"], 0);
+    assert_eq!(replicas[0]["lagSeconds"], 0);
+}
@@ -0,0 +1,99 @@
+use crate::support::{TestEnv, connect_database};
+use ak_asset_storage::{
+    config::DEFAULT_CHANNEL,
+    database::row::{AssetMappingStatus, BundleRow, FileRow, VersionRow},
+};
+use axum::http::StatusCode;
+use serde_json::Value;
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn stats_split_new_and_reused_bytes() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+
+    let database = connect_database().await;
+    let versions = database.query_versions(None).await.unwrap();
+    let unique_bytes: i64 = database
+        .list_files()
+        .await
+        .unwrap()
+        .iter()
+        .map(|file| file.size)
+        .sum();
+
+    let (status, global): (_, Value) = env.get_json("/api/v1/stats").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(global["versionCount"], 2);
+    assert_eq!(global["uniqueBytes"], unique_bytes);
+    assert!(global["logicalBytes"].as_i64().unwrap() >= unique_bytes);
+    assert!(global["dedupeRatio"].as_f64().unwrap() >= 1.0);
+    let growth = global["growth"].as_array().unwrap();
+    assert_eq!(growth.len(), 2);
+    assert_eq!(growth[1]["cumulativeUniqueBytes"], unique_bytes);
+
+    let (_, first): (_, Value) = env
+        .get_json(&format!("/api/v1/version/{}/stats", versions[0].id))
+        .await;
+    assert_eq!(first["newBytes"], first["totalSize"]);
+    assert_eq!(first["reusedBytes"], 0);
+
+    let (_, second): (_, Value) = env
+        .get_json(&format!("/api/v1/version/{}/stats", versions[1].id))
+        .await;
+    assert_eq!(
+        second["newBytes"].as_i64().unwrap() + second["reusedBytes"].as_i64().unwrap(),
+        second["totalSize"].as_i64().unwrap()
+    );
+    assert_eq!(second["newBytes"], growth[1]["newBytes"]);
+    assert!(
+        second["largestNewBundles"].as_array().unwrap().len()
+            <= usize::try_from(second["newBundleCount"].as_i64().unwrap()).unwrap()
+    );
+
+    // Two bundles of one version sharing a new file count its bytes once.
+    let version_id = database
+        .create_version(VersionRow {
+            id: None,
+            channel: DEFAULT_CHANNEL.to_string(),
+            res: "e2e-shared-file".to_string(),
+            client: "0.0.0".to_string(),
+            is_ready: true,
+            asset_mapping_status: AssetMappingStatus::Pending,
+            hot_update_list: "{}".to_string(),
+        })
+        .await
+        .unwrap();
+    let file_id = database
+        .create_file(FileRow {
+            id: None,
+            hash: "f".repeat(64),
+            size: 1000,
+        })
+        .await
+        .unwrap();
+    for path in ["shared/a.ab", "shared/b.ab"] {
+        database
+            .create_bundle(BundleRow {
+                id: None,
+                path: path.to_string(),
+                version_id,
+                file_id,
+                upstream_hash: None,
+                upstream_md5: None,
+            })
+            .await
+            .unwrap();
+    }
+    let (_, shared): (_, Value) = env
+        .get_json(&format!("/api/v1/version/{version_id}/stats"))
+        .await;
+    assert_eq!(shared["totalSize"], 2000);
+    assert_eq!(shared["newBundleCount"], 2);
+    assert_eq!(shared["newBytes"], 1000);
+    assert_eq!(shared["reusedBytes"], 1000);
+
+    let (status, _): (_, Value) = env.get_json("/api/v1/version/999999/stats").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
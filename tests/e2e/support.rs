@@ -1,22 +1,28 @@
-use ak_asset_storage::database::{
-    Database,
-    bundle::BundleFilter,
-    model::{AssetMappingDetails, ManifestNode},
-    row::{AssetMappingStatus, VersionRow},
+use ak_asset_storage::{
+    config::DEFAULT_CHANNEL,
+    database::{
+        Database,
+        bundle::{BundleFilter, BundleSort},
+        model::{AssetMappingDetails, ManifestNode},
+        row::{AssetMappingStatus, VersionRow},
+    },
+    service::types::dat_file_name,
 };
 use axum::{
     Router,
+    body::Body,
     extract::{Path, State},
     http::StatusCode,
     routing::get,
 };
+use futures::stream;
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use std::{
     collections::{HashMap, HashSet},
     fs,
     path::{Path as StdPath, PathBuf},
     process::Stdio,
-    sync::Arc,
+    sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
 use tokio::{
@@ -30,8 +36,6 @@ type TestResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
 
 const SERVER_PORT: u16 = 25150;
 const FAKE_AK_PORT: u16 = 25151;
-const BUCKET_NAME: &str = "ak-asset-storage-e2e";
-const RC_ALIAS_NAME: &str = "ak-asset-storage-e2e";
 const DATABASE_NAME: &str = "ak_asset_storage_e2e";
 const DATABASE_URI: &str = "postgres://ak:ak@localhost:25432/ak_asset_storage_e2e";
 const POSTGRES_ADMIN_URI: &str = "postgres://ak:ak@localhost:25432/postgres";
@@ -58,13 +62,38 @@ pub struct TestEnv {
     runtime_dir: PathBuf,
     config_path: PathBuf,
     client: reqwest::Client,
+    fake_ak: Arc<FakeAkState>,
     fake_ak_task: JoinHandle<()>,
     server: Option<Child>,
 }
 
-#[derive(Debug, Clone)]
+/// How the fake AK server misbehaves when serving one asset file.
+#[derive(Debug, Clone, Copy)]
+pub enum AssetFault {
+    /// Serves the file with its middle bytes flipped, at the right length.
+    Corrupt,
+    /// Serves the file without its last byte.
+    Truncated,
+    /// Answers 503 to the next `times` requests, then serves the file.
+    Unavailable { times: usize },
+    /// Streams the file in eight chunks with `chunk_delay` between them.
+    Slow { chunk_delay: Duration },
+}
+
+#[derive(Debug)]
 struct FakeAkState {
     versions: HashMap<String, FixtureVersion>,
+    faults: Mutex<HashMap<String, AssetFault>>,
+    /// Asset file requests answered per server segment of the URL.
+    downloads: Mutex<HashMap<String, usize>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub total: i64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -94,16 +123,24 @@ pub struct BundleDetails {
     pub path: String,
     pub file_id: i32,
     pub file_hash: String,
-    pub file_size: i32,
+    pub file_size: i64,
     pub version_id: i32,
     pub version_res: String,
     pub version_client: String,
     pub version_is_ready: bool,
+    pub pid: Option<String>,
 }
 
 impl TestEnv {
     pub async fn bootstrap() -> Self {
-        let (mut env, config_path) = Self::bootstrap_common().await;
+        Self::bootstrap_with_channels(&[]).await
+    }
+
+    /// Starts the server with one `[[ak.channels]]` entry per `(name, server)`
+    /// pair instead of the legacy single channel. Every server of the fake AK
+    /// API serves the same fixture versions.
+    pub async fn bootstrap_with_channels(channels: &[(&str, &str)]) -> Self {
+        let (mut env, config_path) = Self::bootstrap_common(channels).await;
         let server = spawn_server(&config_path).await;
         wait_for_http_ok(&format!("http://127.0.0.1:{SERVER_PORT}/api/v1/_health")).await;
 
@@ -112,11 +149,11 @@ impl TestEnv {
     }
 
     pub async fn bootstrap_worker() -> Self {
-        let (env, _config_path) = Self::bootstrap_common().await;
+        let (env, _config_path) = Self::bootstrap_common(&[]).await;
         env
     }
 
-    async fn bootstrap_common() -> (Self, PathBuf) {
+    async fn bootstrap_common(channels: &[(&str, &str)]) -> (Self, PathBuf) {
         install_rustls_provider();
         let repo_root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
         let runtime_dir = repo_root.join("e2e/runtime");
@@ -129,16 +166,16 @@ impl TestEnv {
 
         let fixture = load_fixture(&repo_root);
         ensure_dependencies_ready(&repo_root).await;
-        recreate_bucket(&repo_root).await;
 
-        let fake_ak_task = spawn_fake_ak_server(fixture.clone()).await;
-        let config_path = write_config(&runtime_dir, &asset_dir).unwrap();
+        let (fake_ak, fake_ak_task) = spawn_fake_ak_server(fixture.clone()).await;
+        let config_path = write_config(&runtime_dir, &asset_dir, channels).unwrap();
 
         let env = Self {
             fixture,
             runtime_dir,
             config_path: config_path.clone(),
             client: reqwest::Client::new(),
+            fake_ak,
             fake_ak_task,
             server: None,
         };
@@ -153,13 +190,47 @@ impl TestEnv {
         &self.runtime_dir
     }
 
+    /// Makes the fake AK server misbehave whenever it serves `bundle_name`.
+    pub fn set_asset_fault(&self, bundle_name: &str, fault: AssetFault) {
+        self.fake_ak
+            .faults
+            .lock()
+            .unwrap()
+            .insert(dat_file_name(bundle_name), fault);
+    }
+
+    /// Number of asset file requests the fake AK server answered for `server`.
+    pub fn asset_downloads(&self, server: &str) -> usize {
+        self.fake_ak
+            .downloads
+            .lock()
+            .unwrap()
+            .get(server)
+            .copied()
+            .unwrap_or_default()
+    }
+
     pub async fn run_seed(&self) {
+        self.run_seed_file(
+            &PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("e2e/fixtures/versions.csv"),
+        )
+        .await;
+    }
+
+    /// Seeds from `csv` rows of `res,client[,channel]`.
+    pub async fn run_seed_csv(&self, csv: &str) {
+        let csv_path = self.runtime_dir.join("versions.csv");
+        fs::write(&csv_path, csv).unwrap();
+        self.run_seed_file(&csv_path).await;
+    }
+
+    async fn run_seed_file(&self, csv_path: &StdPath) {
         let status = build_binary_command()
             .arg("seed")
             .arg("-c")
             .arg(&self.config_path)
             .arg("--csv-path")
-            .arg(PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("e2e/fixtures/versions.csv"))
+            .arg(csv_path)
             .arg("--concurrent")
             .arg("1")
             .stdout(Stdio::inherit())
@@ -170,6 +241,119 @@ impl TestEnv {
         assert!(status.success(), "seed command failed: {status}");
     }
 
+    pub async fn run_audit(&self, args: &[&str]) -> (bool, serde_json::Value) {
+        let report_path = self.runtime_dir.join("audit.json");
+        let status = build_binary_command()
+            .arg("audit")
+            .arg("-c")
+            .arg(&self.config_path)
+            .arg("--output")
+            .arg(&report_path)
+            .args(args)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .await
+            .unwrap();
+        let report = serde_json::from_str(&fs::read_to_string(&report_path).unwrap()).unwrap();
+        (status.success(), report)
+    }
+
+    pub async fn run_delete_version(&self, res_version: &str) {
+        let status = build_binary_command()
+            .arg("delete-version")
+            .arg("-c")
+            .arg(&self.config_path)
+            .arg("--res-version")
+            .arg(res_version)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .await
+            .unwrap();
+        assert!(status.success(), "delete-version command failed: {status}");
+    }
+
+    /// Creates an API key and returns it from the command's last output line.
+    pub async fn run_create_api_key(&self, name: &str, scopes: &[&str]) -> String {
+        let mut command = build_binary_command();
+        command
+            .arg("create-api-key")
+            .arg("-c")
+            .arg(&self.config_path)
+            .arg("--name")
+            .arg(name);
+        for scope in scopes {
+            command.arg("--scope").arg(scope);
+        }
+        let output = command.stderr(Stdio::inherit()).output().await.unwrap();
+        assert!(
+            output.status.success(),
+            "create-api-key command failed: {}",
+            output.status
+        );
+        String::from_utf8(output.stdout)
+            .unwrap()
+            .lines()
+            .last()
+            .unwrap()
+            .to_string()
+    }
+
+    pub async fn run_revoke_api_key(&self, name: &str) {
+        let status = build_binary_command()
+            .arg("revoke-api-key")
+            .arg("-c")
+            .arg(&self.config_path)
+            .arg("--name")
+            .arg(name)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .await
+            .unwrap();
+        assert!(status.success(), "revoke-api-key command failed: {status}");
+    }
+
+    pub async fn run_gc(&self, dry_run: bool) -> serde_json::Value {
+        let report_path = self.runtime_dir.join("gc.json");
+        let mut command = build_binary_command();
+        command
+            .arg("gc")
+            .arg("-c")
+            .arg(&self.config_path)
+            .arg("--output")
+            .arg(&report_path);
+        if dry_run {
+            command.arg("--dry-run");
+        }
+        let status = command
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .await
+            .unwrap();
+        assert!(status.success(), "gc command failed: {status}");
+        serde_json::from_str(&fs::read_to_string(&report_path).unwrap()).unwrap()
+    }
+
+    pub async fn run_replicate(&self) {
+        let status = build_binary_command()
+            .arg("replicate")
+            .arg("-c")
+            .arg(&self.config_path)
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .status()
+            .await
+            .unwrap();
+        assert!(status.success(), "replicate command failed: {status}");
+    }
+
+    pub fn count_replica_objects(&self) -> usize {
+        count_files(&self.runtime_dir.join("replica"))
+    }
+
     pub async fn run_import_manifest(&self, res_version: &str) {
         let status = build_binary_command()
             .arg("import-manifest")
@@ -218,6 +402,7 @@ impl TestEnv {
         database
             .create_version(VersionRow {
                 id: None,
+                channel: DEFAULT_CHANNEL.to_string(),
                 res: version.res_version.clone(),
                 client: version.client_version.clone(),
                 is_ready,
@@ -251,6 +436,28 @@ impl TestEnv {
         (status, body)
     }
 
+    /// Collects every item of a paginated list endpoint by following
+    /// `nextCursor` until the last page.
+    pub async fn get_all_pages<T: DeserializeOwned>(&self, path: &str) -> Vec<T> {
+        let separator = if path.contains('?') { '&' } else { '?' };
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+        loop {
+            let url = cursor.as_ref().map_or_else(
+                || path.to_string(),
+                |cursor| format!("{path}{separator}cursor={cursor}"),
+            );
+            let (status, page): (_, Page<T>) = self.get_json(&url).await;
+            assert_eq!(status, StatusCode::OK);
+            items.extend(page.items);
+            if page.next_cursor.is_none() {
+                assert_eq!(i64::try_from(items.len()).unwrap(), page.total);
+                return items;
+            }
+            cursor = page.next_cursor;
+        }
+    }
+
     pub async fn get_text(&self, path: &str) -> (StatusCode, String) {
         let response = self
             .client
@@ -263,9 +470,56 @@ impl TestEnv {
         (status, body)
     }
 
+    pub async fn get_status(&self, path: &str, token: Option<&str>) -> StatusCode {
+        let mut request = self
+            .client
+            .get(format!("http://127.0.0.1:{SERVER_PORT}{path}"));
+        if let Some(token) = token {
+            request = request.header("torappu-auth", token);
+        }
+        request.send().await.unwrap().status()
+    }
+
+    pub async fn delete(&self, path: &str, token: Option<&str>) -> StatusCode {
+        let mut request = self
+            .client
+            .delete(format!("http://127.0.0.1:{SERVER_PORT}{path}"));
+        if let Some(token) = token {
+            request = request.header("torappu-auth", token);
+        }
+        request.send().await.unwrap().status()
+    }
+
+    pub async fn post_json<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        token: Option<&str>,
+    ) -> (StatusCode, T) {
+        let mut request = self
+            .client
+            .post(format!("http://127.0.0.1:{SERVER_PORT}{path}"));
+        if let Some(token) = token {
+            request = request.header("torappu-auth", token);
+        }
+        let response = request.send().await.unwrap();
+        let status = response.status();
+        let body = response.json().await.unwrap();
+        (status, body)
+    }
+
+    pub async fn get_response(&self, path: &str, range: Option<&str>) -> reqwest::Response {
+        let mut request = self
+            .client
+            .get(format!("http://127.0.0.1:{SERVER_PORT}{path}"));
+        if let Some(range) = range {
+            request = request.header(reqwest::header::RANGE, range);
+        }
+        request.send().await.unwrap()
+    }
+
     pub async fn assert_database_state(&self) {
         let database = connect_database().await;
-        let versions = database.query_versions().await.unwrap();
+        let versions = database.query_versions(None).await.unwrap();
         let bundles = database
             .query_bundles_with_details(&all_bundles_filter())
             .await
@@ -284,19 +538,8 @@ impl TestEnv {
         assert!(file_id_by_hash.values().all(|ids| ids.len() == 1));
     }
 
-    pub async fn assert_s3_state(&self) {
-        let output = Command::new("rc")
-            .arg("object")
-            .arg("list")
-            .arg("--recursive")
-            .arg(format!("{RC_ALIAS_NAME}/{BUCKET_NAME}"))
-            .stdout(Stdio::piped())
-            .stderr(Stdio::inherit())
-            .output()
-            .await
-            .unwrap();
-        assert!(output.status.success(), "rc object list failed");
-        let stdout = String::from_utf8(output.stdout).unwrap();
+    pub async fn assert_storage_state(&self) {
+        let objects = count_files(&self.runtime_dir.join("objects"));
 
         let database = connect_database().await;
         let bundles = database
@@ -308,14 +551,22 @@ impl TestEnv {
             bundles.into_iter().map(|bundle| bundle.file_hash).collect();
 
         assert!(
-            stdout.lines().count() >= unique_hashes.len(),
-            "expected at least {} S3 objects, got {}\n{stdout}",
-            unique_hashes.len(),
-            stdout.lines().count()
+            objects >= unique_hashes.len(),
+            "expected at least {} stored objects, got {objects}",
+            unique_hashes.len()
         );
     }
 }
 
+pub fn count_files(dir: &StdPath) -> usize {
+    fs::read_dir(dir).map_or(0, |entries| {
+        entries
+            .map(|entry| entry.unwrap().path())
+            .map(|path| if path.is_dir() { count_files(&path) } else { 1 })
+            .sum()
+    })
+}
+
 impl Drop for TestEnv {
     fn drop(&mut self) {
         if let Some(ref mut server) = self.server {
@@ -374,7 +625,6 @@ async fn ensure_dependencies_ready(repo_root: &StdPath) {
         .arg("up")
         .arg("-d")
         .arg("db")
-        .arg("rustfs")
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
         .status()
@@ -383,55 +633,11 @@ async fn ensure_dependencies_ready(repo_root: &StdPath) {
     assert!(status.success(), "docker compose up failed");
 
     wait_for_postgres().await;
-    wait_for_rustfs().await;
     recreate_database(repo_root).await;
     let database = connect_database().await;
     database.migrate().await.unwrap();
 }
 
-async fn recreate_bucket(repo_root: &StdPath) {
-    let status = Command::new("rc")
-        .arg("alias")
-        .arg("set")
-        .arg(RC_ALIAS_NAME)
-        .arg("http://127.0.0.1:9000")
-        .arg("torappu")
-        .arg("torappu123")
-        .arg("--bucket-lookup")
-        .arg("path")
-        .current_dir(repo_root)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .status()
-        .await
-        .unwrap();
-    assert!(status.success(), "rc alias set failed");
-
-    let _ = Command::new("rc")
-        .arg("bucket")
-        .arg("remove")
-        .arg("--force")
-        .arg(format!("{RC_ALIAS_NAME}/{BUCKET_NAME}"))
-        .current_dir(repo_root)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::null())
-        .status()
-        .await;
-
-    let create_status = Command::new("rc")
-        .arg("bucket")
-        .arg("create")
-        .arg("--ignore-existing")
-        .arg(format!("{RC_ALIAS_NAME}/{BUCKET_NAME}"))
-        .current_dir(repo_root)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .status()
-        .await
-        .unwrap();
-    assert!(create_status.success(), "rc bucket create failed");
-}
-
 pub async fn connect_database() -> Database {
     Database::connect(&ak_asset_storage::config::DatabaseConfig {
         uri: DATABASE_URI.to_string(),
@@ -448,12 +654,22 @@ fn all_bundles_filter() -> BundleFilter {
         hash: None,
         file: None,
         version: None,
+        channel: None,
+        md5: None,
+        upstream_hash: None,
+        pid: None,
+        bundle_type: None,
+        cid: None,
+        min_total_size: None,
+        max_total_size: None,
+        sort: BundleSort::Id,
+        descending: false,
     }
 }
 
 pub async fn wait_for_ready_version(database: &Database, timeout: Duration) -> TestResult<()> {
     wait_for(timeout, Duration::from_secs(1), || async {
-        match database.query_versions().await {
+        match database.query_versions(None).await {
             Ok(versions) => versions.into_iter().any(|version| version.is_ready),
             Err(_) => false,
         }
@@ -469,7 +685,7 @@ pub async fn wait_for_asset_mapping_status(
     timeout: Duration,
 ) -> TestResult<()> {
     wait_for(timeout, Duration::from_secs(1), || async {
-        match database.get_version_by_res(res_version).await {
+        match database.get_version_by_res(DEFAULT_CHANNEL, res_version).await {
             Ok(Some(version)) => version.asset_mapping_status == expected_status,
             Ok(None) => false,
             Err(_) => false,
@@ -561,13 +777,22 @@ pub async fn assert_manifest_fixture_imported(database: &Database, version_id: i
         Some("level_a001_01"),
     );
 
-    assert_manifest_children(
-        &database.search_manifest(version_id, "amiya").await.unwrap(),
-        &[(
+    let (hits, total) = database
+        .search_manifest(Some(version_id), "amiya", None, 200)
+        .await
+        .unwrap();
+    assert_eq!(total, 1);
+    let hits: Vec<(&str, &str, &str)> = hits
+        .iter()
+        .map(|hit| (hit.name.as_str(), hit.path.as_str(), hit.node_type.as_str()))
+        .collect();
+    assert_eq!(
+        hits,
+        [(
             "char_002_amiya_1",
             "arts/charportraits/char_002_amiya_1",
             "file",
-        )],
+        )]
     );
 }
 
@@ -604,7 +829,35 @@ fn docker_compose_exec_psql(repo_root: &StdPath, statement: &str) -> Command {
     cmd
 }
 
-fn write_config(runtime_dir: &StdPath, asset_dir: &StdPath) -> std::io::Result<PathBuf> {
+fn write_config(
+    runtime_dir: &StdPath,
+    asset_dir: &StdPath,
+    channels: &[(&str, &str)],
+) -> std::io::Result<PathBuf> {
+    let ak = if channels.is_empty() {
+        format!(
+            r#"[ak]
+asset_url = "http://127.0.0.1:{FAKE_AK_PORT}/assetbundle/official/Android/assets"
+conf_url = "http://127.0.0.1:{FAKE_AK_PORT}/config/prod/official/Android"
+"#
+        )
+    } else {
+        channels
+            .iter()
+            .map(|(name, server)| {
+                format!(
+                    r#"[[ak.channels]]
+name = "{name}"
+server = "{server}"
+platform = "Android"
+asset_url = "http://127.0.0.1:{FAKE_AK_PORT}/assetbundle/{server}/Android/assets"
+conf_url = "http://127.0.0.1:{FAKE_AK_PORT}/config/prod/{server}/Android"
+"#
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
     let config = format!(
         r#"[logger]
 enable = true
@@ -619,16 +872,15 @@ host = "http://127.0.0.1:{SERVER_PORT}"
 [database]
 uri = "{DATABASE_URI}"
 
-[ak]
-asset_url = "http://127.0.0.1:{FAKE_AK_PORT}/assetbundle/official/Android/assets"
-conf_url = "http://127.0.0.1:{FAKE_AK_PORT}/config/prod/official/Android"
+{ak}
+[storage]
+backend = "local"
+path = "{}"
 
-[s3]
-endpoint = "http://127.0.0.1:9000"
-bucket_name = "{BUCKET_NAME}"
-access_key_id = "torappu"
-secret_access_key = "torappu123"
-with_virtual_hosted_style_request = false
+[[storage.replicas]]
+name = "mirror"
+backend = "local"
+path = "{}"
 
 [sentry]
 dsn = "https://public@example.com/1"
@@ -638,6 +890,8 @@ traces_sample_rate = 0.0
 token = "e2e-token"
 asset_base_path = "{}"
 "#,
+        runtime_dir.join("objects").display(),
+        runtime_dir.join("replica").display(),
         asset_dir.display()
     );
 
@@ -646,26 +900,30 @@ asset_base_path = "{}"
     Ok(config_path)
 }
 
-async fn spawn_fake_ak_server(fixture: Fixture) -> JoinHandle<()> {
+async fn spawn_fake_ak_server(fixture: Fixture) -> (Arc<FakeAkState>, JoinHandle<()>) {
     let versions: HashMap<String, FixtureVersion> = fixture
         .versions
         .into_iter()
         .map(|version| (version.res_version.clone(), version))
         .collect();
 
-    let state = Arc::new(FakeAkState { versions });
+    let state = Arc::new(FakeAkState {
+        versions,
+        faults: Mutex::default(),
+        downloads: Mutex::default(),
+    });
 
     let router = Router::new()
-        .route("/config/prod/official/Android/version", get(fake_version))
+        .route("/config/prod/{server}/Android/version", get(fake_version))
         .route(
-            "/assetbundle/official/Android/assets/{res_version}/hot_update_list.json",
+            "/assetbundle/{server}/Android/assets/{res_version}/hot_update_list.json",
             get(fake_hot_update_list),
         )
         .route(
-            "/assetbundle/official/Android/assets/{res_version}/{*file_path}",
+            "/assetbundle/{server}/Android/assets/{res_version}/{*file_path}",
             get(fake_asset),
         )
-        .with_state(state);
+        .with_state(state.clone());
 
     let listener = TcpListener::bind(("127.0.0.1", FAKE_AK_PORT))
         .await
@@ -679,7 +937,7 @@ async fn spawn_fake_ak_server(fixture: Fixture) -> JoinHandle<()> {
     ))
     .await;
 
-    handle
+    (state, handle)
 }
 
 async fn fake_version(
@@ -699,7 +957,7 @@ async fn fake_version(
 
 async fn fake_hot_update_list(
     State(state): State<Arc<FakeAkState>>,
-    Path(res_version): Path<String>,
+    Path((_server, res_version)): Path<(String, String)>,
 ) -> Result<String, StatusCode> {
     state
         .versions
@@ -710,13 +968,54 @@ async fn fake_hot_update_list(
 
 async fn fake_asset(
     State(state): State<Arc<FakeAkState>>,
-    Path((res_version, file_path)): Path<(String, String)>,
-) -> Result<Vec<u8>, StatusCode> {
+    Path((server, res_version, file_path)): Path<(String, String, String)>,
+) -> Result<Body, StatusCode> {
     let version = state
         .versions
         .get(&res_version)
         .ok_or(StatusCode::NOT_FOUND)?;
-    fs::read(version.root.join(file_path)).map_err(|_| StatusCode::NOT_FOUND)
+    let bytes = fs::read(version.root.join(&file_path)).map_err(|_| StatusCode::NOT_FOUND)?;
+    *state.downloads.lock().unwrap().entry(server).or_default() += 1;
+
+    let fault = match state.faults.lock().unwrap().get_mut(&file_path) {
+        Some(AssetFault::Unavailable { times }) if *times > 0 => {
+            *times -= 1;
+            return Err(StatusCode::SERVICE_UNAVAILABLE);
+        }
+        Some(AssetFault::Unavailable { .. }) | None => None,
+        Some(fault) => Some(*fault),
+    };
+    match fault {
+        None | Some(AssetFault::Unavailable { .. }) => Ok(Body::from(bytes)),
+        Some(AssetFault::Corrupt) => {
+            let mut bytes = bytes;
+            let middle = bytes.len() / 2;
+            for byte in bytes.iter_mut().skip(middle).take(64) {
+                *byte = !*byte;
+            }
+            Ok(Body::from(bytes))
+        }
+        Some(AssetFault::Truncated) => {
+            let mut bytes = bytes;
+            bytes.pop();
+            Ok(Body::from(bytes))
+        }
+        Some(AssetFault::Slow { chunk_delay }) => {
+            let chunk_size = bytes.len().div_ceil(8).max(1);
+            Ok(Body::from_stream(stream::unfold(
+                (bytes, 0),
+                move |(bytes, offset)| async move {
+                    if offset >= bytes.len() {
+                        return None;
+                    }
+                    sleep(chunk_delay).await;
+                    let end = (offset + chunk_size).min(bytes.len());
+                    let chunk = bytes[offset..end].to_vec();
+                    Some((Ok::<_, std::io::Error>(chunk), (bytes, end)))
+                },
+            )))
+        }
+    }
 }
 
 async fn spawn_server(config_path: &StdPath) -> Child {
@@ -777,12 +1076,6 @@ async fn wait_for_postgres() {
     .expect("postgres did not become ready");
 }
 
-async fn wait_for_rustfs() {
-    wait_for_http_success("http://127.0.0.1:9000/health")
-        .await
-        .expect("rustfs did not become ready");
-}
-
 async fn wait_for_http_ok(url: &str) {
     wait_for_http_success(url)
         .await
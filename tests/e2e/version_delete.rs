@@ -0,0 +1,84 @@
+use crate::support::{self, AssetFault, TestEnv, connect_database};
+use axum::http::StatusCode;
+use std::time::Duration;
+use tokio::time::{Instant, sleep};
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn deleted_versions_are_garbage_collected() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+    let token = env.run_create_api_key("e2e-admin", &["admin:write"]).await;
+
+    let database = connect_database().await;
+    let versions = database.query_versions(None).await.unwrap();
+    let total_bytes: i64 = database
+        .list_files()
+        .await
+        .unwrap()
+        .iter()
+        .map(|file| file.size)
+        .sum();
+
+    let path = format!("/api/v1/version/{}", versions[1].id);
+    assert_eq!(env.delete(&path, None).await, StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        env.delete(&path, Some("wrong-token")).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(env.delete(&path, Some(&token)).await, StatusCode::OK);
+    assert_eq!(env.delete(&path, Some(&token)).await, StatusCode::NOT_FOUND);
+
+    env.run_delete_version(&versions[0].res_version).await;
+    assert!(database.query_versions(None).await.unwrap().is_empty());
+
+    let report = env.run_gc(true).await;
+    assert_eq!(report["bytes"], total_bytes);
+    assert_eq!(
+        database.list_files().await.unwrap().len() as u64,
+        report["files"].as_u64().unwrap()
+    );
+
+    let report = env.run_gc(false).await;
+    assert_eq!(report["bytes"], total_bytes);
+    assert!(database.list_files().await.unwrap().is_empty());
+
+    let (success, report) = env.run_audit(&[]).await;
+    assert!(success, "audit failed after gc: {report}");
+    assert_eq!(report["checkedObjects"], 0);
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn downloading_versions_cannot_be_deleted() {
+    let env = TestEnv::bootstrap().await;
+    let token = env.run_create_api_key("e2e-admin", &["admin:write"]).await;
+    let latest = env.fixture.versions.last().unwrap();
+    env.set_asset_fault(
+        &latest.bundle_names[0],
+        AssetFault::Slow {
+            chunk_delay: Duration::from_secs(2),
+        },
+    );
+    let mut worker = support::spawn_worker(env.config_path(), 3600).await;
+
+    let database = connect_database().await;
+    let deadline = Instant::now() + Duration::from_mins(1);
+    while !database.has_active_download_jobs().await.unwrap() {
+        assert!(
+            Instant::now() < deadline,
+            "worker did not start downloading"
+        );
+        sleep(Duration::from_millis(200)).await;
+    }
+    let version = database.query_versions(None).await.unwrap().remove(0);
+    let path = format!("/api/v1/version/{}", version.id);
+    assert_eq!(env.delete(&path, Some(&token)).await, StatusCode::CONFLICT);
+
+    support::wait_for_ready_version(&database, Duration::from_mins(1))
+        .await
+        .unwrap();
+    let _ = worker.start_kill();
+    assert_eq!(env.delete(&path, Some(&token)).await, StatusCode::OK);
+}
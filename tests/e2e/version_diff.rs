@@ -0,0 +1,59 @@
+use crate::support::{TestEnv, VersionSummary};
+use axum::http::StatusCode;
+
+fn paths(entries: &serde_json::Value) -> Vec<&str> {
+    entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|entry| entry["path"].as_str().unwrap())
+        .collect()
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn diff_between_seeded_versions() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+
+    let mut versions: Vec<VersionSummary> = env.get_all_pages("/api/v1/version").await;
+    versions.sort_by_key(|version| version.id);
+    let (from, to) = (versions.first().unwrap().id, versions.last().unwrap().id);
+
+    let (status, diff): (_, serde_json::Value) = env
+        .get_json(&format!("/api/v1/diff?from={from}&to={to}"))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(diff["fromVersionId"], from);
+    assert_eq!(diff["toVersionId"], to);
+    assert_eq!(
+        paths(&diff["added"]),
+        [
+            "arts/charportraits/portraits_hub.ab",
+            "scenes/activities/a001/level_a001_01/level_a001_01.ab",
+            "scenes/activities/a001/level_a001_01/level_a001_01/lightingdata.ab",
+        ]
+    );
+    assert_eq!(
+        paths(&diff["removed"]),
+        ["refs/fx/texture/overseas/live2d/dyn_char_1037_amiya3_sale#13.ab"]
+    );
+    assert!(paths(&diff["changed"]).is_empty());
+
+    for entry in diff["added"].as_array().unwrap() {
+        assert!(entry["oldFileId"].is_null());
+        assert_eq!(entry["sizeDelta"], entry["newSize"]);
+    }
+    let removed = &diff["removed"][0];
+    assert!(removed["newFileId"].is_null());
+    assert_eq!(
+        removed["sizeDelta"].as_i64().unwrap(),
+        -removed["oldSize"].as_i64().unwrap()
+    );
+
+    let (status, _): (_, serde_json::Value) = env
+        .get_json(&format!("/api/v1/diff?from={from}&to=-1"))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
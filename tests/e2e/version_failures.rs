@@ -0,0 +1,81 @@
+use crate::support::{AssetFault, BundleDetails, TestEnv};
+use axum::http::StatusCode;
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BundleFailure {
+    path: String,
+    attempts: i32,
+    last_error: String,
+    first_failed_at: DateTime<Utc>,
+    last_failed_at: DateTime<Utc>,
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn seeded_versions_have_no_failures() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+
+    let versions: Vec<serde_json::Value> = env.get_all_pages("/api/v1/version").await;
+    for version in &versions {
+        assert_eq!(version["downloadStatus"], "ready");
+
+        let (status, failures): (_, serde_json::Value) = env
+            .get_json(&format!("/api/v1/version/{}/failures", version["id"]))
+            .await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(failures.as_array().unwrap().is_empty());
+    }
+
+    let (status, _): (_, serde_json::Value) = env.get_json("/api/v1/version/-1/failures").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn failing_bundles_back_off_and_leave_the_version_partial() {
+    const BROKEN_BUNDLE: &str = "arts/charportraits/portraits_hub.ab";
+    const FLAKY_BUNDLE: &str = "scenes/activities/a001/level_a001_01/level_a001_01.ab";
+
+    let env = TestEnv::bootstrap().await;
+    env.set_asset_fault(BROKEN_BUNDLE, AssetFault::Unavailable { times: usize::MAX });
+    env.set_asset_fault(FLAKY_BUNDLE, AssetFault::Unavailable { times: 2 });
+
+    env.run_seed().await;
+
+    let versions: Vec<serde_json::Value> = env.get_all_pages("/api/v1/version").await;
+    let (latest, earlier): (Vec<_>, Vec<_>) = versions.iter().partition(|version| {
+        version["resVersion"] == env.fixture.versions.last().unwrap().res_version.as_str()
+    });
+    assert!(
+        earlier
+            .iter()
+            .all(|version| version["downloadStatus"] == "ready")
+    );
+    let latest = latest.first().unwrap();
+    assert_eq!(latest["downloadStatus"], "partial");
+
+    // The flaky bundle recovered on its third attempt and left no failure.
+    let (status, failures): (_, Vec<BundleFailure>) = env
+        .get_json(&format!("/api/v1/version/{}/failures", latest["id"]))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(failures.len(), 1);
+    let failure = &failures[0];
+    assert_eq!(failure.path, BROKEN_BUNDLE);
+    assert_eq!(failure.attempts, 5);
+    assert!(failure.last_error.contains("503"));
+    // Four retries, waiting 2s, 4s, 8s and 16s.
+    assert!(
+        failure.last_failed_at - failure.first_failed_at >= TimeDelta::seconds(30),
+        "retries did not back off: {failure:?}"
+    );
+
+    let bundles: Vec<BundleDetails> = env.get_all_pages("/api/v1/bundle").await;
+    assert!(bundles.iter().any(|bundle| bundle.path == FLAKY_BUNDLE));
+    assert!(bundles.iter().all(|bundle| bundle.path != BROKEN_BUNDLE));
+}
@@ -0,0 +1,92 @@
+use crate::support::{TestEnv, VersionSummary, connect_database};
+use ak_asset_storage::{
+    config::DEFAULT_CHANNEL,
+    database::row::{AssetMappingStatus, VersionRow},
+};
+use axum::http::StatusCode;
+use serde_json::{Value, json};
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn packs_are_listed_per_version() {
+    let env = TestEnv::bootstrap().await;
+
+    let hot_update_list = json!({
+        "versionId": "99-99-99-99-99-99-pack00",
+        "abInfos": [
+            {"name": "a.ab", "hash": "h1", "md5": "m1", "totalSize": 10, "abSize": 8, "pid": "lang_en"},
+            {"name": "b.ab", "hash": "h2", "md5": "m2", "totalSize": 20, "abSize": 16, "pid": "lang_en"},
+            {"name": "c.ab", "hash": "h3", "md5": "m3", "totalSize": 30, "abSize": 24}
+        ],
+        "packInfos": [
+            {"name": "lang_en", "hash": "ph", "md5": "pm", "totalSize": 30, "abSize": 24, "cid": 1}
+        ]
+    });
+    let version_id = connect_database()
+        .await
+        .create_version(VersionRow {
+            id: None,
+            channel: DEFAULT_CHANNEL.to_string(),
+            res: "99-99-99-99-99-99-pack00".to_string(),
+            client: "9.9.9".to_string(),
+            is_ready: true,
+            asset_mapping_status: AssetMappingStatus::Pending,
+            hot_update_list: hot_update_list.to_string(),
+        })
+        .await
+        .unwrap();
+
+    let (status, packs): (_, Vec<Value>) = env
+        .get_json(&format!("/api/v1/version/{version_id}/packs"))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(packs.len(), 1);
+    assert_eq!(packs[0]["pid"], "lang_en");
+    assert_eq!(packs[0]["totalSize"], 30);
+    assert_eq!(packs[0]["bundleCount"], 2);
+
+    let (status, pack): (_, Value) = env
+        .get_json(&format!("/api/v1/version/{version_id}/packs/lang_en"))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let paths: Vec<&str> = pack["bundles"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|bundle| bundle["path"].as_str().unwrap())
+        .collect();
+    assert_eq!(paths, ["a.ab", "b.ab"]);
+
+    let (status, _) = env
+        .get_text(&format!("/api/v1/version/{version_id}/packs/missing"))
+        .await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn diff_summarizes_changes_per_pack() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+
+    let mut versions: Vec<VersionSummary> = env.get_all_pages("/api/v1/version").await;
+    versions.sort_by_key(|version| version.id);
+    let (from, to) = (versions.first().unwrap().id, versions.last().unwrap().id);
+
+    let (status, diff): (_, Value) = env
+        .get_json(&format!("/api/v1/diff?from={from}&to={to}"))
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    let count = |key: &str| diff[key].as_array().unwrap().len() as u64;
+    let packs = diff["packs"].as_array().unwrap();
+    let total = |key: &str| {
+        packs
+            .iter()
+            .map(|pack| pack[key].as_u64().unwrap())
+            .sum::<u64>()
+    };
+    assert_eq!(total("added"), count("added"));
+    assert_eq!(total("removed"), count("removed"));
+    assert_eq!(total("changed"), count("changed"));
+}
@@ -0,0 +1,61 @@
+use crate::support::TestEnv;
+use axum::http::StatusCode;
+use std::time::Duration;
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn seeded_versions_report_finished_progress() {
+    let env = TestEnv::bootstrap().await;
+
+    env.run_seed().await;
+
+    let versions: Vec<serde_json::Value> = env.get_all_pages("/api/v1/version").await;
+    for version in &versions {
+        let (status, progress): (_, serde_json::Value) = env
+            .get_json(&format!("/api/v1/version/{}/progress", version["id"]))
+            .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(progress["doneBundles"], progress["totalBundles"]);
+        assert_eq!(progress["failedBundles"], 0);
+        assert!(!progress["finishedAt"].is_null());
+        assert!(
+            progress["bytesStored"].as_i64().unwrap() <= progress["bytesFetched"].as_i64().unwrap()
+        );
+
+        let response = env
+            .get_response(
+                &format!("/api/v1/version/{}/progress/stream", version["id"]),
+                None,
+            )
+            .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.text().await.unwrap();
+        assert_eq!(body.matches("event: progress").count(), 1);
+    }
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn progress_stream_closes_without_an_active_run() {
+    let env = TestEnv::bootstrap().await;
+    let res_version = env.fixture.versions[0].res_version.clone();
+    let version_id = env
+        .create_version_for_manifest_test(&res_version, false)
+        .await;
+
+    let response = tokio::time::timeout(
+        Duration::from_secs(10),
+        env.get_response(
+            &format!("/api/v1/version/{version_id}/progress/stream"),
+            None,
+        ),
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = tokio::time::timeout(Duration::from_secs(10), response.text())
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!body.contains("event: progress"));
+}
@@ -0,0 +1,92 @@
+use crate::support::{self, TestEnv};
+use axum::http::StatusCode;
+use serde_json::Value;
+use std::time::Duration;
+use tokio::time::{Instant, sleep};
+
+async fn wait_for_leader(env: &TestEnv, pid: u32) -> Value {
+    let deadline = Instant::now() + Duration::from_mins(1);
+    loop {
+        let (status, leader): (_, Value) = env.get_json("/api/v1/worker/leader").await;
+        if status == StatusCode::OK
+            && leader["worker"]
+                .as_str()
+                .is_some_and(|worker| worker.ends_with(&format!(":{pid}")))
+        {
+            return leader;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "worker {pid} did not become leader"
+        );
+        sleep(Duration::from_millis(500)).await;
+    }
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn follower_takes_over_when_the_leader_stops() {
+    let env = TestEnv::bootstrap().await;
+
+    let (status, _): (_, Value) = env.get_json("/api/v1/worker/leader").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let mut leader = support::spawn_worker(env.config_path(), 3600).await;
+    let leader_pid = leader.id().unwrap();
+    let elected = wait_for_leader(&env, leader_pid).await;
+
+    let mut follower = support::spawn_worker(env.config_path(), 3600).await;
+    let follower_pid = follower.id().unwrap();
+    sleep(Duration::from_secs(10)).await;
+    let (status, current): (_, Value) = env.get_json("/api/v1/worker/leader").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(current, elected);
+
+    // Followers only work the download queue, which still reports them.
+    let (_, workers): (_, Value) = env.get_json("/api/v1/worker/status").await;
+    let follower_components: Vec<&str> = workers
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|worker| {
+            worker["worker"]
+                .as_str()
+                .is_some_and(|worker| worker.ends_with(&format!(":{follower_pid}")))
+        })
+        .map(|worker| worker["component"].as_str().unwrap())
+        .collect();
+    assert_eq!(follower_components, ["download_queue"]);
+
+    // The follower keeps retrying on the session it opened at startup.
+    let database = support::connect_database().await;
+    let (sessions, age): (i64, f64) = sqlx::query_as(
+        r"
+SELECT COUNT(*), EXTRACT(EPOCH FROM NOW() - MIN(backend_start))::float8
+FROM pg_stat_activity
+WHERE application_name = $1
+        ",
+    )
+    .bind(format!(
+        "{}:{follower_pid}",
+        current["worker"]
+            .as_str()
+            .unwrap()
+            .rsplit_once(':')
+            .unwrap()
+            .0
+    ))
+    .fetch_one(database.pool())
+    .await
+    .unwrap();
+    assert_eq!(sessions, 1);
+    assert!(age >= 8.0, "follower session is {age}s old");
+
+    leader.kill().await.unwrap();
+    let killed_at = chrono::Utc::now();
+    let takeover = wait_for_leader(&env, follower_pid).await;
+    let takeover_at: chrono::DateTime<chrono::Utc> =
+        takeover["electedAt"].as_str().unwrap().parse().unwrap();
+    assert!(takeover_at >= killed_at - chrono::Duration::seconds(1));
+
+    let _ = follower.start_kill();
+}
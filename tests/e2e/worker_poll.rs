@@ -2,7 +2,7 @@ use crate::support::{self, TestEnv};
 use std::time::Duration;
 
 #[tokio::test]
-#[ignore = "manual e2e test requiring docker, rc, and fixture assets"]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
 async fn worker_polls_new_version_and_downloads_assets() {
     let env = TestEnv::bootstrap_worker().await;
     let mut worker = support::spawn_worker(env.config_path(), 1).await;
@@ -14,7 +14,7 @@ async fn worker_polls_new_version_and_downloads_assets() {
 
     let _ = worker.start_kill();
 
-    let versions = database.query_versions().await.unwrap();
+    let versions = database.query_versions(None).await.unwrap();
     assert_eq!(versions.len(), 1);
 
     let bundles = database
@@ -31,5 +31,5 @@ async fn worker_polls_new_version_and_downloads_assets() {
         5
     );
 
-    env.assert_s3_state().await;
+    env.assert_storage_state().await;
 }
@@ -0,0 +1,106 @@
+use crate::support::{self, TestEnv};
+use axum::http::StatusCode;
+use serde_json::Value;
+use std::time::Duration;
+use tokio::time::{Instant, sleep};
+
+fn component<'a>(workers: &'a Value, name: &str) -> Option<&'a Value> {
+    workers
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|worker| worker["component"] == name)
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn health_fails_when_the_worker_dies() {
+    let env = TestEnv::bootstrap().await;
+
+    let (status, health): (_, Value) = env.get_json("/api/v1/_health").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(health["workers"], Value::Array(Vec::new()));
+
+    let mut worker = support::spawn_worker(env.config_path(), 1).await;
+    let deadline = Instant::now() + Duration::from_mins(1);
+    let workers = loop {
+        let (status, workers): (_, Value) = env.get_json("/api/v1/worker/status").await;
+        assert_eq!(status, StatusCode::OK);
+        let checked = component(&workers, "version_check")
+            .is_some_and(|check| !check["lastSuccessAt"].is_null());
+        if checked && workers.as_array().unwrap().len() == 4 {
+            break workers;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "worker did not report its status"
+        );
+        sleep(Duration::from_millis(500)).await;
+    };
+    let check = component(&workers, "version_check").unwrap();
+    assert_eq!(check["stale"], false);
+    assert!(check["lastError"].is_null());
+    assert!(component(&workers, "manifest_watcher").is_some());
+    assert!(component(&workers, "item_demand_watcher").is_some());
+    assert!(component(&workers, "download_queue").is_some());
+
+    let (status, health): (_, Value) = env.get_json("/api/v1/_health").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(health["ok"], true);
+    assert_eq!(health["database"], true);
+
+    worker.kill().await.unwrap();
+
+    let deadline = Instant::now() + Duration::from_mins(2);
+    let health = loop {
+        let (status, health): (_, Value) = env.get_json("/api/v1/_health").await;
+        if status == StatusCode::SERVICE_UNAVAILABLE {
+            break health;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "health check did not notice the dead worker"
+        );
+        sleep(Duration::from_secs(1)).await;
+    };
+    assert_eq!(health["ok"], false);
+    assert_eq!(health["database"], true);
+    assert_eq!(
+        component(&health["workers"], "manifest_watcher").unwrap()["stale"],
+        true
+    );
+}
+
+#[tokio::test]
+#[ignore = "manual e2e test requiring docker and fixture assets"]
+async fn health_fails_when_the_worker_shuts_down() {
+    let env = TestEnv::bootstrap().await;
+
+    let mut worker = support::spawn_worker(env.config_path(), 1).await;
+    let deadline = Instant::now() + Duration::from_mins(1);
+    loop {
+        let (status, health): (_, Value) = env.get_json("/api/v1/_health").await;
+        if status == StatusCode::OK && health["workers"].as_array().unwrap().len() == 4 {
+            break;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "worker did not report its status"
+        );
+        sleep(Duration::from_millis(500)).await;
+    }
+
+    let terminated = std::process::Command::new("kill")
+        .arg("-TERM")
+        .arg(worker.id().unwrap().to_string())
+        .status()
+        .unwrap();
+    assert!(terminated.success());
+    assert!(worker.wait().await.unwrap().success());
+
+    let (status, health): (_, Value) = env.get_json("/api/v1/_health").await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    let workers = health["workers"].as_array().unwrap();
+    assert_eq!(workers.len(), 4);
+    assert!(workers.iter().all(|worker| worker["stoppedAt"].is_string()));
+}